@@ -1,5 +1,7 @@
-use crate::{Deck, Card, TableState, Street, Rank, Suit};
+use crate::{Deck, Card, TableState, Street, PlayerAction};
 use serde::{Serialize, Deserialize};
+use texas_engine::history::HandEvent;
+use texas_engine::{GameConfig, GameStage, Player, TexasHoldem};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ApplyOutcome { Continue, NextStreet, HandEnded }
@@ -20,6 +22,8 @@ impl Seat {
     pub fn empty() -> Self { Self { user_id: None, stack: 0, hole: vec![], sitting_out: false, has_folded: false, is_allin: false, acted_in_round: false } }
 }
 
+/// A table of seats around texas-engine: the engine runs each hand and owns the betting rules,
+/// the public fields mirror its state for table snapshots.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Table {
     pub id: String,
@@ -32,11 +36,23 @@ pub struct Table {
     pub round_bet: u64,
     pub round_contrib: Vec<u64>,
     pub total_contrib: Vec<u64>,
+    /// seats whose hole cards were shown at the last showdown
+    #[serde(default)]
+    pub revealed: Vec<bool>,
+    /// the hand in progress; its history never goes out with table snapshots
+    #[serde(skip)]
+    pub game: Option<TexasHoldem>,
+    /// seat index of each player in `game`
+    #[serde(skip)]
+    pub hand_seats: Vec<usize>,
+    /// events of the last finished hand, kept until `take_finished_hand`
+    #[serde(skip)]
+    pub finished_hand: Option<Vec<HandEvent>>,
 }
 
 impl Table {
     pub fn new(id: String, max_seats: usize, sb: u64, bb: u64) -> Self {
-        Self { id, max_seats, seats: (0..max_seats).map(|_| Seat::empty()).collect(), dealer_idx: 0, to_act_idx: 0, board: vec![], state: TableState { small_blind: sb, big_blind: bb, pot: 0, street: None }, round_bet: 0, round_contrib: vec![0; max_seats], total_contrib: vec![0; max_seats] }
+        Self { id, max_seats, seats: (0..max_seats).map(|_| Seat::empty()).collect(), dealer_idx: 0, to_act_idx: 0, board: vec![], state: TableState { small_blind: sb, big_blind: bb, pot: 0, street: None }, round_bet: 0, round_contrib: vec![0; max_seats], total_contrib: vec![0; max_seats], revealed: vec![false; max_seats], game: None, hand_seats: vec![], finished_hand: None }
     }
 
     pub fn sit(&mut self, user_id: String, stack: u64) -> bool {
@@ -46,274 +62,141 @@ impl Table {
         } else { false }
     }
 
+    /// Copy of the table as `viewer` may see it: other seats' hole cards are hidden unless shown at showdown.
+    pub fn view_for(&self, viewer: Option<&str>) -> Table {
+        let mut view = self.clone();
+        for (i, seat) in view.seats.iter_mut().enumerate() {
+            let own = viewer.is_some() && seat.user_id.as_deref() == viewer;
+            if !own && !self.revealed.get(i).copied().unwrap_or(false) { seat.hole.clear(); }
+        }
+        view
+    }
+
     pub fn active_player_count(&self) -> usize {
         self.seats.iter().filter(|s| s.user_id.is_some() && !s.sitting_out).count()
     }
 
-    fn next_occupied_from(&self, mut idx: usize) -> usize {
+    fn is_seated(&self, idx: usize) -> bool {
+        let s = &self.seats[idx];
+        s.user_id.is_some() && !s.sitting_out
+    }
+
+    fn next_seated_from(&self, mut idx: usize) -> usize {
         for _ in 0..self.max_seats {
             idx = (idx + 1) % self.max_seats;
-            let s = &self.seats[idx];
-            if s.user_id.is_some() && !s.sitting_out && !s.is_allin && !s.has_folded { return idx; }
+            if self.is_seated(idx) { return idx; }
         }
         idx
     }
 
+    /// Deal a hand from `deck` (used up, dealing pops from the end) to every seated player with chips.
+    /// The button moves to the first such seat from `dealer_idx` on; the engine posts the blinds.
     pub fn start_hand(&mut self, deck: &mut Deck) {
-        self.state.start_hand();
-        self.board.clear();
-        self.round_bet = 0;
-        self.round_contrib.fill(0);
-        self.total_contrib.fill(0);
+        let seats: Vec<usize> = (0..self.max_seats).filter(|&i| self.is_seated(i) && self.seats[i].stack > 0).collect();
+        if seats.len() < 2 { return; }
+        let dealer = seats.iter().position(|&i| i >= self.dealer_idx).unwrap_or(0);
+        self.dealer_idx = seats[dealer];
+        let players = seats.iter().map(|&i| Player {
+            id: self.uid(i), name: self.uid(i), chips: chips(self.seats[i].stack),
+            cards: None, is_active: true, current_bet: 0, has_acted: false, total_bet_in_hand: 0,
+        }).collect();
+        let config = GameConfig { small_blind: chips(self.state.small_blind), big_blind: chips(self.state.big_blind), ..Default::default() };
+        self.revealed.fill(false);
         for seat in &mut self.seats { seat.hole.clear(); seat.has_folded = false; seat.is_allin = false; seat.acted_in_round = false; }
-        // deal 2 cards each
-        for _ in 0..2 { for seat in &mut self.seats { if seat.user_id.is_some() && !seat.sitting_out { if let Some(c)=deck.deal() { seat.hole.push(c); } } } }
-        self.state.street = Some(Street::Preflop);
-        // blinds
-        let sb_idx = self.next_occupied_from(self.dealer_idx);
-        let bb_idx = self.next_occupied_from(sb_idx);
-        let sb_amt = self.state.small_blind.min(self.seats[sb_idx].stack);
-        self.seats[sb_idx].stack -= sb_amt; self.round_contrib[sb_idx] += sb_amt; self.total_contrib[sb_idx] += sb_amt; self.state.pot += sb_amt;
-        let bb_amt = self.state.big_blind.min(self.seats[bb_idx].stack);
-        self.seats[bb_idx].stack -= bb_amt; self.round_contrib[bb_idx] += bb_amt; self.total_contrib[bb_idx] += bb_amt; self.state.pot += bb_amt;
-        self.round_bet = self.round_contrib[bb_idx];
-        self.to_act_idx = self.next_occupied_from(bb_idx);
-    }
-
-    pub fn next_street(&mut self, deck: &mut Deck) {
-        match self.state.street {
-            Some(Street::Preflop) => { self.board.extend(deck.deal_n(3)); self.state.street = Some(Street::Flop); }
-            Some(Street::Flop) => { self.board.extend(deck.deal_n(1)); self.state.street = Some(Street::Turn); }
-            Some(Street::Turn) => { self.board.extend(deck.deal_n(1)); self.state.street = Some(Street::River); }
-            Some(Street::River) => { self.state.street = Some(Street::Showdown); }
-            _ => {}
-        }
-        // reset round state
-        self.round_bet = 0;
-        self.round_contrib.fill(0);
-        for s in &mut self.seats { s.acted_in_round = false; }
-        // on postflop, first to act is next from dealer
-        if let Some(street) = self.state.street {
-            if street != Street::Showdown {
-                self.to_act_idx = self.next_occupied_from(self.dealer_idx);
-            }
-        }
-    }
-
-    fn alive_players(&self) -> Vec<usize> {
-        (0..self.max_seats).filter(|&i| {
-            let s = &self.seats[i];
-            s.user_id.is_some() && !s.sitting_out && !s.has_folded && (s.stack > 0 || self.round_contrib[i] > 0 || self.total_contrib[i] > 0)
-        }).collect()
-    }
-
-    fn all_matched(&self) -> bool {
-        for i in 0..self.max_seats {
-            let s = &self.seats[i];
-            if s.user_id.is_none() || s.sitting_out || s.has_folded || s.is_allin { continue; }
-            if self.round_contrib[i] != self.round_bet { return false; }
-            if !s.acted_in_round { return false; }
-        }
-        true
+        self.game = Some(TexasHoldem::with_deck(players, &config, dealer, std::mem::take(&mut deck.0)));
+        self.hand_seats = seats;
+        self.sync();
     }
 
     pub fn apply_action_by_user(&mut self, user_id: &str, action: &str, amount: Option<u64>) -> Result<ApplyOutcome, String> {
-        let seat_idx = self.seats.iter().position(|s| s.user_id.as_deref() == Some(user_id)).ok_or("not seated")?;
-        if self.to_act_idx != seat_idx { return Err("not your turn".into()); }
-        if self.state.street == Some(Street::Showdown) || self.state.street.is_none() { return Err("hand not active".into()); }
-        let to_call = self.round_bet.saturating_sub(self.round_contrib[seat_idx]);
-        match action {
-            "fold" => { self.seats[seat_idx].has_folded = true; self.seats[seat_idx].acted_in_round = true; }
-            "check" => { if to_call != 0 { return Err("cannot check".into()); } self.seats[seat_idx].acted_in_round = true; }
-            "call" => {
-                let s = &mut self.seats[seat_idx];
-                let pay = to_call.min(s.stack);
-                s.stack -= pay; self.round_contrib[seat_idx] += pay; self.total_contrib[seat_idx] += pay; self.state.pot += pay; s.acted_in_round = true; if s.stack == 0 { s.is_allin = true; }
-            }
-            "raise" => {
-                let raise_by = amount.unwrap_or(0);
-                if raise_by < self.state.big_blind { return Err("min raise".into()); }
-                let s = &mut self.seats[seat_idx];
-                let need = to_call + raise_by;
-                if need == 0 { return Err("bad raise".into()); }
-                let pay = need.min(s.stack);
-                s.stack -= pay; self.round_contrib[seat_idx] += pay; self.total_contrib[seat_idx] += pay; self.state.pot += pay; s.acted_in_round = true; if s.stack == 0 { s.is_allin = true; }
-                self.round_bet = self.round_contrib[seat_idx];
-                // on raise, others need to act again
-                for i in 0..self.max_seats { if i != seat_idx { self.seats[i].acted_in_round = false; } }
-            }
-            _ => return Err("unknown action".into()),
+        let game = self.game.as_mut().ok_or("hand not active")?;
+        let mut parsed = parse_action(action, amount)?;
+        // a bet or raise beyond the stack goes all in, as it always has at this table
+        if let (PlayerAction::Bet(raise) | PlayerAction::Raise(raise), Some(p)) = (&mut parsed, game.state.players.iter().find(|p| p.id == user_id)) {
+            let bet = game.state.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
+            let room = (p.chips + p.current_bet).saturating_sub(bet);
+            if room > 0 { *raise = (*raise).min(room); }
         }
-
-        // check if only one player remains
-        let alive: Vec<_> = self.alive_players();
-        if alive.len() <= 1 {
-            if let Some(&winner) = alive.first() { self.seats[winner].stack += self.state.pot; }
-            self.state.pot = 0; self.state.street = None; self.board.clear(); self.round_contrib.fill(0); self.total_contrib.fill(0);
-            self.dealer_idx = self.next_occupied_from(self.dealer_idx);
+        let stage = game.state.stage;
+        game.handle_action_for(user_id, parsed).map_err(|e| e.to_string())?;
+        let now = game.state.stage;
+        if now == GameStage::Showdown {
+            self.end_hand();
             return Ok(ApplyOutcome::HandEnded);
         }
+        self.sync();
+        Ok(if now != stage { ApplyOutcome::NextStreet } else { ApplyOutcome::Continue })
+    }
 
-        // advance street if matched
-        if self.all_matched() {
-            return Ok(ApplyOutcome::NextStreet);
-        } else {
-            self.to_act_idx = self.next_occupied_from(seat_idx);
-            return Ok(ApplyOutcome::Continue);
+    /// Copy the engine's state of the hand in progress onto the seats and table fields.
+    fn sync(&mut self) {
+        let Some(game) = &self.game else { return };
+        let st = &game.state;
+        self.round_contrib.fill(0);
+        self.total_contrib.fill(0);
+        for (p, &i) in st.players.iter().zip(&self.hand_seats) {
+            let seat = &mut self.seats[i];
+            seat.stack = p.chips as u64;
+            seat.hole = p.cards.map(|(c1, c2)| vec![c1, c2]).unwrap_or_default();
+            seat.has_folded = !p.is_active;
+            seat.is_allin = p.is_active && p.chips == 0;
+            seat.acted_in_round = p.has_acted;
+            self.round_contrib[i] = p.current_bet as u64;
+            self.total_contrib[i] = p.total_bet_in_hand as u64;
         }
+        self.round_bet = self.round_contrib.iter().copied().max().unwrap_or(0);
+        self.board = st.community_cards.clone();
+        self.state.pot = st.pot as u64;
+        self.state.street = Some(street_of(st.stage));
+        if let Some(&idx) = self.hand_seats.get(st.current_player_index) { self.to_act_idx = idx; }
     }
 
-    pub fn showdown_and_payout(&mut self) {
-        // Build side pots from total_contrib
-        let mut remaining: Vec<u64> = self.total_contrib.clone();
-        let mut pots: Vec<(u64, Vec<usize>)> = Vec::new();
-        loop {
-            let mut min_pos: Option<u64> = None;
-            for (i, &c) in remaining.iter().enumerate() {
-                if c > 0 { min_pos = Some(match min_pos { Some(m) => m.min(c), None => c }); }
-            }
-            let Some(layer) = min_pos else { break };
-            let mut amount = 0u64;
-            let mut eligible: Vec<usize> = Vec::new();
-            for i in 0..remaining.len() {
-                if remaining[i] > 0 { amount += layer; remaining[i] -= layer; }
-                // eligible if player has not folded and had chips in this layer
-                if self.seats[i].user_id.is_some() && !self.seats[i].has_folded && !self.seats[i].sitting_out && self.total_contrib[i] > 0 {
-                    eligible.push(i);
-                }
+    /// Pay out a hand that reached showdown (or was folded to one player), reveal the hands shown
+    /// and clear the table for the next hand.
+    fn end_hand(&mut self) {
+        if let Some(game) = self.game.as_mut() { game.resolve_showdown(); }
+        self.sync();
+        let Some(game) = self.game.take() else { return };
+        let hand = game.current_hand().to_vec();
+        for event in &hand {
+            if let HandEvent::HandShown { player_id, .. } = event {
+                if let Some(i) = self.seats.iter().position(|s| s.user_id.as_deref() == Some(player_id.as_str())) { self.revealed[i] = true; }
             }
-            pots.push((amount, eligible));
         }
-
-        // Evaluate all active players
-        let mut ranks: Vec<Option<HandRank>> = vec![None; self.max_seats];
-        for i in 0..self.max_seats { if self.seats[i].user_id.is_some() && !self.seats[i].has_folded {
-            ranks[i] = Some(best_rank(&self.seats[i].hole, &self.board));
-        }}
-
-        for (mut amount, eligible) in pots.into_iter() {
-            if amount == 0 || eligible.is_empty() { continue; }
-            // pick winners
-            let mut best: Option<HandRank> = None;
-            let mut winners: Vec<usize> = Vec::new();
-            for &i in &eligible { if let Some(r) = &ranks[i] {
-                if best.is_none() || r > best.as_ref().unwrap() { best = Some(*r); winners.clear(); winners.push(i); }
-                else if Some(r) == best.as_ref() { winners.push(i); }
-            }}
-            if winners.is_empty() { continue; }
-            let share = amount / winners.len() as u64;
-            let mut remainder = amount - share * winners.len() as u64;
-            for &w in &winners { self.seats[w].stack += share; }
-            // assign remainder to lowest index winners for determinism
-            winners.iter().take(remainder as usize).for_each(|&w| { self.seats[w].stack += 1; });
-        }
-
-        self.state.pot = 0; self.state.street = None; self.board.clear(); self.round_contrib.fill(0); self.total_contrib.fill(0);
-        self.dealer_idx = self.next_occupied_from(self.dealer_idx);
+        self.finished_hand = Some(hand);
+        self.state.pot = 0; self.state.street = None; self.board.clear(); self.round_bet = 0; self.round_contrib.fill(0); self.total_contrib.fill(0);
+        self.dealer_idx = self.next_seated_from(self.dealer_idx);
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct HandRank(pub u8, pub [u8;5]);
-
-fn best_rank(hole: &Vec<Card>, board: &Vec<Card>) -> HandRank {
-    let mut cards = hole.clone(); cards.extend(board.iter().copied());
-    // choose best 5 out of up to 7
-    let n = cards.len();
-    let mut best = HandRank(0, [0;5]);
-    let idxs: Vec<usize> = (0..n).collect();
-    let combs = combinations(&idxs, 5);
-    for c in combs {
-        let five = [cards[c[0]], cards[c[1]], cards[c[2]], cards[c[3]], cards[c[4]]];
-        let r = eval5(&five);
-        if r > best { best = r; }
-    }
-    best
-}
+/// Events of the last finished hand, for feeding texas-engine's stats or history exports.
+impl Table {
+    pub fn take_finished_hand(&mut self) -> Option<Vec<HandEvent>> { self.finished_hand.take() }
 
-fn combinations(items: &Vec<usize>, k: usize) -> Vec<Vec<usize>> {
-    let mut res = Vec::new();
-    let n = items.len();
-    let mut idx: Vec<usize> = (0..k).collect();
-    loop {
-        res.push(idx.iter().map(|&i| items[i]).collect());
-        let mut i = k; while i>0 { i-=1; if idx[i] != i + n - k { idx[i]+=1; for j in i+1..k { idx[j]=idx[j-1]+1; } break; } }
-        if idx[0] == n - k { break; }
-    }
-    res
+    fn uid(&self, idx: usize) -> String { self.seats[idx].user_id.clone().unwrap_or_default() }
 }
 
-fn rank_value(rank: Rank) -> u8 { rank as u8 }
-
-fn eval5(cards: &[Card;5]) -> HandRank {
-    // sort by rank desc
-    let mut ranks: Vec<u8> = cards.iter().map(|c| rank_value(c.0)).collect();
-    ranks.sort_unstable_by(|a,b| b.cmp(a));
-    // count occurrences
-    let mut counts = [0u8;15]; // 2..14
-    for &r in &ranks { counts[r as usize]+=1; }
-    let is_flush = cards.iter().all(|c| c.1 == cards[0].1);
-    let is_straight = is_straight_sorted(&mut ranks.clone());
-
-    if is_flush && is_straight { return HandRank(8, normalize_kickers(&ranks)); }
+fn chips(amount: u64) -> u32 { u32::try_from(amount).unwrap_or(u32::MAX) }
 
-    // four of a kind
-    if let Some((quad, kicker)) = find_n_of_a_kind(&counts, 4, &ranks) { return HandRank(7, [quad, quad, quad, quad, kicker]); }
-    // full house
-    if let Some(trip) = find_value_with_count(&counts, 3) { if let Some(pair) = find_second_pair(&counts, trip) { return HandRank(6, [trip, trip, trip, pair, pair]); } }
-    if is_flush { return HandRank(5, normalize_kickers(&ranks)); }
-    if is_straight { return HandRank(4, normalize_kickers(&ranks)); }
-    if let Some((trip, kickers)) = find_three(&counts, &ranks) { return HandRank(3, [trip, trip, trip, kickers[0], kickers[1]]); }
-    if let Some((hp, lp, kicker)) = find_two_pair(&counts, &ranks) { return HandRank(2, [hp, hp, lp, lp, kicker]); }
-    if let Some((pair, ks)) = find_pair(&counts, &ranks) { return HandRank(1, [pair, pair, ks[0], ks[1], ks[2]]); }
-    HandRank(0, normalize_kickers(&ranks))
-}
-
-fn is_straight_sorted(ranks: &mut Vec<u8>) -> bool {
-    // handle wheel A-2-3-4-5
-    let mut uniq = ranks.clone(); uniq.dedup();
-    if uniq.len() < 5 { return false; }
-    // try sequences including wheel
-    let mut seq = 1; for i in 0..uniq.len()-1 { if uniq[i] == uniq[i+1]+1 { seq+=1; if seq>=5 { return true; } } else { seq=1; } }
-    // wheel
-    uniq.sort_unstable(); if uniq.ends_with(&[5,4,3,2]) && ranks.contains(&14) { return true; }
-    false
+fn street_of(stage: GameStage) -> Street {
+    match stage {
+        GameStage::PreFlop => Street::Preflop,
+        GameStage::Flop => Street::Flop,
+        GameStage::Turn => Street::Turn,
+        GameStage::River => Street::River,
+        GameStage::Showdown => Street::Showdown,
+    }
 }
 
-fn normalize_kickers(r: &Vec<u8>) -> [u8;5] { [r[0], r[1], r[2], r[3], r[4]] }
-
-fn find_value_with_count(counts: &[u8;15], n: u8) -> Option<u8> {
-    for v in (2..=14).rev() { if counts[v as usize] == n { return Some(v as u8); } }
-    None
-}
-fn find_second_pair(counts: &[u8;15], exclude: u8) -> Option<u8> {
-    for v in (2..=14).rev() { if v as u8 != exclude && counts[v as usize] >= 2 { return Some(v as u8); } }
-    None
-}
-fn find_n_of_a_kind(counts: &[u8;15], n: u8, ranks: &Vec<u8>) -> Option<(u8,u8)> {
-    if let Some(v) = find_value_with_count(counts, n) { let kicker = ranks.iter().copied().find(|&x| x != v).unwrap_or(2); return Some((v, kicker)); } None
-}
-fn find_three(counts: &[u8;15], ranks: &Vec<u8>) -> Option<(u8,[u8;2])> {
-    if let Some(v) = find_value_with_count(counts, 3) {
-        let mut ks: Vec<u8> = ranks.iter().copied().filter(|&x| x != v).collect(); ks.sort_unstable_by(|a,b| b.cmp(a)); ks.truncate(2);
-        return Some((v, [ks[0], ks[1]]));
-    } None
-}
-fn find_pair(counts: &[u8;15], ranks: &Vec<u8>) -> Option<(u8,[u8;3])> {
-    if let Some(v) = find_value_with_count(counts, 2) {
-        let mut ks: Vec<u8> = ranks.iter().copied().filter(|&x| x != v).collect(); ks.sort_unstable_by(|a,b| b.cmp(a)); ks.truncate(3);
-        return Some((v, [ks[0], ks[1], ks[2]]));
-    } None
-}
-fn find_two_pair(counts: &[u8;15], ranks: &Vec<u8>) -> Option<(u8,u8,u8)> {
-    let mut pairs: Vec<u8> = Vec::new();
-    for v in (2..=14).rev() { if counts[v as usize] >= 2 { pairs.push(v as u8); if pairs.len()==2 { break; } } }
-    if pairs.len()==2 {
-        let kicker = ranks.iter().copied().find(|&x| x != pairs[0] && x != pairs[1]).unwrap_or(2);
-        return Some((pairs[0], pairs[1], kicker));
+/// Map a client action string to the engine's `PlayerAction`; "raise"/"bet" amounts are the raise size.
+pub fn parse_action(action: &str, amount: Option<u64>) -> Result<PlayerAction, String> {
+    let amount = || u32::try_from(amount.unwrap_or(0)).map_err(|_| String::from("bad raise"));
+    match action {
+        "fold" => Ok(PlayerAction::Fold),
+        "check" => Ok(PlayerAction::Check),
+        "call" => Ok(PlayerAction::Call),
+        "bet" => Ok(PlayerAction::Bet(amount()?)),
+        "raise" => Ok(PlayerAction::Raise(amount()?)),
+        _ => Err("unknown action".into()),
     }
-    None
 }
-
-
-
@@ -1,30 +1,17 @@
 use rand::seq::SliceRandom;
-use std::fmt::{Display, Formatter};
 use serde::{Serialize, Deserialize};
 
 pub mod game;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Suit { S, H, D, C }
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum Rank { Two=2, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace }
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Card(pub Rank, pub Suit);
+// Cards, evaluation and actions come from texas-engine so there is one source of truth
+pub use texas_engine::{Card, Rank, Suit, PlayerAction, CardSet};
+pub use texas_engine::evaluator::evaluate_strength;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deck(pub Vec<Card>);
 
 impl Deck {
-    pub fn new() -> Self {
-        use Rank::*; use Suit::*;
-        let mut cards = Vec::with_capacity(52);
-        let ranks = [Two,Three,Four,Five,Six,Seven,Eight,Nine,Ten,Jack,Queen,King,Ace];
-        let suits = [S,H,D,C];
-        for &s in &suits { for &r in &ranks { cards.push(Card(r,s)); } }
-        Self(cards)
-    }
+    pub fn new() -> Self { Self(CardSet::full().to_vec()) }
     pub fn shuffle(&mut self) {
         let mut rng = rand::thread_rng();
         self.0.as_mut_slice().shuffle(&mut rng);
@@ -33,56 +20,15 @@ impl Deck {
     pub fn deal_n(&mut self, n: usize) -> Vec<Card> { (0..n).filter_map(|_| self.deal()).collect() }
 }
 
-impl Display for Suit {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", match self { Suit::S => 's', Suit::H => 'h', Suit::D => 'd', Suit::C => 'c' })
-    }
-}
-
-impl Display for Rank {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        use Rank::*;
-        let s = match self { Two=>'2',Three=>'3',Four=>'4',Five=>'5',Six=>'6',Seven=>'7',Eight=>'8',Nine=>'9',Ten=>'T',Jack=>'J',Queen=>'Q',King=>'K',Ace=>'A' };
-        write!(f, "{}", s)
-    }
-}
-
-impl Display for Card {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result { write!(f, "{}{}", self.0, self.1) }
+impl Default for Deck {
+    fn default() -> Self { Self::new() }
 }
 
+/// Parse a card like "As" or "Td"; panics on bad input, use `str::parse` to handle errors.
 pub fn parse_card(s: &str) -> Card {
-    assert!(s.len() == 2, "card like As, Td");
-    let bytes = s.as_bytes();
-    let r = match bytes[0] as char {
-        '2' => Rank::Two,
-        '3' => Rank::Three,
-        '4' => Rank::Four,
-        '5' => Rank::Five,
-        '6' => Rank::Six,
-        '7' => Rank::Seven,
-        '8' => Rank::Eight,
-        '9' => Rank::Nine,
-        'T' | 't' => Rank::Ten,
-        'J' | 'j' => Rank::Jack,
-        'Q' | 'q' => Rank::Queen,
-        'K' | 'k' => Rank::King,
-        'A' | 'a' => Rank::Ace,
-        _ => panic!("bad rank"),
-    };
-    let s = match bytes[1] as char {
-        's' | 'S' => Suit::S,
-        'h' | 'H' => Suit::H,
-        'd' | 'D' => Suit::D,
-        'c' | 'C' => Suit::C,
-        _ => panic!("bad suit"),
-    };
-    Card(r, s)
+    s.parse().unwrap_or_else(|e| panic!("bad card {s:?}: {e}"))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum PlayerAction { Fold, Check, Call(u64), Raise(u64) }
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Street { Preflop, Flop, Turn, River, Showdown }
 
@@ -94,20 +40,25 @@ pub struct TableState {
     pub street: Option<Street>,
 }
 
-impl TableState {
-    pub fn start_hand(&mut self) {
-        self.pot = 0;
-        self.street = Some(Street::Preflop);
-    }
-    pub fn next_street(&mut self) {
-        self.street = match self.street {
-            Some(Street::Preflop) => Some(Street::Flop),
-            Some(Street::Flop) => Some(Street::Turn),
-            Some(Street::Turn) => Some(Street::River),
-            Some(Street::River) => Some(Street::Showdown),
-            _ => self.street,
-        };
-    }
+/// The user a websocket connection plays as. The first join (or room creation) binds it and every
+/// later message must carry the same id, so a socket cannot claim another player's id to see
+/// their hole cards or act for them.
+#[derive(Debug, Default, Clone)]
+pub struct Connection {
+    user: Option<String>,
 }
 
-
+impl Connection {
+    pub fn user(&self) -> Option<&str> { self.user.as_deref() }
+
+    /// Check the id a client message claims; `joining` messages bind a connection that has no user yet.
+    pub fn claim(&mut self, user_id: &str, joining: bool) -> Result<&str, String> {
+        match &self.user {
+            Some(user) if user != user_id => return Err("client_msg_id does not match this connection".into()),
+            Some(_) => {}
+            None if joining => self.user = Some(user_id.to_string()),
+            None => return Err("join a table first".into()),
+        }
+        Ok(self.user.as_deref().unwrap_or_default())
+    }
+}
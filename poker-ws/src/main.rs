@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{collections::{BTreeMap, HashMap}, net::SocketAddr, path::{Path, PathBuf}, sync::Arc};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use axum::{
@@ -9,7 +9,7 @@ use axum::{
 };
 use tower_http::services::ServeDir;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, Mutex, Notify};
 use tracing::{info, Level};
 use tracing_subscriber::EnvFilter;
 use axum::extract::ws::Message as WsMessage;
@@ -17,8 +17,10 @@ use futures::{StreamExt, SinkExt};
 use tokio::time::{sleep, Duration};
 
 // Reuse simple poker types scaffold
-use poker_ws::{Deck, TableState};
+use poker_ws::{Connection, Deck, TableState};
 use poker_ws::game::{Table, ApplyOutcome};
+use texas_engine::history::HandEvent;
+use texas_engine::stats::{HudStats, StatsStore};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")] 
@@ -30,6 +32,8 @@ enum ClientAction {
     Rebuy { table_id: String, client_msg_id: String },
     Ready { table_id: String, client_msg_id: String, ready: bool },
     LeaveRoom { table_id: String, client_msg_id: String },
+    /// HUD stats for the players seated at the table, answered only to the asking client
+    Stats { table_id: String, client_msg_id: String },
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -38,12 +42,13 @@ enum ServerEvent {
     Welcome { msg: String },
     PlayerJoined { table_id: String },
     ActionAck { table_id: String, hand_id: String, action: String },
-    TableSnapshot { table: poker_ws::game::Table, ready: HashMap<String, bool>, to_act_uid: Option<String>, ms_left: Option<u64> },
+    TableSnapshot { table: Box<poker_ws::game::Table>, ready: HashMap<String, bool>, to_act_uid: Option<String>, ms_left: Option<u64> },
     RoomCreated { table_id: String },
     RoomClosed { table_id: String },
     PlayerReady { table_id: String, client_msg_id: String, ready: bool },
     PlayerLeft { table_id: String, client_msg_id: String },
     GameStartCountdown { table_id: String, ms_left: u64 },
+    Stats { table_id: String, players: BTreeMap<String, HudStats> },
     Error { message: String },
 }
 
@@ -72,34 +77,91 @@ impl Default for RoomConfig {
 
 type ClientTx = mpsc::UnboundedSender<WsMessage>;
 
+/// How long the stats file waits after a hand ends, so hands finishing together are saved at once
+const STATS_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Player stats shared by every table; a background task saves them to `POKER_STATS_FILE` (if set) as hands end
+struct Stats {
+    store: Mutex<StatsStore>,
+    file: Option<PathBuf>,
+    changed: Notify,
+}
+
+impl Stats {
+    async fn load(file: Option<PathBuf>) -> Arc<Self> {
+        let json = match &file { Some(path) => tokio::fs::read_to_string(path).await.ok(), None => None };
+        let store = json.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default();
+        let stats = Arc::new(Self { store: Mutex::new(store), file, changed: Notify::new() });
+        if stats.file.is_some() { tokio::spawn(stats.clone().save_loop()); }
+        stats
+    }
+
+    async fn record(&self, hand: &[HandEvent]) {
+        self.store.lock().await.record_hand(hand);
+        self.changed.notify_one();
+    }
+
+    /// HUD figures for each player; players without recorded hands get empty stats
+    async fn hud(&self, players: impl Iterator<Item = String>) -> BTreeMap<String, HudStats> {
+        let store = self.store.lock().await;
+        players.map(|id| { let hud = store.get(&id).cloned().unwrap_or_default().hud(); (id, hud) }).collect()
+    }
+
+    /// Save the store a moment after each change; the file is replaced whole, never left half written.
+    async fn save_loop(self: Arc<Self>) {
+        let Some(path) = self.file.clone() else { return };
+        loop {
+            self.changed.notified().await;
+            sleep(STATS_SAVE_DELAY).await;
+            let json = serde_json::to_string(&*self.store.lock().await);
+            let saved = match json { Ok(json) => write_replacing(&path, json).await, Err(e) => Err(e.into()) };
+            if let Err(e) = saved { tracing::warn!(error=%e, "stats_save_failed"); }
+        }
+    }
+}
+
+/// Write `contents` to a temporary file next to `path`, then rename it over `path`.
+async fn write_replacing(path: &Path, contents: String) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// A websocket connection and the user it belongs to, so snapshots can hide other players' cards
+struct Subscriber {
+    viewer: String,
+    tx: ClientTx,
+}
+
 enum ActorMsg {
     Client(ClientAction),
-    Subscribe(ClientTx),
+    Subscribe { viewer: String, tx: ClientTx },
     Tick,
 }
 
 struct TableActor {
     table_id: String,
     rx: mpsc::UnboundedReceiver<ActorMsg>,
-    subscribers: Vec<ClientTx>,
+    subscribers: Vec<Subscriber>,
     state: TableState,
     deck: Deck,
     table: Table,
-    host_user: String,
     config: RoomConfig,
     rebuys_left: HashMap<String, u32>,
     room_end_at: Option<Instant>,
     action_deadline: Option<Instant>,
     ready_status: HashMap<String, bool>,
     countdown_end: Option<Instant>,
+    stats: Arc<Stats>,
 }
 
 impl TableActor {
-    fn spawn(table_id: String) -> mpsc::UnboundedSender<ActorMsg> {
-        Self::spawn_with_config(table_id, "host".into(), RoomConfig::default())
+    fn spawn(table_id: String, stats: Arc<Stats>) -> mpsc::UnboundedSender<ActorMsg> {
+        Self::spawn_with_config(table_id, RoomConfig::default(), stats)
     }
 
-    fn spawn_with_config(table_id: String, host_user: String, config: RoomConfig) -> mpsc::UnboundedSender<ActorMsg> {
+    fn spawn_with_config(table_id: String, config: RoomConfig, stats: Arc<Stats>) -> mpsc::UnboundedSender<ActorMsg> {
         let (tx, rx) = mpsc::unbounded_channel();
         let tx_return = tx.clone();
         let mut actor = TableActor {
@@ -109,16 +171,15 @@ impl TableActor {
             state: TableState::default(),
             deck: Deck::new(),
             table: Table::new(table_id.clone(), 6, config.small_blind, config.big_blind),
-            host_user,
             config: config.clone(),
             rebuys_left: HashMap::new(),
             room_end_at: if config.room_duration_sec > 0 { Some(Instant::now() + Duration::from_secs(config.room_duration_sec)) } else { None },
             action_deadline: None,
             ready_status: HashMap::new(),
             countdown_end: None,
+            stats,
         };
         tokio::spawn(async move {
-            actor.deck.shuffle();
             // kick off a periodic tick for timers
             let tick_tx = tx.clone();
             tokio::spawn(async move {
@@ -126,8 +187,10 @@ impl TableActor {
             });
             while let Some(msg) = actor.rx.recv().await {
                 match msg {
-                    ActorMsg::Subscribe(client_tx) => {
-                        actor.subscribers.push(client_tx);
+                    ActorMsg::Subscribe { viewer, tx } => {
+                        // clients resubscribe with every message; keep one entry per connection, whose viewer never changes
+                        if actor.subscribers.iter().any(|s| s.tx.same_channel(&tx)) { continue; }
+                        actor.subscribers.push(Subscriber { viewer, tx });
                         let _ = actor.broadcast(&ServerEvent::Welcome { msg: format!("joined {}", actor.table_id) });
                     }
                     ActorMsg::Client(ClientAction::CreateRoom { .. }) => {
@@ -143,12 +206,8 @@ impl TableActor {
                             if let Some(uid) = actor.table.seats[to_act].user_id.clone() {
                                 let _ = actor.table.apply_action_by_user(&uid, "check", None)
                                     .or_else(|_| actor.table.apply_action_by_user(&uid, "fold", None));
-                                actor.table.next_street(&mut actor.deck);
                                 actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
-                                if actor.table.state.street == Some(poker_ws::Street::Showdown) { actor.table.showdown_and_payout(); }
-                                let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                                let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                                let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                                actor.broadcast_snapshot();
                             }
                         } else {
                             // if hand not active, handle start countdown
@@ -157,21 +216,16 @@ impl TableActor {
                                 let mut all_ready = true;
                                 for seat in &actor.table.seats {
                                     if let Some(uid) = &seat.user_id {
-                                        if !seat.sitting_out {
-                                            if *actor.ready_status.get(uid).unwrap_or(&false) == false { all_ready = false; break; }
-                                        }
+                                        if !seat.sitting_out && !actor.ready_status.get(uid).copied().unwrap_or(false) { all_ready = false; break; }
                                     }
                                 }
-                                if all_ready && actor.room_end_at.map_or(true, |end| Instant::now() < end) {
+                                if all_ready && actor.room_end_at.is_none_or(|end| Instant::now() < end) {
                                     if actor.countdown_end.is_none() { actor.countdown_end = Some(Instant::now() + Duration::from_millis(2000)); }
                                     if let Some(end) = actor.countdown_end {
                                         if Instant::now() >= end {
-                                            actor.table.start_hand(&mut actor.deck);
-                                            actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
+                                            actor.start_hand();
                                             actor.countdown_end = None;
-                                            let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                                            let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                                            let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                                            actor.broadcast_snapshot();
                                         } else {
                                             let ms_left = end.saturating_duration_since(Instant::now()).as_millis() as u64;
                                             let _ = actor.broadcast(&ServerEvent::GameStartCountdown { table_id: actor.table_id.clone(), ms_left });
@@ -191,30 +245,24 @@ impl TableActor {
                         actor.rebuys_left.entry(client_msg_id.clone()).or_insert(actor.config.rebuy_hands);
                         actor.ready_status.insert(client_msg_id.clone(), false);
                         if actor.table.active_player_count() >= 2 && actor.state.street.is_none() {
-                            if actor.room_end_at.map_or(true, |end| Instant::now() < end) {
-                                actor.table.start_hand(&mut actor.deck);
-                                actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
+                            if actor.room_end_at.is_none_or(|end| Instant::now() < end) {
+                                actor.start_hand();
                             } else { let _ = actor.broadcast(&ServerEvent::RoomClosed { table_id: actor.table_id.clone() }); }
                         }
                         let _ = actor.broadcast(&ServerEvent::PlayerJoined { table_id });
-                        let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                        let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                        let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                        actor.broadcast_snapshot();
                     }
                     ActorMsg::Client(ClientAction::JoinRoom { table_id, client_msg_id }) => {
                         let _ = actor.table.sit(client_msg_id.clone(), actor.config.starting_stack);
                         actor.rebuys_left.entry(client_msg_id.clone()).or_insert(actor.config.rebuy_hands);
                         actor.ready_status.insert(client_msg_id.clone(), false);
                         if actor.table.active_player_count() >= 2 && actor.state.street.is_none() {
-                            if actor.room_end_at.map_or(true, |end| Instant::now() < end) {
-                                actor.table.start_hand(&mut actor.deck);
-                                actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
+                            if actor.room_end_at.is_none_or(|end| Instant::now() < end) {
+                                actor.start_hand();
                             } else { let _ = actor.broadcast(&ServerEvent::RoomClosed { table_id: actor.table_id.clone() }); }
                         }
                         let _ = actor.broadcast(&ServerEvent::PlayerJoined { table_id });
-                        let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                        let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                        let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                        actor.broadcast_snapshot();
                     }
                     ActorMsg::Client(ClientAction::Ready { table_id, client_msg_id, ready }) => {
                         actor.ready_status.insert(client_msg_id.clone(), ready);
@@ -232,9 +280,7 @@ impl TableActor {
                             }
                             actor.countdown_end = None; // any leave cancels countdown
                             let _ = actor.broadcast(&ServerEvent::PlayerLeft { table_id, client_msg_id });
-                            let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                            let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                            let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                            actor.broadcast_snapshot();
                         }
                     }
                     ActorMsg::Client(ClientAction::Rebuy { table_id: _, client_msg_id }) => {
@@ -243,58 +289,91 @@ impl TableActor {
                             if *entry > 0 {
                                 if let Some(seat) = actor.table.seats.iter_mut().find(|s| s.user_id.as_deref() == Some(&client_msg_id)) {
                                     if seat.stack < actor.config.starting_stack { seat.stack = actor.config.starting_stack; *entry -= 1; 
-                                        let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                                        let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                                        let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left }); 
+                                        actor.broadcast_snapshot();
                                     }
                                 }
                             }
                         }
                     }
+                    ActorMsg::Client(ClientAction::Stats { table_id, client_msg_id }) => {
+                        let players = actor.stats.hud(actor.table.seats.iter().filter_map(|s| s.user_id.clone())).await;
+                        actor.send_to(&client_msg_id, &ServerEvent::Stats { table_id, players });
+                    }
                     ActorMsg::Client(ClientAction::Action { table_id, hand_id, action, amount, client_msg_id }) => {
                         info!(table_id=%table_id, hand_id=%hand_id, action=%action, amount=?amount, client_msg_id, "action_received");
                         let outcome = actor.table.apply_action_by_user(&client_msg_id, &action, amount);
                         match outcome {
                             Ok(ApplyOutcome::Continue) => {}
                             Ok(ApplyOutcome::NextStreet) => {
-                                actor.table.next_street(&mut actor.deck);
                                 actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
-                                if actor.table.state.street == Some(poker_ws::Street::Showdown) {
-                                    actor.table.showdown_and_payout();
-                                    if actor.table.active_player_count() >= 2 && actor.room_end_at.map_or(true, |end| Instant::now() < end) {
-                                        actor.table.start_hand(&mut actor.deck);
-                                        actor.action_deadline = Some(Instant::now() + Duration::from_millis(actor.config.action_time_ms));
-                                    } else if actor.room_end_at.is_some() { let _ = actor.broadcast(&ServerEvent::RoomClosed { table_id: actor.table_id.clone() }); }
-                                }
                             },
-                            Ok(ApplyOutcome::HandEnded) => { /* next hand will start on next join or by timer */ }
+                            Ok(ApplyOutcome::HandEnded) => {
+                                // everyone sees the showdown, with the hands that were shown, before the next deal clears it
+                                actor.broadcast_snapshot();
+                                if actor.table.active_player_count() >= 2 && actor.room_end_at.is_none_or(|end| Instant::now() < end) {
+                                    actor.start_hand();
+                                } else if actor.room_end_at.is_some() { let _ = actor.broadcast(&ServerEvent::RoomClosed { table_id: actor.table_id.clone() }); }
+                            }
                             Err(e) => { let _ = actor.broadcast(&ServerEvent::Error{ message: e }); }
                         }
                         let _ = actor.broadcast(&ServerEvent::ActionAck { table_id, hand_id, action });
-                        let to_act_uid = actor.table.seats.get(actor.table.to_act_idx).and_then(|s| s.user_id.clone());
-                        let ms_left = actor.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
-                        let _ = actor.broadcast(&ServerEvent::TableSnapshot { table: actor.table.clone(), ready: actor.ready_status.clone(), to_act_uid, ms_left });
+                        actor.broadcast_snapshot();
                     }
                 }
+                if let Some(hand) = actor.table.take_finished_hand() { actor.stats.record(&hand).await; }
             }
         });
         tx_return
     }
 
+    /// Deal the next hand from a freshly shuffled deck and restart the action timer.
+    fn start_hand(&mut self) {
+        self.deck = Deck::new();
+        self.deck.shuffle();
+        self.table.start_hand(&mut self.deck);
+        self.action_deadline = Some(Instant::now() + Duration::from_millis(self.config.action_time_ms));
+    }
+
     fn broadcast(&mut self, evt: &ServerEvent) -> Result<(), ()> {
         let msg = serde_json::to_string(evt).map_err(|_| ())?;
-        self.subscribers.retain(|tx| tx.send(WsMessage::Text(msg.clone())).is_ok());
+        self.subscribers.retain(|s| s.tx.send(WsMessage::Text(msg.clone())).is_ok());
         Ok(())
     }
+
+    /// Send an event only to the connections of `viewer`.
+    fn send_to(&mut self, viewer: &str, evt: &ServerEvent) {
+        let Ok(msg) = serde_json::to_string(evt) else { return };
+        self.subscribers.retain(|s| s.viewer != viewer || s.tx.send(WsMessage::Text(msg.clone())).is_ok());
+    }
+
+    /// Send each subscriber a snapshot that only shows their own hole cards (plus any shown at showdown).
+    fn broadcast_snapshot(&mut self) {
+        let to_act_uid = self.table.seats.get(self.table.to_act_idx).and_then(|s| s.user_id.clone());
+        let ms_left = self.action_deadline.map(|dl| dl.saturating_duration_since(Instant::now()).as_millis() as u64);
+        let (table, ready) = (&self.table, &self.ready_status);
+        self.subscribers.retain(|s| {
+            let evt = ServerEvent::TableSnapshot { table: Box::new(table.view_for(Some(&s.viewer))), ready: ready.clone(), to_act_uid: to_act_uid.clone(), ms_left };
+            match serde_json::to_string(&evt) {
+                Ok(msg) => s.tx.send(WsMessage::Text(msg)).is_ok(),
+                Err(_) => true,
+            }
+        });
+    }
 }
 
 type TableMap = Arc<Mutex<HashMap<String, mpsc::UnboundedSender<ActorMsg>>>>;
 
-async fn ws_handler(ws: WebSocketUpgrade, State(tables): State<TableMap>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, tables))
+#[derive(Clone)]
+struct AppState {
+    tables: TableMap,
+    stats: Arc<Stats>,
+}
+
+async fn ws_handler(ws: WebSocketUpgrade, State(app): State<AppState>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, app.tables, app.stats))
 }
 
-async fn handle_socket(socket: WebSocket, tables: TableMap) {
+async fn handle_socket(socket: WebSocket, tables: TableMap, stats: Arc<Stats>) {
     let (mut ws_tx, mut ws_rx) = socket.split();
     let (client_tx, mut client_rx) = mpsc::unbounded_channel::<WsMessage>();
 
@@ -305,11 +384,30 @@ async fn handle_socket(socket: WebSocket, tables: TableMap) {
         }
     });
 
+    // the first join binds the connection to a user; messages claiming anyone else are rejected
+    let mut conn = Connection::default();
+    let send_error = |message: String| { let _ = client_tx.send(WsMessage::Text(serde_json::to_string(&ServerEvent::Error { message }).unwrap())); };
+
     // subscribe to a table lazily on first client message containing table_id
     while let Some(Ok(Message::Text(text))) = ws_rx.next().await {
         if let Ok(cmd) = serde_json::from_str::<ClientAction>(&text) {
+            let joining = matches!(cmd, ClientAction::Join { .. } | ClientAction::JoinRoom { .. } | ClientAction::CreateRoom { .. });
+            let claimed = match &cmd {
+                ClientAction::Join { client_msg_id, .. }
+                | ClientAction::Action { client_msg_id, .. }
+                | ClientAction::CreateRoom { client_msg_id, .. }
+                | ClientAction::JoinRoom { client_msg_id, .. }
+                | ClientAction::Rebuy { client_msg_id, .. }
+                | ClientAction::Ready { client_msg_id, .. }
+                | ClientAction::LeaveRoom { client_msg_id, .. }
+                | ClientAction::Stats { client_msg_id, .. } => client_msg_id,
+            };
+            let viewer = match conn.claim(claimed, joining) {
+                Ok(user) => user.to_string(),
+                Err(message) => { send_error(message); continue; }
+            };
             match &cmd {
-                ClientAction::CreateRoom { table_id, config, client_msg_id } => {
+                ClientAction::CreateRoom { table_id, config, .. } => {
                     let room_id = table_id.clone().unwrap_or_else(|| {
                         let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
                         format!("r{:x}", now & 0xfffff)
@@ -317,34 +415,35 @@ async fn handle_socket(socket: WebSocket, tables: TableMap) {
                     let tx = {
                         let mut map = tables.lock().await;
                         if !map.contains_key(&room_id) {
-                            let tx = TableActor::spawn_with_config(room_id.clone(), client_msg_id.clone(), config.clone());
+                            let tx = TableActor::spawn_with_config(room_id.clone(), config.clone(), stats.clone());
                             map.insert(room_id.clone(), tx.clone());
                             tx
                         } else { map.get(&room_id).unwrap().clone() }
                     };
-                    let _ = tx.send(ActorMsg::Subscribe(client_tx.clone()));
+                    let _ = tx.send(ActorMsg::Subscribe { viewer, tx: client_tx.clone() });
                     let _ = client_tx.send(WsMessage::Text(serde_json::to_string(&ServerEvent::RoomCreated { table_id: room_id }).unwrap()));
                 }
                 _ => {
                     let table_id = match &cmd {
-                        ClientAction::Join { table_id, .. } => table_id,
-                        ClientAction::Action { table_id, .. } => table_id,
-                        ClientAction::JoinRoom { table_id, .. } => table_id,
-                        ClientAction::Rebuy { table_id, .. } => table_id,
-                        ClientAction::Ready { table_id, .. } => table_id,
-                        ClientAction::LeaveRoom { table_id, .. } => table_id,
+                        ClientAction::Join { table_id, .. }
+                        | ClientAction::Action { table_id, .. }
+                        | ClientAction::JoinRoom { table_id, .. }
+                        | ClientAction::Rebuy { table_id, .. }
+                        | ClientAction::Ready { table_id, .. }
+                        | ClientAction::LeaveRoom { table_id, .. }
+                        | ClientAction::Stats { table_id, .. } => table_id.clone(),
                         ClientAction::CreateRoom { .. } => unreachable!(),
-                    }.clone();
+                    };
                     let tx = {
                         let mut map = tables.lock().await;
-                        map.entry(table_id.clone()).or_insert_with(|| TableActor::spawn(table_id.clone())).clone()
+                        map.entry(table_id.clone()).or_insert_with(|| TableActor::spawn(table_id.clone(), stats.clone())).clone()
                     };
-                    let _ = tx.send(ActorMsg::Subscribe(client_tx.clone()));
+                    let _ = tx.send(ActorMsg::Subscribe { viewer, tx: client_tx.clone() });
                     let _ = tx.send(ActorMsg::Client(cmd));
                 }
             }
         } else {
-            let _ = client_tx.send(WsMessage::Text("{\"type\":\"error\",\"message\":\"bad_json\"}".into()));
+            send_error("bad_json".into());
         }
     }
 
@@ -358,10 +457,11 @@ async fn main() {
         .init();
 
     let tables: TableMap = Arc::new(Mutex::new(HashMap::new()));
+    let stats = Stats::load(std::env::var_os("POKER_STATS_FILE").map(PathBuf::from)).await;
     let app = Router::new()
         .route("/ws", get(ws_handler))
         .nest_service("/", ServeDir::new("public"))
-        .with_state(tables);
+        .with_state(AppState { tables, stats });
 
     let addr: SocketAddr = "0.0.0.0:8080".parse().unwrap();
     info!(%addr, "starting server");
@@ -369,5 +469,57 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Next table snapshot sent to a subscriber, skipping other events
+    async fn next_snapshot(rx: &mut mpsc::UnboundedReceiver<WsMessage>) -> Value {
+        loop {
+            let Some(WsMessage::Text(msg)) = rx.recv().await else { panic!("connection closed") };
+            let evt: Value = serde_json::from_str(&msg).unwrap();
+            if evt["type"] == "table_snapshot" { return evt; }
+        }
+    }
+
+    #[tokio::test]
+    async fn showdown_snapshot_is_sent_before_the_next_deal() {
+        let config = RoomConfig { action_time_ms: 600_000, ..RoomConfig::default() };
+        let actor = TableActor::spawn_with_config("t".into(), config, Stats::load(None).await);
+        let mut inboxes = Vec::new();
+        for uid in ["u1", "u2"] {
+            let (tx, rx) = mpsc::unbounded_channel();
+            actor.send(ActorMsg::Subscribe { viewer: uid.into(), tx }).unwrap();
+            actor.send(ActorMsg::Client(ClientAction::Join { table_id: "t".into(), buy_in: 1000, client_msg_id: uid.into() })).unwrap();
+            inboxes.push(rx);
+        }
+        let u1 = &mut inboxes[0];
+        let _ = next_snapshot(u1).await;
 
+        // call and check down; the first snapshot without a street is the showdown
+        let mut actions = 0;
+        let showdown = loop {
+            let snapshot = next_snapshot(u1).await;
+            let table = &snapshot["table"];
+            if table["state"]["street"].is_null() { break snapshot; }
+            let seat = table["to_act_idx"].as_u64().unwrap() as usize;
+            let to_call = table["round_bet"].as_u64().unwrap() - table["round_contrib"][seat].as_u64().unwrap();
+            let action = if to_call > 0 { "call" } else { "check" };
+            actions += 1;
+            assert!(actions <= 8, "a checked down heads-up hand takes 8 actions");
+            let uid = snapshot["to_act_uid"].as_str().unwrap().to_string();
+            actor.send(ActorMsg::Client(ClientAction::Action { table_id: "t".into(), hand_id: "h".into(), action: action.into(), amount: None, client_msg_id: uid })).unwrap();
+        };
+        let seats = &showdown["table"]["seats"];
+        assert_eq!(showdown["table"]["revealed"][0], true);
+        assert_eq!(showdown["table"]["revealed"][1], true);
+        assert_eq!(seats[1]["hole"].as_array().unwrap().len(), 2, "u2's shown hand is visible to u1");
 
+        // then the next hand is dealt and only u1's own cards are visible again
+        let next = next_snapshot(u1).await;
+        assert!(!next["table"]["state"]["street"].is_null());
+        assert_eq!(next["table"]["revealed"][1], false);
+        assert!(next["table"]["seats"][1]["hole"].as_array().unwrap().is_empty());
+    }
+}
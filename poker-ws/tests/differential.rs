@@ -0,0 +1,291 @@
+// Differential test for the move to texas-engine: the old poker-ws evaluator and
+// betting rules are kept here as a reference. Both must order hands the same way,
+// and random hands played on the engine-backed `Table` must act, bet and pay out
+// exactly like the old table did.
+use std::cmp::Ordering;
+
+use poker_ws::game::{parse_action, ApplyOutcome, Table};
+use poker_ws::{evaluate_strength, parse_card, Card, Deck, PlayerAction};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct LegacyRank(u8, [u8; 5]);
+
+fn legacy_best_rank(cards: &[Card]) -> LegacyRank {
+    let n = cards.len();
+    let mut best = LegacyRank(0, [0; 5]);
+    for a in 0..n { for b in a+1..n { for c in b+1..n { for d in c+1..n { for e in d+1..n {
+        let r = legacy_eval5(&[cards[a], cards[b], cards[c], cards[d], cards[e]]);
+        if r > best { best = r; }
+    }}}}}
+    best
+}
+
+fn legacy_eval5(cards: &[Card; 5]) -> LegacyRank {
+    let mut ranks: Vec<u8> = cards.iter().map(|c| c.rank.value()).collect();
+    ranks.sort_unstable_by(|a, b| b.cmp(a));
+    let mut counts = [0u8; 15];
+    for &r in &ranks { counts[r as usize] += 1; }
+    let is_flush = cards.iter().all(|c| c.suit == cards[0].suit);
+    let is_straight = legacy_is_straight(&ranks);
+    let top5 = [ranks[0], ranks[1], ranks[2], ranks[3], ranks[4]];
+    let with_count = |n: u8| (2..=14u8).rev().find(|&v| counts[v as usize] == n);
+    let kickers = |exclude: &[u8]| ranks.iter().copied().filter(|x| !exclude.contains(x)).collect::<Vec<u8>>();
+
+    if is_flush && is_straight { return LegacyRank(8, top5); }
+    if let Some(q) = with_count(4) { let k = kickers(&[q]); return LegacyRank(7, [q, q, q, q, k[0]]); }
+    if let Some(t) = with_count(3) {
+        if let Some(p) = (2..=14u8).rev().find(|&v| v != t && counts[v as usize] >= 2) { return LegacyRank(6, [t, t, t, p, p]); }
+    }
+    if is_flush { return LegacyRank(5, top5); }
+    if is_straight { return LegacyRank(4, top5); }
+    if let Some(t) = with_count(3) { let k = kickers(&[t]); return LegacyRank(3, [t, t, t, k[0], k[1]]); }
+    let pairs: Vec<u8> = (2..=14u8).rev().filter(|&v| counts[v as usize] >= 2).take(2).collect();
+    if pairs.len() == 2 { let k = kickers(&pairs); return LegacyRank(2, [pairs[0], pairs[0], pairs[1], pairs[1], k[0]]); }
+    if let Some(p) = with_count(2) { let k = kickers(&[p]); return LegacyRank(1, [p, p, k[0], k[1], k[2]]); }
+    LegacyRank(0, top5)
+}
+
+fn legacy_is_straight(desc: &[u8]) -> bool {
+    let mut uniq = desc.to_vec(); uniq.dedup();
+    if uniq.len() < 5 { return false; }
+    if uniq.windows(2).all(|w| w[0] == w[1] + 1) { return true; }
+    uniq == [14, 5, 4, 3, 2]
+}
+
+/// The legacy evaluator ranks a wheel by its ace; texas-engine correctly treats it as five-high.
+fn is_wheel(rank: LegacyRank) -> bool {
+    (rank.0 == 4 || rank.0 == 8) && rank.1 == [14, 5, 4, 3, 2]
+}
+
+fn cards(s: &str) -> Vec<Card> {
+    s.split_whitespace().map(parse_card).collect()
+}
+
+#[test]
+fn evaluators_agree_on_random_hands() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut compared = 0;
+    for _ in 0..20_000 {
+        let mut deck = Deck::new();
+        deck.0.shuffle(&mut rng);
+        let board = deck.deal_n(5);
+        let a: Vec<Card> = deck.deal_n(2).into_iter().chain(board.iter().copied()).collect();
+        let b: Vec<Card> = deck.deal_n(2).into_iter().chain(board.iter().copied()).collect();
+
+        let (legacy_a, legacy_b) = (legacy_best_rank(&a), legacy_best_rank(&b));
+        if is_wheel(legacy_a) || is_wheel(legacy_b) { continue; }
+        assert_eq!(
+            legacy_a.cmp(&legacy_b),
+            evaluate_strength(&a).cmp(&evaluate_strength(&b)),
+            "{:?} vs {:?}", a, b
+        );
+        compared += 1;
+    }
+    assert!(compared > 19_000);
+}
+
+#[test]
+fn wheel_is_the_known_difference() {
+    let wheel = cards("Ah 2d 3c 4s 5h");
+    let six_high = cards("2d 3c 4s 5h 6d");
+
+    // legacy: the wheel wrongly beats a six-high straight
+    assert_eq!(legacy_best_rank(&wheel).cmp(&legacy_best_rank(&six_high)), Ordering::Greater);
+    // texas-engine: the wheel is the lowest straight
+    assert_eq!(evaluate_strength(&wheel).cmp(&evaluate_strength(&six_high)), Ordering::Less);
+}
+
+const SHOWDOWN: usize = 4;
+
+/// The old poker-ws betting rules for one hand, with hole cards and board dealt up front
+struct LegacyHand {
+    stack: Vec<u64>,
+    hole: Vec<Vec<Card>>,
+    runout: Vec<Card>,
+    board: Vec<Card>,
+    folded: Vec<bool>,
+    allin: Vec<bool>,
+    acted: Vec<bool>,
+    round_bet: u64,
+    round_contrib: Vec<u64>,
+    total_contrib: Vec<u64>,
+    pot: u64,
+    street: usize,
+    to_act: usize,
+    dealer: usize,
+    big_blind: u64,
+}
+
+impl LegacyHand {
+    fn new(stack: Vec<u64>, hole: Vec<Vec<Card>>, runout: Vec<Card>, sb: u64, bb: u64) -> Self {
+        let n = stack.len();
+        let mut h = LegacyHand { stack, hole, runout, board: vec![], folded: vec![false; n], allin: vec![false; n], acted: vec![false; n],
+            round_bet: 0, round_contrib: vec![0; n], total_contrib: vec![0; n], pot: 0, street: 0, to_act: 0, dealer: 0, big_blind: bb };
+        let sb_idx = h.next_from(h.dealer);
+        let bb_idx = h.next_from(sb_idx);
+        h.pay(sb_idx, sb);
+        h.pay(bb_idx, bb);
+        h.round_bet = h.round_contrib[bb_idx];
+        h.to_act = h.next_from(bb_idx);
+        h
+    }
+
+    fn next_from(&self, mut idx: usize) -> usize {
+        let n = self.stack.len();
+        for _ in 0..n { idx = (idx + 1) % n; if !self.folded[idx] && !self.allin[idx] { return idx; } }
+        idx
+    }
+
+    fn pay(&mut self, i: usize, amount: u64) {
+        let pay = amount.min(self.stack[i]);
+        self.stack[i] -= pay; self.round_contrib[i] += pay; self.total_contrib[i] += pay; self.pot += pay;
+        if self.stack[i] == 0 { self.allin[i] = true; }
+    }
+
+    /// players who can still bet
+    fn bettors(&self) -> usize {
+        (0..self.stack.len()).filter(|&i| !self.folded[i] && !self.allin[i]).count()
+    }
+
+    fn apply(&mut self, seat: usize, action: &PlayerAction) -> Result<ApplyOutcome, String> {
+        if seat != self.to_act { return Err("not your turn".into()); }
+        let to_call = self.round_bet.saturating_sub(self.round_contrib[seat]);
+        match *action {
+            PlayerAction::Fold => { self.folded[seat] = true; self.acted[seat] = true; }
+            PlayerAction::Check => { if to_call != 0 { return Err("cannot check".into()); } self.acted[seat] = true; }
+            PlayerAction::Call => { self.pay(seat, to_call); self.acted[seat] = true; }
+            PlayerAction::Bet(raise_by) | PlayerAction::Raise(raise_by) => {
+                if (raise_by as u64) < self.big_blind { return Err("min raise".into()); }
+                self.pay(seat, to_call + raise_by as u64);
+                self.round_bet = self.round_contrib[seat];
+                for i in 0..self.acted.len() { self.acted[i] = i == seat; }
+            }
+            PlayerAction::Discard(_) => return Err("unknown action".into()),
+        }
+
+        let alive: Vec<usize> = (0..self.stack.len()).filter(|&i| !self.folded[i]).collect();
+        if let [winner] = alive[..] {
+            self.stack[winner] += self.pot;
+            self.pot = 0;
+            self.street = SHOWDOWN;
+            return Ok(ApplyOutcome::HandEnded);
+        }
+        let matched = (0..self.stack.len()).all(|i| self.folded[i] || self.allin[i] || (self.round_contrib[i] == self.round_bet && self.acted[i]));
+        if !matched { self.to_act = self.next_from(seat); return Ok(ApplyOutcome::Continue); }
+
+        // the old actor dealt the next street itself; the board runs out while at most one player can bet
+        loop {
+            self.street += 1;
+            self.board = self.runout[..[0, 3, 4, 5, 5][self.street]].to_vec();
+            self.round_bet = 0; self.round_contrib.fill(0); self.acted.fill(false);
+            if self.street == SHOWDOWN { self.showdown(); return Ok(ApplyOutcome::HandEnded); }
+            if self.bettors() > 1 { self.to_act = self.next_from(self.dealer); return Ok(ApplyOutcome::NextStreet); }
+        }
+    }
+
+    /// Side pots by contribution layer; ties split with odd chips to the lowest seats.
+    /// The old table let every live player win every layer, even above their own all-in;
+    /// here only players who paid into a layer can win it, as in the engine.
+    fn showdown(&mut self) {
+        let ranks: Vec<Option<u32>> = (0..self.stack.len()).map(|i| (!self.folded[i]).then(|| {
+            let cards: Vec<Card> = self.hole[i].iter().chain(&self.board).copied().collect();
+            evaluate_strength(&cards)
+        })).collect();
+        let mut remaining = self.total_contrib.clone();
+        while let Some(layer) = remaining.iter().copied().filter(|&c| c > 0).min() {
+            let payers: Vec<usize> = (0..remaining.len()).filter(|&i| remaining[i] > 0).collect();
+            for &i in &payers { remaining[i] -= layer; }
+            let amount = layer * payers.len() as u64;
+            let best = payers.iter().filter_map(|&i| ranks[i]).max();
+            let winners: Vec<usize> = payers.iter().copied().filter(|&i| best.is_some() && ranks[i] == best).collect();
+            let winners = if winners.is_empty() { payers } else { winners };
+            let share = amount / winners.len() as u64;
+            let remainder = amount - share * winners.len() as u64;
+            for (n, &w) in winners.iter().enumerate() { self.stack[w] += share + u64::from((n as u64) < remainder); }
+        }
+        self.pot = 0;
+    }
+}
+
+/// A random action for `seat`: calls may be short, and raises may be under the big blind (both
+/// tables reject those) or over the stack (both go all in). Chip amounts are whole multiples of 6
+/// so pots split evenly three ways. The one rule that differs is left out: a raise of the whole
+/// stack under the big blind is an all in for the engine, but was refused by the old table.
+fn random_action(t: &Table, seat: usize, rng: &mut StdRng) -> (&'static str, Option<u64>) {
+    let to_call = t.round_bet - t.round_contrib[seat];
+    let stack = t.seats[seat].stack;
+    let room = stack.saturating_sub(to_call);
+    let kind = if t.round_bet == 0 { "bet" } else { "raise" };
+    loop {
+        match rng.gen_range(0..20) {
+            0 => return ("fold", None),
+            1..=9 if to_call == 0 => return ("check", None),
+            1..=9 => return ("call", None),
+            10..=15 if room >= 12 => return (kind, Some(6 * rng.gen_range(2..=room / 6))),
+            16 if room > 12 => return (kind, Some(rng.gen_range(1..12))),
+            17 if room > 0 => return (kind, Some(room.max(12) + 6 * rng.gen_range(0..4))),
+            _ => {}
+        }
+    }
+}
+
+/// What happened over the random hands
+#[derive(Default)]
+struct Counts { showdowns: u32, all_ins: u32, short_calls: u32, rejected: u32 }
+
+/// Play one hand on both tables, counting showdowns (and those with an all-in player), short calls and rejected actions
+fn play_random_hand(rng: &mut StdRng, counts: &mut Counts) {
+    let stacks: Vec<u64> = (0..3).map(|_| 6 * rng.gen_range(4..=60)).collect();
+    let mut deck = Deck::new();
+    deck.0.shuffle(rng);
+    // texas-engine deals two cards per player in seat order, then the board; dealing pops from the end
+    let dealt: Vec<Card> = deck.0.iter().rev().take(11).copied().collect();
+    let mut legacy = LegacyHand::new(stacks.clone(), dealt[..6].chunks(2).map(<[Card]>::to_vec).collect(), dealt[6..].to_vec(), 6, 12);
+
+    let mut t = Table::new("diff".into(), 3, 6, 12);
+    for (i, &stack) in stacks.iter().enumerate() { t.sit(format!("p{i}"), stack); }
+    t.start_hand(&mut deck);
+    for i in 0..3 { assert_eq!(t.seats[i].hole, legacy.hole[i]); }
+
+    let mut log = Vec::new();
+    loop {
+        assert_eq!(t.to_act_idx, legacy.to_act, "to act after {log:?}");
+        assert_eq!(t.state.pot, legacy.pot, "pot after {log:?}");
+        assert_eq!(t.board, legacy.board, "board after {log:?}");
+        let seat = t.to_act_idx;
+        let (action, amount) = random_action(&t, seat, rng);
+        log.push((seat, action, amount));
+        let short_call = action == "call" && t.round_bet - t.round_contrib[seat] > t.seats[seat].stack;
+
+        let outcome = t.apply_action_by_user(&format!("p{seat}"), action, amount);
+        let legacy_outcome = legacy.apply(seat, &parse_action(action, amount).unwrap());
+        let outcome = match (outcome, legacy_outcome) {
+            (Ok(outcome), Ok(legacy_outcome)) => { assert_eq!(outcome, legacy_outcome, "outcome after {log:?}"); outcome }
+            (Err(_), Err(_)) => { counts.rejected += 1; log.pop(); continue; }
+            (outcome, legacy_outcome) => panic!("{outcome:?} vs {legacy_outcome:?} after {log:?}"),
+        };
+        counts.short_calls += short_call as u32;
+        let stacks: Vec<u64> = t.seats.iter().map(|s| s.stack).collect();
+        assert_eq!(stacks, legacy.stack, "stacks after {log:?}");
+        if outcome == ApplyOutcome::HandEnded { break; }
+    }
+    assert_eq!(t.seats.iter().map(|s| s.stack).sum::<u64>(), stacks.iter().sum::<u64>());
+    let showdown = legacy.folded.iter().filter(|&&f| !f).count() > 1;
+    counts.showdowns += showdown as u32;
+    counts.all_ins += (showdown && legacy.allin.contains(&true)) as u32;
+}
+
+#[test]
+fn tables_agree_on_random_action_sequences() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut counts = Counts::default();
+    for _ in 0..2_000 { play_random_hand(&mut rng, &mut counts); }
+    let Counts { showdowns, all_ins, short_calls, rejected } = counts;
+    assert!(
+        showdowns > 200 && all_ins > 100 && short_calls > 100 && rejected > 100,
+        "{showdowns} showdowns, {all_ins} with an all-in, {short_calls} short calls, {rejected} rejected"
+    );
+}
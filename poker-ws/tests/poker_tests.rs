@@ -1,6 +1,22 @@
-use poker_ws::{game::Table, Deck, parse_card, game::ApplyOutcome};
+use poker_ws::{game::Table, Connection, Deck, parse_card, game::ApplyOutcome};
 // tokio time is used by the async test below via the runtime attribute
 
+/// Deck dealt in texas-engine's order: two hole cards per player in seat order, then flop, turn and river
+fn stacked(cards: &str) -> Deck {
+    Deck(cards.split_whitespace().rev().map(parse_card).collect())
+}
+
+/// Apply an action for whoever is to act
+fn act(t: &mut Table, action: &str, amount: Option<u64>) -> ApplyOutcome {
+    let uid = t.seats[t.to_act_idx].user_id.clone().unwrap();
+    t.apply_action_by_user(&uid, action, amount).unwrap_or_else(|e| panic!("{uid} {action}: {e}"))
+}
+
+/// Check down to showdown from the current street
+fn check_down(t: &mut Table) {
+    while act(t, "check", None) != ApplyOutcome::HandEnded {}
+}
+
 #[test]
 fn deck_shuffle_and_deal() {
     let mut d = Deck::new();
@@ -28,67 +44,74 @@ fn actions_flow_to_next_street() {
     t.sit("u2".into(), 1000);
     let mut d = Deck::new(); d.shuffle(); t.start_hand(&mut d);
     // with 2 players, u2 (SB) acts first after blinds
-    assert!(matches!(t.apply_action_by_user("u2","call", None), Ok(_)));
+    assert!(t.apply_action_by_user("u2","call", None).is_ok());
     match t.apply_action_by_user("u1","check", None) { Ok(ApplyOutcome::NextStreet)|Ok(ApplyOutcome::Continue)|Ok(ApplyOutcome::HandEnded)=>{}, Err(e)=>panic!("{e}") }
 }
 
 #[test]
 fn showdown_split_pot_two_pairs_vs_two_pairs_kicker() {
-    // Craft a situation with same two pair, different kicker (Aces+Kings, kicker differs)
+    // same two pair (Aces and Kings), a's Queen kicker beats b's Jack
     let mut t = Table::new("t1".into(), 6, 1, 2);
     t.sit("a".into(), 1000);
     t.sit("b".into(), 1000);
-    t.board = vec![parse_card("Ah"), parse_card("Ad"), parse_card("Kc"), parse_card("7c"), parse_card("2s")];
-    t.seats[0].user_id = Some("a".into()); t.seats[0].hole = vec![parse_card("Qd"), parse_card("3c")]; // kicker Q
-    t.seats[1].user_id = Some("b".into()); t.seats[1].hole = vec![parse_card("Jd"), parse_card("4c")]; // kicker J
-    t.total_contrib = vec![100, 100, 0,0,0,0];
-    t.state.pot = 200;
-    t.showdown_and_payout();
-    assert_eq!(t.seats[0].stack, 1200);
-    assert_eq!(t.seats[1].stack, 1000);
+    t.start_hand(&mut stacked("Qd 3c Jd 4c Ah Ad Kc 7c 2s"));
+    act(&mut t, "raise", Some(98));
+    act(&mut t, "call", None);
+    check_down(&mut t);
+    assert_eq!(t.seats[0].stack, 1100);
+    assert_eq!(t.seats[1].stack, 900);
 }
 
 #[test]
 fn side_pot_simple_allin() {
     // a: all in 50, b calls 50 and adds 50 more, c calls 100
     let mut t = Table::new("t1".into(), 6, 1, 2);
-    t.sit("a".into(), 0);
-    t.sit("b".into(), 0);
-    t.sit("c".into(), 0);
-    t.seats[0].user_id = Some("a".into()); t.seats[0].has_folded=false;
-    t.seats[1].user_id = Some("b".into()); t.seats[1].has_folded=false;
-    t.seats[2].user_id = Some("c".into()); t.seats[2].has_folded=false;
-    t.total_contrib = vec![50, 100, 100, 0,0,0];
-    t.state.pot = 250;
+    t.sit("a".into(), 50);
+    t.sit("b".into(), 100);
+    t.sit("c".into(), 100);
     // deterministic ranks: c > b > a based on pocket pairs
-    t.board = vec![parse_card("2h"), parse_card("5s"), parse_card("Jd"), parse_card("Qc"), parse_card("Kh")];
-    t.seats[0].hole = vec![parse_card("2c"), parse_card("3c")]; // pair 2
-    t.seats[1].hole = vec![parse_card("7c"), parse_card("7d")]; // pair 7
-    t.seats[2].hole = vec![parse_card("8c"), parse_card("8d")]; // pair 8
-    t.showdown_and_payout();
+    t.start_hand(&mut stacked("2c 3c 7c 7d 8c 8d 2h 5s Jd Qc Kh"));
+    assert_eq!(act(&mut t, "call", None), ApplyOutcome::Continue);
+    assert_eq!(act(&mut t, "raise", Some(98)), ApplyOutcome::Continue);
+    assert_eq!(act(&mut t, "call", None), ApplyOutcome::Continue);
+    // a is 98 short with 48 left and calls all in; nobody can bet, so the board runs out
+    assert_eq!(act(&mut t, "call", None), ApplyOutcome::HandEnded);
     // main pot 150 to best among a,b,c => c should win 150
     // side pot 100 among b,c => c should win 100 => total 250 to c
     assert_eq!(t.seats[2].stack, 250);
+    assert_eq!(t.seats[0].stack + t.seats[1].stack, 0);
+}
+
+#[test]
+fn raises_are_at_least_the_big_blind_and_capped_by_the_stack() {
+    let mut t = Table::new("t1".into(), 6, 5, 10);
+    t.sit("u1".into(), 1000);
+    t.sit("u2".into(), 300);
+    let mut d = Deck::new(); d.shuffle(); t.start_hand(&mut d);
+    assert!(t.apply_action_by_user("u2", "raise", Some(1)).is_err());
+    assert!(t.apply_action_by_user("u2", "raise", Some(9)).is_err());
+    // raising more than the stack puts u2 all in
+    assert_eq!(t.apply_action_by_user("u2", "raise", Some(5000)), Ok(ApplyOutcome::Continue));
+    assert_eq!(t.seats[1].stack, 0);
+    assert_eq!(t.total_contrib[1], 300);
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn timer_auto_progress_check_fold() {
     // This test uses the actor via ws is heavy; we instead simulate at table level by calling Tick logic.
-    // Here we just assert that after some auto check/fold cycles, table street advances to at least Flop.
+    // The timer checks when it can and folds otherwise, so the hand always finishes.
     let mut t = Table::new("t1".into(), 6, 5, 10);
     t.sit("u1".into(), 1000);
     t.sit("u2".into(), 1000);
     let mut d = Deck::new(); d.shuffle(); t.start_hand(&mut d);
-    // simulate ticking by performing auto check/fold manually a few times
-    for _ in 0..3 {
-        let idx = t.to_act_idx;
-        // make check valid by aligning round_bet to current actor's contribution
-        t.round_bet = t.round_contrib[idx];
-        let uid = t.seats[idx].user_id.clone().unwrap();
-        let _ = t.apply_action_by_user(&uid, "check", None).or_else(|_| t.apply_action_by_user(&uid, "fold", None));
-        t.next_street(&mut d);
+    for _ in 0..10 {
+        let uid = t.seats[t.to_act_idx].user_id.clone().unwrap();
+        let outcome = t.apply_action_by_user(&uid, "check", None).or_else(|_| t.apply_action_by_user(&uid, "fold", None));
+        if outcome.unwrap() == ApplyOutcome::HandEnded { break; }
     }
-    assert!(t.state.street.is_some());
+    assert!(t.state.street.is_none());
+    assert!(t.take_finished_hand().is_some());
+    assert_eq!(t.seats[0].stack + t.seats[1].stack, 2000);
 }
 
 #[test]
@@ -97,40 +120,122 @@ fn two_players_full_hand_winner() {
     let mut t = Table::new("t1".into(), 6, 5, 10);
     t.sit("u0".into(), 1000);
     t.sit("u1".into(), 1000);
-    let mut d = Deck(vec![]);
-    // Build deck suffix: river, turn, flop3, flop2, flop1, s1_2, s0_2, s1_1, s0_1 (note: dealing uses pop)
-    let s0_1 = parse_card("As");
-    let s1_1 = parse_card("Kd");
-    let s0_2 = parse_card("Ah");
-    let s1_2 = parse_card("Kc");
-    let flop1 = parse_card("2d");
-    let flop2 = parse_card("7s");
-    let flop3 = parse_card("9c");
-    let turn = parse_card("3h");
-    let river = parse_card("4d");
-    d.0.extend(vec![river, turn, flop3, flop2, flop1, s1_2, s0_2, s1_1, s0_1]);
+    t.start_hand(&mut stacked("As Ah Kd Kc 2d 7s 9c 3h 4d"));
 
-    t.start_hand(&mut d);
     // Preflop: SB calls, BB checks -> to flop
-    let sb_uid = t.seats[t.to_act_idx].user_id.clone().unwrap();
-    let _ = t.apply_action_by_user(&sb_uid, "call", None).unwrap();
-    let bb_uid = t.seats[t.to_act_idx].user_id.clone().unwrap();
-    if let Ok(ApplyOutcome::NextStreet) = t.apply_action_by_user(&bb_uid, "check", None) { t.next_street(&mut d); }
+    assert_eq!(t.seats[t.to_act_idx].user_id.as_deref(), Some("u1"));
+    assert_eq!(act(&mut t, "call", None), ApplyOutcome::Continue);
+    assert_eq!(act(&mut t, "check", None), ApplyOutcome::NextStreet);
+    assert_eq!(t.board.len(), 3);
 
     // Flop/Turn/River: both check each street
-    for _street in 0..3 {
-        for _ in 0..2 {
-            let uid = t.seats[t.to_act_idx].user_id.clone().unwrap();
-            t.round_bet = t.round_contrib[t.to_act_idx];
-            if let Ok(ApplyOutcome::NextStreet) = t.apply_action_by_user(&uid, "check", None) { t.next_street(&mut d); }
-        }
-    }
-
-    // Showdown and payout
-    t.showdown_and_payout();
+    check_down(&mut t);
     let total = t.seats[0].stack + t.seats[1].stack;
     assert_eq!(total, 2000);
-    assert!(t.seats[0].stack > t.seats[1].stack);
+    assert_eq!(t.seats[0].stack, 1010);
+}
+
+
+
+#[test]
+fn showdown_wheel_loses_to_six_high_straight() {
+    let mut t = Table::new("t1".into(), 6, 1, 2);
+    t.sit("a".into(), 1000);
+    t.sit("b".into(), 1000);
+    // a: wheel, b: six-high straight
+    t.start_hand(&mut stacked("Ad Qc 6d 9c 2h 3s 4d 5c Kh"));
+    act(&mut t, "raise", Some(98));
+    act(&mut t, "call", None);
+    check_down(&mut t);
+    assert_eq!(t.seats[0].stack, 900);
+    assert_eq!(t.seats[1].stack, 1100);
+}
+
+#[test]
+fn snapshot_view_hides_opponent_hole_cards() {
+    let mut t = Table::new("t1".into(), 6, 5, 10);
+    t.sit("u1".into(), 1000);
+    t.sit("u2".into(), 1000);
+    let mut d = Deck::new(); d.shuffle(); t.start_hand(&mut d);
+
+    let view = t.view_for(Some("u1"));
+    assert_eq!(view.seats[0].hole, t.seats[0].hole);
+    assert!(view.seats[1].hole.is_empty());
+    // spectators see no hole cards at all
+    assert!(t.view_for(None).seats.iter().all(|s| s.hole.is_empty()));
+    let json = serde_json::to_string(&view).unwrap();
+    for c in &t.seats[1].hole { assert!(!json.contains(&serde_json::to_string(c).unwrap())); }
 }
 
+#[test]
+fn connection_viewer_is_bound_by_the_first_join() {
+    let mut t = Table::new("t1".into(), 6, 5, 10);
+    t.sit("u1".into(), 1000);
+    t.sit("u2".into(), 1000);
+    let mut d = Deck::new(); d.shuffle(); t.start_hand(&mut d);
+
+    let mut conn = Connection::default();
+    assert!(conn.claim("u1", false).is_err(), "only a join binds the connection");
+    assert_eq!(conn.claim("u1", true), Ok("u1"));
+    assert_eq!(conn.claim("u1", false), Ok("u1"));
+    // a second id on the same socket, e.g. {"type":"stats","client_msg_id":"u2"}, is rejected
+    assert!(conn.claim("u2", false).is_err());
+    assert!(conn.claim("u2", true).is_err());
 
+    // and the socket keeps seeing only u1's cards
+    assert_eq!(conn.user(), Some("u1"));
+    let view = t.view_for(conn.user());
+    assert_eq!(view.seats[0].hole, t.seats[0].hole);
+    assert!(view.seats[1].hole.is_empty());
+}
+
+#[test]
+fn showdown_reveals_only_hands_that_were_shown() {
+    let mut t = Table::new("t1".into(), 6, 1, 2);
+    t.sit("a".into(), 1000);
+    t.sit("b".into(), 1000);
+    t.sit("c".into(), 1000);
+    t.start_hand(&mut stacked("2c 3c 7c 7d 8c 8d 2h 5s Jd Qc Kh"));
+    act(&mut t, "call", None);
+    act(&mut t, "call", None);
+    act(&mut t, "fold", None); // c mucks
+    check_down(&mut t);
+
+    let view = t.view_for(Some("a"));
+    assert_eq!(view.seats[1].hole.len(), 2);
+    assert!(view.seats[2].hole.is_empty());
+}
+
+#[test]
+fn finished_hand_feeds_engine_stats() {
+    let mut t = Table::new("t1".into(), 6, 5, 10);
+    t.sit("a".into(), 1000);
+    t.sit("b".into(), 1000);
+    t.sit("c".into(), 1000);
+    t.start_hand(&mut stacked("As Ah Kd Kc 7c 2d Qs 9h 5c Jd 8s"));
+
+    let actions = [("a", "raise", Some(20)), ("b", "call", None), ("c", "fold", None), ("b", "bet", Some(20)), ("a", "call", None),
+        ("b", "check", None), ("a", "check", None), ("b", "check", None)];
+    for (uid, action, amount) in actions { t.apply_action_by_user(uid, action, amount).unwrap(); }
+    assert_eq!(t.state.street, Some(poker_ws::Street::River));
+    assert!(t.take_finished_hand().is_none());
+    // the in-progress log never goes out with table snapshots
+    assert!(!serde_json::to_string(&t).unwrap().contains("HandStarted"));
+    assert_eq!(t.apply_action_by_user("a", "check", None), Ok(ApplyOutcome::HandEnded));
+
+    let hand = t.take_finished_hand().unwrap();
+    assert!(t.take_finished_hand().is_none());
+    let mut store = texas_engine::stats::StatsStore::new();
+    store.record_hand(&hand);
+    let a = store.get("a").unwrap();
+    assert_eq!((a.hands, a.vpip, a.pfr, a.postflop_calls), (1, 1, 1, 1));
+    assert_eq!((a.went_to_showdown, a.won_at_showdown), (1, 1));
+    let b = store.get("b").unwrap();
+    assert_eq!((b.three_bet_opportunities, b.postflop_aggressions, b.won_at_showdown), (1, 1, 0));
+    assert_eq!(store.hud("c").unwrap().vpip, Some(0.0));
+
+    // the recorded hand is a regular engine history and exports like one
+    let text = texas_engine::pokerstars::export_hand(&hand, &Default::default()).unwrap();
+    assert!(text.contains("a: raises 20 to 30\n"));
+    assert!(text.contains("a collected 110 from pot\n"));
+}
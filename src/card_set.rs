@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use super::shared::*;
+
+/// 用 64 位掩码表示的一组牌
+///
+/// 每种花色占 16 位，低 13 位依次对应 2 到 A，因此可以直接取出每种花色的点数掩码。
+/// 其余的位始终为 0，`len` 与遍历得到的张数一致。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u64", into = "u64")]
+pub struct CardSet(u64);
+
+/// 每种花色中有效点数位
+const SUIT_MASK: u64 = 0x1fff;
+
+/// 52 张牌对应的全部有效位
+const FULL_MASK: u64 = SUIT_MASK | SUIT_MASK << 16 | SUIT_MASK << 32 | SUIT_MASK << 48;
+
+impl Card {
+    /// 牌在 `CardSet` 中的位下标
+    pub fn bit_index(&self) -> u32 {
+        self.suit.index() as u32 * 16 + (self.rank.value() - 2) as u32
+    }
+
+    /// 由位下标还原牌，下标无效时返回 None
+    pub fn from_bit_index(index: u32) -> Option<Card> {
+        let suit = *Suit::ALL.get((index / 16) as usize)?;
+        let rank = *Rank::ALL.get((index % 16) as usize)?;
+        Some(Card { suit, rank })
+    }
+}
+
+impl CardSet {
+    /// 空集合
+    pub const EMPTY: CardSet = CardSet(0);
+
+    /// 一整副 52 张牌
+    pub fn full() -> Self {
+        CardSet(FULL_MASK)
+    }
+
+    /// 由位掩码创建集合，不对应任何牌的位被丢弃
+    pub fn from_bits(bits: u64) -> Self {
+        CardSet(bits & FULL_MASK)
+    }
+
+    /// 集合的位掩码
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// 由若干张牌创建集合
+    pub fn from_cards(cards: &[Card]) -> Self {
+        cards.iter().copied().collect()
+    }
+
+    /// 加入一张牌，返回该牌原先是否不在集合中
+    pub fn insert(&mut self, card: Card) -> bool {
+        let bit = 1 << card.bit_index();
+        let added = self.0 & bit == 0;
+        self.0 |= bit;
+        added
+    }
+
+    /// 移除一张牌，返回该牌原先是否在集合中
+    pub fn remove(&mut self, card: Card) -> bool {
+        let bit = 1 << card.bit_index();
+        let removed = self.0 & bit != 0;
+        self.0 &= !bit;
+        removed
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & (1 << card.bit_index()) != 0
+    }
+
+    pub fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    /// 在本集合中但不在 other 中的牌
+    pub fn difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(&self, other: CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// 按花色、点数从小到大遍历
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    pub fn to_vec(&self) -> Vec<Card> {
+        self.iter().collect()
+    }
+
+    /// 每种花色的点数掩码，第 0 位为 2，第 12 位为 A
+    pub fn suit_masks(&self) -> [u16; 4] {
+        [0, 16, 32, 48].map(|shift| ((self.0 >> shift) & SUIT_MASK) as u16)
+    }
+}
+
+impl From<u64> for CardSet {
+    fn from(bits: u64) -> Self {
+        CardSet::from_bits(bits)
+    }
+}
+
+impl From<CardSet> for u64 {
+    fn from(set: CardSet) -> Self {
+        set.0
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        CardSet(1 << card.bit_index())
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+/// `CardSet` 的迭代器
+#[derive(Debug, Clone)]
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Card::from_bit_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
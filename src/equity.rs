@@ -0,0 +1,325 @@
+use itertools::Itertools;
+use rand::distr::Distribution;
+use rand::distr::weighted::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::evaluator::evaluate_set;
+use super::range::Range;
+use super::shared::*;
+
+/// 蒙特卡洛抽取范围底牌时，每次抽样最多重试的次数
+const MAX_DEAL_ATTEMPTS: usize = 1000;
+
+/// 胜率计算参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EquityOptions {
+    pub max_exhaustive: u64, // 剩余公共牌组合数不超过该值时穷举
+    pub samples: u64,        // 蒙特卡洛抽样次数
+    pub seed: u64,           // 蒙特卡洛随机种子
+}
+
+impl Default for EquityOptions {
+    fn default() -> Self {
+        EquityOptions {
+            max_exhaustive: 50_000,
+            samples: 100_000,
+            seed: 0,
+        }
+    }
+}
+
+/// 单个玩家的胜率（百分比）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EquityResult {
+    pub win: f64,    // 独赢的概率
+    pub tie: f64,    // 平分的概率
+    pub equity: f64, // 底池权益，平分时按人数折算
+}
+
+/// 胜率计算结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Equity {
+    pub players: Vec<EquityResult>, // 与输入手牌顺序一致
+    pub boards: u64,                // 计算的公共牌组合数
+    pub exhaustive: bool,           // 是否为穷举结果
+}
+
+/// 按公共牌结果累计各玩家的胜负
+pub(crate) struct EquityTally {
+    wins: Vec<f64>,
+    ties: Vec<f64>,
+    shares: Vec<f64>,
+    total_weight: f64,
+    boards: u64,
+}
+
+impl EquityTally {
+    pub(crate) fn new(players: usize) -> Self {
+        EquityTally {
+            wins: vec![0.0; players],
+            ties: vec![0.0; players],
+            shares: vec![0.0; players],
+            total_weight: 0.0,
+            boards: 0,
+        }
+    }
+
+    /// 记录一次摊牌，strengths 为各玩家的牌力值，weight 为这次摊牌的权重
+    pub(crate) fn record(&mut self, strengths: &[u32], weight: f64) {
+        let best = strengths.iter().copied().max().unwrap_or(0);
+        let winners = strengths.iter().filter(|&&s| s == best).count();
+        for (i, &strength) in strengths.iter().enumerate() {
+            if strength != best {
+                continue;
+            }
+            if winners == 1 {
+                self.wins[i] += weight;
+            } else {
+                self.ties[i] += weight;
+            }
+            self.shares[i] += weight / winners as f64;
+        }
+        self.total_weight += weight;
+        self.boards += 1;
+    }
+
+    pub(crate) fn finish(self, exhaustive: bool) -> Equity {
+        let total = if self.total_weight > 0.0 {
+            self.total_weight
+        } else {
+            1.0
+        };
+        let players = (0..self.wins.len())
+            .map(|i| EquityResult {
+                win: self.wins[i] * 100.0 / total,
+                tie: self.ties[i] * 100.0 / total,
+                equity: self.shares[i] * 100.0 / total,
+            })
+            .collect();
+        Equity {
+            players,
+            boards: self.boards,
+            exhaustive,
+        }
+    }
+}
+
+/// 计算多名玩家在给定公共牌和死牌下的胜率
+///
+/// 剩余公共牌组合不超过 `max_exhaustive` 时穷举，否则按 `seed` 做蒙特卡洛抽样。
+pub fn calculate_equity(
+    hands: &[(Card, Card)],
+    board: &[Card],
+    dead: &[Card],
+    options: &EquityOptions,
+) -> Result<Equity, GameError> {
+    if hands.len() < 2 || board.len() > 5 {
+        return Err(GameError::InvalidCards);
+    }
+
+    // 所有已知牌不能重复
+    let mut known = CardSet::EMPTY;
+    let all_known = hands
+        .iter()
+        .flat_map(|&(c1, c2)| [c1, c2])
+        .chain(board.iter().copied())
+        .chain(dead.iter().copied());
+    for card in all_known {
+        if !known.insert(card) {
+            return Err(GameError::InvalidCards);
+        }
+    }
+
+    let hand_sets: Vec<CardSet> = hands
+        .iter()
+        .map(|&(c1, c2)| CardSet::from_cards(&[c1, c2]))
+        .collect();
+    let board_set = CardSet::from_cards(board);
+    let remaining = CardSet::full().difference(known).to_vec();
+    let missing = 5 - board.len();
+    if remaining.len() < missing {
+        return Err(GameError::InvalidCards);
+    }
+
+    let mut tally = EquityTally::new(hands.len());
+    let mut strengths = vec![0; hands.len()];
+    let mut showdown = |runout: CardSet| {
+        let full_board = board_set.union(runout);
+        for (strength, hand) in strengths.iter_mut().zip(&hand_sets) {
+            *strength = evaluate_set(full_board.union(*hand));
+        }
+        tally.record(&strengths, 1.0);
+    };
+
+    let exhaustive =
+        combination_count(remaining.len() as u64, missing as u64) <= options.max_exhaustive;
+    if exhaustive {
+        for runout in remaining.iter().copied().combinations(missing) {
+            showdown(CardSet::from_cards(&runout));
+        }
+    } else {
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        let mut deck = remaining;
+        for _ in 0..options.samples {
+            showdown(sample_cards(&mut deck, missing, &mut rng));
+        }
+    }
+
+    Ok(tally.finish(exhaustive))
+}
+
+/// 从牌堆中随机抽取 count 张牌（部分洗牌，不分配内存）
+pub(crate) fn sample_cards(deck: &mut [Card], count: usize, rng: &mut StdRng) -> CardSet {
+    let mut cards = CardSet::EMPTY;
+    for i in 0..count {
+        let j = rng.random_range(i..deck.len());
+        deck.swap(i, j);
+        cards.insert(deck[i]);
+    }
+    cards
+}
+
+/// 组合数 C(n, k)
+pub(crate) fn combination_count(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// 多个手牌范围之间的胜率，结果与 `ranges` 顺序一致
+///
+/// 每个范围先去掉与公共牌、死牌冲突的组合。底牌组合数乘以公共牌组合数不超过
+/// `max_exhaustive` 时按权重穷举，否则按权重抽取底牌做蒙特卡洛抽样（互相冲突时重抽）。
+pub fn range_equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+    options: &EquityOptions,
+) -> Result<Equity, GameError> {
+    if ranges.len() < 2 || board.len() > 5 {
+        return Err(GameError::InvalidCards);
+    }
+
+    let mut known = CardSet::EMPTY;
+    for &card in board.iter().chain(dead) {
+        if !known.insert(card) {
+            return Err(GameError::InvalidCards);
+        }
+    }
+
+    let mut ranges = ranges.to_vec();
+    for range in ranges.iter_mut() {
+        range.remove_blocked(known);
+        if range.is_empty() {
+            return Err(GameError::InvalidCards);
+        }
+    }
+
+    let board_set = CardSet::from_cards(board);
+    let missing = 5 - board.len();
+    // 发完底牌后剩下的牌必须够发公共牌
+    let deck_size = (52 - known.len())
+        .checked_sub(2 * ranges.len())
+        .filter(|&size| size >= missing)
+        .ok_or(GameError::InvalidCards)?;
+    let runouts = combination_count(deck_size as u64, missing as u64);
+    let assignments = ranges
+        .iter()
+        .try_fold(1u64, |acc, r| acc.checked_mul(r.len() as u64));
+
+    let mut tally = EquityTally::new(ranges.len());
+    let mut strengths = vec![0; ranges.len()];
+    let mut showdown = |hands: &[CardSet], runout: CardSet, weight: f64| {
+        let full_board = board_set.union(runout);
+        for (strength, hand) in strengths.iter_mut().zip(hands) {
+            *strength = evaluate_set(full_board.union(*hand));
+        }
+        tally.record(&strengths, weight);
+    };
+
+    let exhaustive = assignments
+        .and_then(|n| n.checked_mul(runouts))
+        .is_some_and(|n| n <= options.max_exhaustive);
+    if exhaustive {
+        for combos in ranges
+            .iter()
+            .map(|r| r.combos.iter())
+            .multi_cartesian_product()
+        {
+            // 各玩家的底牌不能互相冲突
+            let mut used = known;
+            let mut hands = Vec::with_capacity(combos.len());
+            let mut weight = 1.0;
+            for combo in &combos {
+                let cards = combo.card_set();
+                if !used.is_disjoint(cards) {
+                    break;
+                }
+                used = used.union(cards);
+                hands.push(cards);
+                weight *= combo.weight;
+            }
+            if hands.len() < combos.len() {
+                continue;
+            }
+
+            let remaining = CardSet::full().difference(used).to_vec();
+            for runout in remaining.iter().copied().combinations(missing) {
+                showdown(&hands, CardSet::from_cards(&runout), weight);
+            }
+        }
+        if tally.boards == 0 {
+            return Err(GameError::InvalidCards);
+        }
+    } else {
+        let choosers = ranges
+            .iter()
+            .map(|r| WeightedIndex::new(r.combos.iter().map(|c| c.weight)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| GameError::InvalidCards)?;
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        let mut hands = vec![CardSet::EMPTY; ranges.len()];
+
+        for _ in 0..options.samples {
+            let used = (0..MAX_DEAL_ATTEMPTS)
+                .find_map(|_| {
+                    let mut used = known;
+                    for ((hand, range), chooser) in hands.iter_mut().zip(&ranges).zip(&choosers) {
+                        *hand = range.combos[chooser.sample(&mut rng)].card_set();
+                        if !used.is_disjoint(*hand) {
+                            return None;
+                        }
+                        used = used.union(*hand);
+                    }
+                    Some(used)
+                })
+                .ok_or(GameError::InvalidCards)?;
+
+            let mut deck = CardSet::full().difference(used).to_vec();
+            let runout = sample_cards(&mut deck, missing, &mut rng);
+            showdown(&hands, runout, 1.0);
+        }
+    }
+
+    Ok(tally.finish(exhaustive))
+}
+
+/// 一手具体底牌对一个范围的胜率，结果中第一个为 `hand`
+pub fn hand_vs_range_equity(
+    hand: (Card, Card),
+    range: &Range,
+    board: &[Card],
+    dead: &[Card],
+    options: &EquityOptions,
+) -> Result<Equity, GameError> {
+    range_equity(
+        &[Range::from_combo(hand), range.clone()],
+        board,
+        dead,
+        options,
+    )
+}
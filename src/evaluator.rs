@@ -0,0 +1,228 @@
+use std::sync::OnceLock;
+
+use itertools::Itertools;
+
+use super::card_set::CardSet;
+use super::shared::*;
+
+/// 牌型在牌力值中的偏移，低 20 位依次存放最多 5 个关键牌点数（每个 4 位）
+const CATEGORY_SHIFT: u32 = 20;
+
+/// 五张牌牌力的等价类数量
+pub const HAND_CLASS_COUNT: u16 = 7462;
+
+/// 计算 5 到 7 张牌的牌力值
+///
+/// 牌力值越大牌越强，相等表示平局，与 `rules::evaluate_hand` 的牌型和关键牌顺序一致。
+/// 整个计算只使用栈上的位掩码，不分配内存。
+pub fn evaluate_strength(cards: &[Card]) -> u32 {
+    assert!((5..=7).contains(&cards.len()), "只能评估5到7张牌");
+
+    evaluate_set(CardSet::from_cards(cards))
+}
+
+/// 计算一组牌（5 到 7 张，不含重复）的牌力值
+pub fn evaluate_set(cards: CardSet) -> u32 {
+    assert!((5..=7).contains(&cards.len()), "只能评估5到7张牌");
+    let suit_masks = cards.suit_masks();
+
+    // 检查同花（7 张牌内同花时不可能再有四条或葫芦）
+    for mask in suit_masks {
+        if mask.count_ones() >= 5 {
+            return match straight_high(mask) {
+                Some(Rank::Ace) => encode(HandRank::RoyalFlush, &[Rank::Ace]),
+                Some(high) => encode(HandRank::StraightFlush, &[high]),
+                None => {
+                    let mut kickers = [Rank::Two; 5];
+                    for (slot, rank) in kickers.iter_mut().zip(ranks_desc(mask)) {
+                        *slot = rank;
+                    }
+                    encode(HandRank::Flush, &kickers)
+                }
+            };
+        }
+    }
+
+    // 按出现次数把点数分组，均为降序
+    let all_mask = suit_masks.iter().fold(0, |acc, m| acc | m);
+    let mut groups = [[Rank::Two; 7]; 5];
+    let mut lens = [0usize; 5];
+    for rank in ranks_desc(all_mask) {
+        let bit = 1 << (rank.value() - 2);
+        let count = suit_masks.iter().filter(|&&m| m & bit != 0).count();
+        groups[count][lens[count]] = rank;
+        lens[count] += 1;
+    }
+    let (quads, trips, pairs) = (
+        &groups[4][..lens[4]],
+        &groups[3][..lens[3]],
+        &groups[2][..lens[2]],
+    );
+
+    // 除去指定点数外最大的若干张单牌
+    let kickers = |exclude: &[Rank], out: &mut [Rank]| {
+        let mut ranks = ranks_desc(all_mask).filter(|r| !exclude.contains(r));
+        for slot in out.iter_mut() {
+            *slot = ranks.next().unwrap_or(Rank::Two);
+        }
+    };
+
+    if let Some(&quad) = quads.first() {
+        let mut kicker = [Rank::Two];
+        kickers(&[quad], &mut kicker);
+        return encode(HandRank::FourOfAKind, &[quad, kicker[0]]);
+    }
+
+    if let Some(&trip) = trips.first() {
+        // 第二组三条也可以当作对子
+        let pair = match (trips.get(1), pairs.first()) {
+            (Some(&a), Some(&b)) => Some(a.max(b)),
+            (a, b) => a.or(b).copied(),
+        };
+        if let Some(pair) = pair {
+            return encode(HandRank::FullHouse, &[trip, pair]);
+        }
+    }
+
+    if let Some(high) = straight_high(all_mask) {
+        return encode(HandRank::Straight, &[high]);
+    }
+
+    if let Some(&trip) = trips.first() {
+        let mut ks = [Rank::Two; 2];
+        kickers(&[trip], &mut ks);
+        return encode(HandRank::ThreeOfAKind, &[trip, ks[0], ks[1]]);
+    }
+
+    if pairs.len() >= 2 {
+        let mut ks = [Rank::Two];
+        kickers(&pairs[..2], &mut ks);
+        return encode(HandRank::TwoPair, &[pairs[0], pairs[1], ks[0]]);
+    }
+
+    if let Some(&pair) = pairs.first() {
+        let mut ks = [Rank::Two; 3];
+        kickers(&[pair], &mut ks);
+        return encode(HandRank::OnePair, &[pair, ks[0], ks[1], ks[2]]);
+    }
+
+    let mut ks = [Rank::Two; 5];
+    kickers(&[], &mut ks);
+    encode(HandRank::HighCard, &ks)
+}
+
+/// 掩码中最大顺子的最大牌，A 可作为 1 组成 A-2-3-4-5
+pub(crate) fn straight_high(mask: u16) -> Option<Rank> {
+    // 左移一位后第 0 位放 A，这样 A-2-3-4-5 也是连续的 5 位
+    let extended = (mask << 1) | ((mask >> 12) & 1);
+    (4..=13u8)
+        .rev()
+        .find(|high| {
+            let window = 0b11111u16 << (high - 4);
+            extended & window == window
+        })
+        .map(|high| Rank::from_value(high + 1))
+}
+
+/// 按点数从大到小遍历掩码中的点数
+fn ranks_desc(mask: u16) -> impl Iterator<Item = Rank> {
+    (0..13u8)
+        .rev()
+        .filter(move |i| mask & (1 << i) != 0)
+        .map(|i| Rank::from_value(i + 2))
+}
+
+/// 把牌型和关键牌编码为牌力值
+fn encode(rank: HandRank, kickers: &[Rank]) -> u32 {
+    let mut strength = (rank as u32) << CATEGORY_SHIFT;
+    for (i, kicker) in kickers.iter().enumerate() {
+        strength |= (kicker.value() as u32) << (16 - 4 * i);
+    }
+    strength
+}
+
+/// 牌力值对应的牌型
+pub fn strength_rank(strength: u32) -> HandRank {
+    match strength >> CATEGORY_SHIFT {
+        0 => HandRank::HighCard,
+        1 => HandRank::OnePair,
+        2 => HandRank::TwoPair,
+        3 => HandRank::ThreeOfAKind,
+        4 => HandRank::Straight,
+        5 => HandRank::Flush,
+        6 => HandRank::FullHouse,
+        7 => HandRank::FourOfAKind,
+        8 => HandRank::StraightFlush,
+        9 => HandRank::RoyalFlush,
+        _ => panic!("Invalid hand strength: {}", strength),
+    }
+}
+
+/// 牌力值中的关键牌序列，与 `HandEvaluation::kickers` 相同
+pub fn strength_kickers(strength: u32) -> Vec<Rank> {
+    (0..5)
+        .map(|i| ((strength >> (16 - 4 * i)) & 0xf) as u8)
+        .take_while(|&value| value != 0)
+        .map(Rank::from_value)
+        .collect()
+}
+
+impl HandEvaluation {
+    /// 与 `evaluate_strength` 相同编码的牌力值
+    pub fn strength(&self) -> u32 {
+        encode(self.rank, &self.kickers)
+    }
+
+    /// 牌型的绝对排名，见 `strength_index`
+    pub fn strength_index(&self) -> u16 {
+        strength_index(self.strength())
+    }
+}
+
+/// 牌力值在全部 7462 个等价类中的绝对排名
+///
+/// 1 为皇家同花顺，7462 为 7-5-4-3-2 高牌，数值越小牌越强。
+pub fn strength_index(strength: u32) -> u16 {
+    let table = class_table();
+    match table.binary_search_by(|probe| strength.cmp(probe)) {
+        Ok(i) => i as u16 + 1,
+        Err(_) => panic!("Invalid hand strength: {}", strength),
+    }
+}
+
+/// 绝对排名对应的牌力值，排名超出 1..=7462 时返回 None
+pub fn strength_from_index(index: u16) -> Option<u32> {
+    let i = index.checked_sub(1)?;
+    class_table().get(i as usize).copied()
+}
+
+/// 所有等价类的牌力值，从强到弱排列
+fn class_table() -> &'static [u32] {
+    static TABLE: OnceLock<Vec<u32>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = Vec::with_capacity(HAND_CLASS_COUNT as usize);
+
+        // 枚举所有点数组合（同一点数最多 4 张）
+        for ranks in (0..13u32).combinations_with_replacement(5) {
+            if ranks.iter().dedup_with_count().any(|(count, _)| count > 4) {
+                continue;
+            }
+
+            // 同点数的牌依次放在不同花色，五张牌不会同花
+            let bits = ranks.iter().enumerate().fold(0u64, |acc, (i, rank)| {
+                acc | 1 << ((i % 4) as u32 * 16 + rank)
+            });
+            table.push(evaluate_set(CardSet::from_bits(bits)));
+
+            // 五个不同点数时再加上同花的情况
+            if ranks.iter().all_unique() {
+                let flush = ranks.iter().fold(0u64, |acc, rank| acc | 1 << rank);
+                table.push(evaluate_set(CardSet::from_bits(flush)));
+            }
+        }
+
+        table.sort_unstable_by(|a, b| b.cmp(a));
+        table.dedup();
+        table
+    })
+}
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::evaluator::evaluate_set;
+use super::shared::*;
+
+/// 机器人常用的牌力指标，均为 0 到 1 之间的比例
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HandStrengthMetrics {
+    pub hs: f64,         // 当前牌力：对一个随机手牌领先的比例（平局算一半）
+    pub hs_n: f64,       // 对 N 个随机手牌的当前牌力，即 hs 的 N 次方
+    pub ppot: f64,       // 正向潜力：当前落后、发完牌后领先的比例
+    pub npot: f64,       // 负向潜力：当前领先、发完牌后落后的比例
+    pub ehs: f64,        // 有效牌力：hs_n × (1 - npot) + (1 - hs_n) × ppot
+    pub hs_squared: f64, // 发完公共牌后牌力平方的期望（E[HS²]）
+}
+
+/// 当前牌力：与所有可能的对手底牌比较，领先加平局的一半所占比例
+pub fn hand_strength(hole_cards: (Card, Card), board: &[Card]) -> Result<f64, GameError> {
+    let (hand, board_set) = validate(hole_cards, board)?;
+    Ok(strength_against_all(hand, board_set))
+}
+
+/// 正向潜力和负向潜力 (PPot, NPot)
+///
+/// 枚举所有对手底牌和剩余公共牌，翻牌圈向后看两张牌，转牌圈一张，河牌圈均为 0。
+pub fn hand_potential(hole_cards: (Card, Card), board: &[Card]) -> Result<(f64, f64), GameError> {
+    let (hand, board_set) = validate(hole_cards, board)?;
+    Ok(potential(hand, board_set))
+}
+
+/// 计算全部牌力指标，`opponents` 为对手人数，为 0 时返回 `InvalidCards`
+pub fn hand_strength_metrics(
+    hole_cards: (Card, Card),
+    board: &[Card],
+    opponents: usize,
+) -> Result<HandStrengthMetrics, GameError> {
+    let (hand, board_set) = validate(hole_cards, board)?;
+    if opponents == 0 {
+        return Err(GameError::InvalidCards);
+    }
+
+    let hs = strength_against_all(hand, board_set);
+    let hs_n = hs.powi(opponents as i32);
+    let (ppot, npot) = potential(hand, board_set);
+
+    // 对每种可能的后续公共牌计算河牌圈的牌力
+    let unseen = CardSet::full().difference(hand.union(board_set)).to_vec();
+    let runouts = unseen.iter().copied().combinations(5 - board.len());
+    let (sum, count) = runouts.fold((0.0, 0u32), |(sum, count), runout| {
+        let river = board_set.union(CardSet::from_cards(&runout));
+        (sum + strength_against_all(hand, river).powi(2), count + 1)
+    });
+
+    Ok(HandStrengthMetrics {
+        hs,
+        hs_n,
+        ppot,
+        npot,
+        ehs: hs_n * (1.0 - npot) + (1.0 - hs_n) * ppot,
+        hs_squared: sum / count as f64,
+    })
+}
+
+/// 检查底牌和 3 到 5 张公共牌没有重复
+fn validate(hole_cards: (Card, Card), board: &[Card]) -> Result<(CardSet, CardSet), GameError> {
+    let hand = CardSet::from_cards(&[hole_cards.0, hole_cards.1]);
+    let board_set = CardSet::from_cards(board);
+    if !(3..=5).contains(&board.len())
+        || hand.len() != 2
+        || board_set.len() != board.len()
+        || !hand.is_disjoint(board_set)
+    {
+        return Err(GameError::InvalidCards);
+    }
+    Ok((hand, board_set))
+}
+
+/// 领先、平局、落后在数组中的下标
+fn outcome(ours: u32, theirs: u32) -> usize {
+    match ours.cmp(&theirs) {
+        Ordering::Greater => 0,
+        Ordering::Equal => 1,
+        Ordering::Less => 2,
+    }
+}
+
+fn strength_against_all(hand: CardSet, board: CardSet) -> f64 {
+    let ours = evaluate_set(hand.union(board));
+    let mut counts = [0u32; 3];
+    let unseen = CardSet::full().difference(hand.union(board));
+    for (c1, c2) in unseen.iter().tuple_combinations() {
+        let theirs = evaluate_set(board.union(CardSet::from_cards(&[c1, c2])));
+        counts[outcome(ours, theirs)] += 1;
+    }
+    let total: u32 = counts.iter().sum();
+    (counts[0] as f64 + counts[1] as f64 / 2.0) / total as f64
+}
+
+fn potential(hand: CardSet, board: CardSet) -> (f64, f64) {
+    let missing = 5 - board.len();
+    if missing == 0 {
+        return (0.0, 0.0);
+    }
+
+    // hp[当前][最终] 为对应结果的次数，totals[当前] 为当前结果的总次数
+    let mut hp = [[0u64; 3]; 3];
+    let mut totals = [0u64; 3];
+    let unseen = CardSet::full().difference(hand.union(board));
+    let ours = evaluate_set(hand.union(board));
+
+    for (c1, c2) in unseen.iter().tuple_combinations() {
+        let opponent = CardSet::from_cards(&[c1, c2]);
+        let now = outcome(ours, evaluate_set(board.union(opponent)));
+        let deck = unseen.difference(opponent).to_vec();
+        for runout in deck.iter().copied().combinations(missing) {
+            let final_board = board.union(CardSet::from_cards(&runout));
+            let ours = evaluate_set(hand.union(final_board));
+            let theirs = evaluate_set(opponent.union(final_board));
+            hp[now][outcome(ours, theirs)] += 1;
+            totals[now] += 1;
+        }
+    }
+
+    let ratio = |numerator: f64, denominator: f64| {
+        if denominator > 0.0 {
+            numerator / denominator
+        } else {
+            0.0
+        }
+    };
+    let (ahead, tied, behind) = (0, 1, 2);
+    let ppot = ratio(
+        hp[behind][ahead] as f64 + hp[behind][tied] as f64 / 2.0 + hp[tied][ahead] as f64 / 2.0,
+        totals[behind] as f64 + totals[tied] as f64 / 2.0,
+    );
+    let npot = ratio(
+        hp[ahead][behind] as f64 + hp[tied][behind] as f64 / 2.0 + hp[ahead][tied] as f64 / 2.0,
+        totals[ahead] as f64 + totals[tied] as f64 / 2.0,
+    );
+    (ppot, npot)
+}
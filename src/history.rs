@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use super::shared::*;
+
+/// 开局时的座位信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatInfo {
+    pub id: String,
+    pub name: String,
+    pub chips: u32, // 下前注和盲注之前的筹码
+}
+
+/// 盲注类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Blind {
+    Small,
+    Big,
+}
+
+/// 一手牌中发生的事件，按发生顺序记录在 `TexasHoldem::history` 中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HandEvent {
+    /// 新一手牌开始
+    HandStarted {
+        dealer_position: usize,
+        players: Vec<SeatInfo>,
+        small_blind: u32,
+        big_blind: u32,
+        ante: u32,
+        variant: GameVariant,
+        #[serde(default)]
+        betting: BettingStructure,
+        bomb_pot: bool,
+        boards: usize,
+    },
+    AntePosted {
+        player_id: String,
+        amount: u32,
+    },
+    BlindPosted {
+        player_id: String,
+        blind: Blind,
+        amount: u32,
+    },
+    /// 发给玩家的底牌（大菠萝玩法包括第三张）
+    HoleCardsDealt {
+        player_id: String,
+        cards: Vec<Card>,
+    },
+    /// 玩家行动，amount 为这次投入的筹码
+    ActionTaken {
+        player_id: String,
+        action: PlayerAction,
+        amount: u32,
+    },
+    /// 进入新的阶段
+    StreetStarted {
+        stage: GameStage,
+    },
+    /// 发到某块牌面（0 为主牌面）的公共牌
+    BoardDealt {
+        board: usize,
+        cards: Vec<Card>,
+    },
+    /// 摊牌时亮出的底牌及每块牌面上的牌型
+    HandShown {
+        player_id: String,
+        cards: (Card, Card),
+        hands: Vec<HandEvaluation>,
+    },
+    /// 摊牌时没有人跟注的筹码退还给下注的玩家，不算赢得底池
+    BetReturned {
+        player_id: String,
+        amount: u32,
+    },
+    /// 玩家从某个底池（0 为主池，之后依次为边池）赢得的筹码
+    PotAwarded {
+        player_id: String,
+        amount: u32,
+        #[serde(default)]
+        pot: usize,
+    },
+}
+
+/// 按 HandStarted 把事件记录切分为每手牌，开头不属于任何一手牌的事件被忽略
+pub fn split_hands(events: &[HandEvent]) -> Vec<&[HandEvent]> {
+    let starts: Vec<usize> = events
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| matches!(e, HandEvent::HandStarted { .. }))
+        .map(|(i, _)| i)
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(events.len());
+            &events[start..end]
+        })
+        .collect()
+}
@@ -1,6 +1,23 @@
+pub mod card_set;
+pub mod equity;
+pub mod evaluator;
+pub mod hand_strength;
+pub mod history;
+pub mod nuts;
+pub mod ohh;
+pub mod outs;
+pub mod pokerstars;
+pub mod preflop;
+pub mod range;
+pub mod replay;
 pub mod rules;
+pub mod session;
 pub mod shared;
 pub mod state;
+pub mod stats;
+pub mod texture;
+pub mod view;
 
+pub use card_set::CardSet;
 pub use shared::*;
 pub use state::TexasHoldem;
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::evaluator::{evaluate_set, strength_rank};
+use super::shared::*;
+
+/// 牌力相同的一档底牌
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NutTier {
+    pub strength: u32,             // 与 `evaluate_strength` 相同编码
+    pub rank: HandRank,            // 这一档的牌型
+    pub combos: Vec<(Card, Card)>, // 组成这一档的底牌
+}
+
+/// 一手底牌在所有可能底牌中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HandRanking {
+    pub tier: usize,          // 第几档，1 为绝对坚果
+    pub tiers: usize,         // 总档数
+    pub better_combos: usize, // 比它大的底牌组合数
+    pub tied_combos: usize,   // 与它一样大的其他底牌组合数
+    pub total_combos: usize,  // 所有可能的底牌组合数
+}
+
+impl HandRanking {
+    pub fn is_nuts(&self) -> bool {
+        self.tier == 1
+    }
+
+    /// 比它小的底牌组合所占百分比
+    pub fn percentile(&self) -> f64 {
+        let worse = self.total_combos - self.better_combos - self.tied_combos - 1;
+        worse as f64 * 100.0 / (self.total_combos - 1) as f64
+    }
+}
+
+/// 列出公共牌下所有可能底牌的排名，第一档为坚果
+pub fn nut_ranking(board: &[Card]) -> Result<Vec<NutTier>, GameError> {
+    let board_set = board_set(board)?;
+
+    let mut tiers: BTreeMap<u32, Vec<(Card, Card)>> = BTreeMap::new();
+    for (c1, c2) in CardSet::full()
+        .difference(board_set)
+        .iter()
+        .tuple_combinations()
+    {
+        let strength = evaluate_set(board_set.union(CardSet::from_cards(&[c1, c2])));
+        // 点数大的牌在前
+        let combo = if c1.rank >= c2.rank {
+            (c1, c2)
+        } else {
+            (c2, c1)
+        };
+        tiers.entry(strength).or_default().push(combo);
+    }
+
+    Ok(tiers
+        .into_iter()
+        .rev()
+        .map(|(strength, combos)| NutTier {
+            strength,
+            rank: strength_rank(strength),
+            combos,
+        })
+        .collect())
+}
+
+/// 一手底牌在公共牌下的坚果排名
+pub fn rank_hand(hole_cards: (Card, Card), board: &[Card]) -> Result<HandRanking, GameError> {
+    let board_set = board_set(board)?;
+    let hand = CardSet::from_cards(&[hole_cards.0, hole_cards.1]);
+    if hand.len() != 2 || !hand.is_disjoint(board_set) {
+        return Err(GameError::InvalidCards);
+    }
+
+    let tiers = nut_ranking(board)?;
+    let strength = evaluate_set(board_set.union(hand));
+    let index = tiers
+        .iter()
+        .position(|t| t.strength == strength)
+        .expect("hand must be among all holdings");
+
+    Ok(HandRanking {
+        tier: index + 1,
+        tiers: tiers.len(),
+        better_combos: tiers[..index].iter().map(|t| t.combos.len()).sum(),
+        tied_combos: tiers[index].combos.len() - 1,
+        total_combos: tiers.iter().map(|t| t.combos.len()).sum(),
+    })
+}
+
+/// 检查公共牌为 3 到 5 张且不重复
+fn board_set(board: &[Card]) -> Result<CardSet, GameError> {
+    let board_set = CardSet::from_cards(board);
+    if !(3..=5).contains(&board.len()) || board_set.len() != board.len() {
+        return Err(GameError::InvalidCards);
+    }
+    Ok(board_set)
+}
@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::history::*;
+use super::replay::*;
+use super::shared::*;
+
+/// 导出时使用的 Open Hand History 版本
+pub const OHH_SPEC_VERSION: &str = "1.4.6";
+
+/// 标记炸弹底池的 flag，标准中没有对应项
+pub const OHH_BOMB_POT_FLAG: &str = "Bomb_Pot";
+
+/// 一个 OHH 文件：`{"ohh": {...}}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhFile {
+    pub ohh: OpenHandHistory,
+}
+
+/// Open Hand History 格式的一手牌，字段名与标准一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenHandHistory {
+    pub spec_version: String,
+    pub site_name: String,
+    pub network_name: String,
+    pub internal_version: String,
+    pub tournament: bool,
+    pub game_number: String,
+    pub start_date_utc: String,
+    pub table_name: String,
+    pub game_type: String,
+    pub bet_limit: OhhBetLimit,
+    pub table_size: usize,
+    pub currency: String,
+    pub dealer_seat: usize,
+    pub small_blind_amount: f64,
+    pub big_blind_amount: f64,
+    pub ante_amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_player_id: Option<usize>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    pub players: Vec<OhhPlayer>,
+    pub rounds: Vec<OhhRound>,
+    pub pots: Vec<OhhPot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhBetLimit {
+    pub bet_type: String, // "NL"、"PL"、"FL"
+    #[serde(default)]
+    pub bet_cap: f64,
+}
+
+/// 玩家；name 保存引擎中的玩家 id，display 保存显示名称
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhPlayer {
+    pub id: usize,
+    pub seat: usize,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    pub starting_stack: f64,
+}
+
+/// 一个阶段：street 为 "Preflop"、"Flop"、"Turn"、"River" 或 "Showdown"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhRound {
+    pub id: usize,
+    pub street: String,
+    #[serde(default, with = "card_codes", skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<Card>,
+    pub actions: Vec<OhhAction>,
+}
+
+/// 一个动作；Raise 的 amount 为加注到的总额，其余为这次投入的筹码
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhAction {
+    pub action_number: usize,
+    pub player_id: usize,
+    pub action: String,
+    #[serde(default)]
+    pub amount: f64,
+    #[serde(default)]
+    pub is_allin: bool,
+    #[serde(default, with = "card_codes", skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhPot {
+    pub number: usize,
+    pub amount: f64,
+    #[serde(default)]
+    pub rake: f64,
+    pub player_wins: Vec<OhhPlayerWin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhPlayerWin {
+    pub player_id: usize,
+    pub win_amount: f64,
+}
+
+/// 导出 OHH 时使用的牌局信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct OhhOptions {
+    pub site_name: String,
+    pub game_number: String,
+    pub start_date_utc: String, // ISO 8601，如 "2024-01-31T20:15:00Z"
+    pub table_name: String,
+    pub table_size: usize,
+    pub currency: String,
+    pub hero: Option<String>, // 只导出该玩家的底牌，None 时导出所有人的底牌
+}
+
+impl Default for OhhOptions {
+    fn default() -> Self {
+        OhhOptions {
+            site_name: "texas-engine".to_string(),
+            game_number: "1".to_string(),
+            start_date_utc: "1970-01-01T00:00:00Z".to_string(),
+            table_name: "Table 1".to_string(),
+            table_size: 9,
+            currency: String::new(),
+            hero: None,
+        }
+    }
+}
+
+/// 牌以 "Ah" 形式的字符串序列化
+mod card_codes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cards: &[Card], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cards.iter().map(|c| c.code()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Card>, D::Error> {
+        let codes = Vec::<String>::deserialize(deserializer)?;
+        codes
+            .iter()
+            .map(|code| code.parse().map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+impl OpenHandHistory {
+    /// 由一手牌的事件记录生成，只支持单块牌面；每个引擎底池对应一个 pot，炸弹底池记在 flags 中
+    pub fn from_history(events: &[HandEvent], options: &OhhOptions) -> Result<Self, GameError> {
+        let Some(HandEvent::HandStarted {
+            dealer_position,
+            players,
+            small_blind,
+            big_blind,
+            ante,
+            variant,
+            betting,
+            bomb_pot,
+            boards,
+        }) = events.first()
+        else {
+            return Err(GameError::InvalidAction);
+        };
+        if *boards != 1 {
+            return Err(GameError::InvalidAction);
+        }
+
+        let index_of: HashMap<&str, usize> = players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id.as_str(), i))
+            .collect();
+        let player_id = |id: &str| index_of.get(id).copied().ok_or(GameError::PlayerNotFound);
+        let mut stacks: Vec<u32> = players.iter().map(|p| p.chips).collect();
+        let mut street_bets = vec![0u32; players.len()];
+
+        let mut rounds = vec![OhhRound {
+            id: 0,
+            street: "Preflop".to_string(),
+            cards: Vec::new(),
+            actions: Vec::new(),
+        }];
+        let mut dealt_board = Vec::new();
+        let mut pots: Vec<OhhPot> = Vec::new();
+        let mut action_number = 0;
+        let mut push = |rounds: &mut Vec<OhhRound>, action: OhhAction| {
+            action_number += 1;
+            rounds.last_mut().unwrap().actions.push(OhhAction {
+                action_number,
+                ..action
+            });
+        };
+        let action = |player_id: usize, action: &str, amount: u32, is_allin: bool| OhhAction {
+            action_number: 0,
+            player_id,
+            action: action.to_string(),
+            amount: amount as f64,
+            is_allin,
+            cards: Vec::new(),
+        };
+
+        // 与 PokerStars 一样，先列出前注和盲注再发底牌
+        for event in events {
+            match event {
+                HandEvent::AntePosted {
+                    player_id: id,
+                    amount,
+                } if *amount > 0 => {
+                    let i = player_id(id)?;
+                    stacks[i] = stacks[i]
+                        .checked_sub(*amount)
+                        .ok_or(GameError::InvalidAction)?;
+                    push(&mut rounds, action(i, "Post Ante", *amount, stacks[i] == 0));
+                }
+                HandEvent::BlindPosted {
+                    player_id: id,
+                    blind,
+                    amount,
+                } => {
+                    let i = player_id(id)?;
+                    stacks[i] = stacks[i]
+                        .checked_sub(*amount)
+                        .ok_or(GameError::InvalidAction)?;
+                    street_bets[i] += amount;
+                    let name = match blind {
+                        Blind::Small => "Post SB",
+                        Blind::Big => "Post BB",
+                    };
+                    push(&mut rounds, action(i, name, *amount, stacks[i] == 0));
+                }
+                _ => {}
+            }
+        }
+        for event in events {
+            if let HandEvent::HoleCardsDealt {
+                player_id: id,
+                cards,
+            } = event
+            {
+                if options.hero.as_ref().is_some_and(|hero| hero != id) {
+                    continue;
+                }
+                let dealt = OhhAction {
+                    cards: cards.clone(),
+                    ..action(player_id(id)?, "Dealt Cards", 0, false)
+                };
+                push(&mut rounds, dealt);
+            }
+        }
+
+        for event in events {
+            match event {
+                HandEvent::ActionTaken {
+                    player_id: id,
+                    action: player_action,
+                    amount,
+                } => {
+                    let i = player_id(id)?;
+                    stacks[i] = stacks[i]
+                        .checked_sub(*amount)
+                        .ok_or(GameError::InvalidAction)?;
+                    street_bets[i] += amount;
+                    let all_in = *amount > 0 && stacks[i] == 0;
+                    let recorded = match player_action {
+                        PlayerAction::Fold => action(i, "Fold", 0, false),
+                        PlayerAction::Check => action(i, "Check", 0, false),
+                        PlayerAction::Call => action(i, "Call", *amount, all_in),
+                        PlayerAction::Bet(_) => action(i, "Bet", *amount, all_in),
+                        PlayerAction::Raise(_) => action(i, "Raise", street_bets[i], all_in),
+                        PlayerAction::Discard(card) => OhhAction {
+                            cards: vec![*card],
+                            ..action(i, "Discard", 0, false)
+                        },
+                    };
+                    push(&mut rounds, recorded);
+                }
+                HandEvent::BoardDealt { board: 0, cards } => dealt_board = cards.clone(),
+                HandEvent::StreetStarted { stage } => {
+                    street_bets.iter_mut().for_each(|bet| *bet = 0);
+                    let street = match stage {
+                        GameStage::PreFlop => continue,
+                        GameStage::Flop => "Flop",
+                        GameStage::Turn => "Turn",
+                        GameStage::River => "River",
+                        GameStage::Showdown => "Showdown",
+                    };
+                    rounds.push(OhhRound {
+                        id: rounds.len(),
+                        street: street.to_string(),
+                        cards: std::mem::take(&mut dealt_board),
+                        actions: Vec::new(),
+                    });
+                }
+                HandEvent::HandShown {
+                    player_id: id,
+                    cards,
+                    ..
+                } => {
+                    let shown = OhhAction {
+                        cards: vec![cards.0, cards.1],
+                        ..action(player_id(id)?, "Shows Cards", 0, false)
+                    };
+                    push(&mut rounds, shown);
+                }
+                HandEvent::PotAwarded {
+                    player_id: id,
+                    amount,
+                    pot,
+                } => {
+                    let index = match pots.iter().position(|p| p.number == *pot) {
+                        Some(index) => index,
+                        None => {
+                            pots.push(OhhPot {
+                                number: *pot,
+                                amount: 0.0,
+                                rake: 0.0,
+                                player_wins: Vec::new(),
+                            });
+                            pots.len() - 1
+                        }
+                    };
+                    pots[index].amount += *amount as f64;
+                    pots[index].player_wins.push(OhhPlayerWin {
+                        player_id: player_id(id)?,
+                        win_amount: *amount as f64,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let game_type = match variant {
+            GameVariant::Holdem => "Holdem",
+            GameVariant::Pineapple => "Pineapple",
+            GameVariant::CrazyPineapple => "CrazyPineapple",
+        };
+        Ok(OpenHandHistory {
+            spec_version: OHH_SPEC_VERSION.to_string(),
+            site_name: options.site_name.clone(),
+            network_name: options.site_name.clone(),
+            internal_version: env!("CARGO_PKG_VERSION").to_string(),
+            tournament: false,
+            game_number: options.game_number.clone(),
+            start_date_utc: options.start_date_utc.clone(),
+            table_name: options.table_name.clone(),
+            game_type: game_type.to_string(),
+            bet_limit: OhhBetLimit {
+                bet_type: match betting {
+                    BettingStructure::NoLimit => "NL",
+                    BettingStructure::PotLimit => "PL",
+                    BettingStructure::FixedLimit => "FL",
+                }
+                .to_string(),
+                bet_cap: 0.0,
+            },
+            table_size: options.table_size,
+            currency: options.currency.clone(),
+            dealer_seat: dealer_position + 1,
+            small_blind_amount: *small_blind as f64,
+            big_blind_amount: *big_blind as f64,
+            ante_amount: *ante as f64,
+            hero_player_id: options
+                .hero
+                .as_ref()
+                .map(|hero| player_id(hero))
+                .transpose()?,
+            flags: if *bomb_pot {
+                vec![OHH_BOMB_POT_FLAG.to_string()]
+            } else {
+                Vec::new()
+            },
+            players: players
+                .iter()
+                .enumerate()
+                .map(|(i, p)| OhhPlayer {
+                    id: i,
+                    seat: i + 1,
+                    name: p.id.clone(),
+                    display: Some(p.name.clone()),
+                    starting_stack: p.chips as f64,
+                })
+                .collect(),
+            rounds,
+            pots,
+        })
+    }
+
+    /// 序列化为 `{"ohh": {...}}`
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&OhhFile { ohh: self.clone() })
+    }
+
+    /// 从 `{"ohh": {...}}` 解析
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str::<OhhFile>(json).map(|file| file.ohh)
+    }
+
+    /// 对应的引擎设置，只支持德州扑克；未知的底牌用其余的牌补齐
+    pub fn setup(&self) -> Result<HandSetup, GameError> {
+        let betting = match self.bet_limit.bet_type.as_str() {
+            "NL" => BettingStructure::NoLimit,
+            "PL" => BettingStructure::PotLimit,
+            "FL" => BettingStructure::FixedLimit,
+            _ => return Err(GameError::InvalidAction),
+        };
+        if self.game_type != "Holdem" {
+            return Err(GameError::InvalidAction);
+        }
+
+        let mut players = self.players.clone();
+        players.sort_by_key(|p| p.seat);
+        let dealer_position = players
+            .iter()
+            .position(|p| p.seat == self.dealer_seat)
+            .ok_or(GameError::PlayerNotFound)?;
+
+        let hole_cards = players
+            .iter()
+            .map(|p| {
+                let dealt = self
+                    .actions()
+                    .find(|a| a.player_id == p.id && a.action == "Dealt Cards")
+                    .or_else(|| {
+                        self.actions()
+                            .find(|a| a.player_id == p.id && a.action == "Shows Cards")
+                    });
+                match dealt.map(|a| a.cards.as_slice()) {
+                    None => Ok(None),
+                    Some([card1, card2]) => Ok(Some((*card1, *card2))),
+                    Some(_) => Err(GameError::InvalidCards),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let board: Vec<Card> = self
+            .rounds
+            .iter()
+            .flat_map(|r| r.cards.iter().copied())
+            .collect();
+
+        Ok(HandSetup {
+            players: players
+                .iter()
+                .map(|p| {
+                    Ok(SeatInfo {
+                        id: p.name.clone(),
+                        name: p.display.clone().unwrap_or_else(|| p.name.clone()),
+                        chips: chips(p.starting_stack)?,
+                    })
+                })
+                .collect::<Result<_, GameError>>()?,
+            config: GameConfig {
+                betting,
+                small_blind: chips(self.small_blind_amount)?,
+                big_blind: chips(self.big_blind_amount)?,
+                ante: chips(self.ante_amount)?,
+                bomb_pot: self.flags.iter().any(|f| f == OHH_BOMB_POT_FLAG),
+                ..Default::default()
+            },
+            dealer_position,
+            deck: DeckSetup::from_known(&hole_cards, &board)?,
+        })
+    }
+
+    /// 玩家的下注动作，加注换算为引擎的加注额（超出当前最高下注的部分）
+    pub fn recorded_actions(&self) -> Result<Vec<RecordedAction>, GameError> {
+        let name = |id: usize| {
+            self.players
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name.clone())
+                .ok_or(GameError::PlayerNotFound)
+        };
+
+        let mut recorded = Vec::new();
+        for round in &self.rounds {
+            let mut street_bets: HashMap<usize, u32> = HashMap::new();
+            for action in &round.actions {
+                let amount = chips(action.amount)?;
+                let current_bet = street_bets.values().copied().max().unwrap_or(0);
+                let street_bet = street_bets.entry(action.player_id).or_insert(0);
+                let player_action = match action.action.as_str() {
+                    "Post SB" | "Post BB" => {
+                        *street_bet += amount;
+                        continue;
+                    }
+                    "Fold" => PlayerAction::Fold,
+                    "Check" => PlayerAction::Check,
+                    "Call" => {
+                        *street_bet += amount;
+                        PlayerAction::Call
+                    }
+                    "Bet" => {
+                        *street_bet += amount;
+                        PlayerAction::Bet(amount)
+                    }
+                    "Raise" => {
+                        if amount <= current_bet {
+                            return Err(GameError::InvalidAction);
+                        }
+                        *street_bet = amount;
+                        PlayerAction::Raise(amount - current_bet)
+                    }
+                    "Discard" => match action.cards.as_slice() {
+                        [card] => PlayerAction::Discard(*card),
+                        _ => return Err(GameError::InvalidCards),
+                    },
+                    _ => continue,
+                };
+                recorded.push(RecordedAction {
+                    player_id: name(action.player_id)?,
+                    action: player_action,
+                });
+            }
+        }
+        Ok(recorded)
+    }
+
+    /// 用引擎重放这手牌
+    pub fn replay(&self) -> Result<Replay, GameError> {
+        Replay::new(self.setup()?, self.recorded_actions()?)
+    }
+
+    fn actions(&self) -> impl Iterator<Item = &OhhAction> {
+        self.rounds.iter().flat_map(|r| &r.actions)
+    }
+}
+
+/// OHH 金额转为筹码，必须是非负整数
+fn chips(amount: f64) -> Result<u32, GameError> {
+    if amount < 0.0 || amount.fract() != 0.0 || amount > u32::MAX as f64 {
+        return Err(GameError::InvalidAction);
+    }
+    Ok(amount as u32)
+}
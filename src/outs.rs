@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::evaluator::{evaluate_set, straight_high, strength_rank};
+use super::shared::*;
+
+/// 听牌类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DrawType {
+    FlushDraw,        // 同花听牌，差一张
+    OpenEnded,        // 两头顺子听牌（包括双卡顺）
+    Gutshot,          // 卡顺听牌
+    BackdoorFlush,    // 后门同花，翻牌圈差两张
+    BackdoorStraight, // 后门顺子，翻牌圈差两张
+}
+
+/// 一张补牌及补中后的牌型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Out {
+    pub card: Card,
+    pub hand_rank: HandRank,
+}
+
+/// 补牌分析结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutsAnalysis {
+    pub current: HandRank,         // 当前牌型
+    pub outs: Vec<Out>,            // 按花色、点数排列
+    pub draws: Vec<DrawType>,      // 当前的听牌
+    pub unseen: usize,             // 未见过的牌数
+    pub rule_of_two: f64,          // 补牌数 × 2（下一张牌的估算百分比）
+    pub rule_of_four: Option<f64>, // 补牌数 × 4（翻牌圈到河牌的估算百分比），转牌圈为 None
+}
+
+impl OutsAnalysis {
+    /// 补牌数量
+    pub fn count(&self) -> usize {
+        self.outs.len()
+    }
+
+    /// 下一张牌补中的精确概率（百分比）
+    pub fn next_card_odds(&self) -> f64 {
+        if self.unseen == 0 {
+            return 0.0;
+        }
+        self.count() as f64 * 100.0 / self.unseen as f64
+    }
+}
+
+/// 分析翻牌或转牌圈的补牌和听牌
+///
+/// 没有给出对手手牌，或已经领先所有对手时，补牌是能让牌型升级、且升级用到底牌
+/// （新牌型高于公共牌本身）或拉大与对手牌型差距的牌，只升级公共牌的牌不算；
+/// 落后时，补牌是能让自己超过所有对手的牌。
+pub fn analyze_outs(
+    hole_cards: (Card, Card),
+    board: &[Card],
+    opponents: &[(Card, Card)],
+) -> Result<OutsAnalysis, GameError> {
+    if board.len() != 3 && board.len() != 4 {
+        return Err(GameError::InvalidCards);
+    }
+
+    let hand = CardSet::from_cards(&[hole_cards.0, hole_cards.1]);
+    let board_set = CardSet::from_cards(board);
+    let opponent_sets: Vec<CardSet> = opponents
+        .iter()
+        .map(|&(c1, c2)| CardSet::from_cards(&[c1, c2]))
+        .collect();
+    let known: CardSet = opponent_sets
+        .iter()
+        .fold(hand.union(board_set), |acc, s| acc.union(*s));
+    if known.len() != 2 + board.len() + 2 * opponents.len() {
+        return Err(GameError::InvalidCards);
+    }
+
+    let strength = evaluate_set(hand.union(board_set));
+    let current = strength_rank(strength);
+    let best_opponent = |board: CardSet| {
+        opponent_sets
+            .iter()
+            .map(|s| evaluate_set(s.union(board)))
+            .max()
+    };
+    // 与最强对手的牌型差距，没有对手时为 None
+    let lead = |rank: HandRank, board: CardSet| {
+        best_opponent(board).map(|best| rank as i8 - strength_rank(best) as i8)
+    };
+    let behind = best_opponent(board_set).is_some_and(|best| best >= strength);
+
+    let unseen = CardSet::full().difference(known);
+    let outs = unseen
+        .iter()
+        .filter_map(|card| {
+            let new_board = board_set.union(CardSet::from(card));
+            let new_strength = evaluate_set(hand.union(new_board));
+            let new_rank = strength_rank(new_strength);
+            let improves = if behind {
+                best_opponent(new_board).is_some_and(|best| new_strength > best)
+            } else {
+                new_rank > current
+                    && (new_rank > board_rank(new_board)
+                        || lead(new_rank, new_board) > lead(current, board_set))
+            };
+            improves.then_some(Out {
+                card,
+                hand_rank: new_rank,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let draws = classify_draws(hand, board_set);
+    let count = outs.len() as f64;
+    Ok(OutsAnalysis {
+        current,
+        outs,
+        draws,
+        unseen: unseen.len(),
+        rule_of_two: (count * 2.0).min(100.0),
+        rule_of_four: (board.len() == 3).then_some((count * 4.0).min(100.0)),
+    })
+}
+
+/// 按花色和点数掩码判断听牌，听牌必须用到至少一张底牌
+fn classify_draws(hand: CardSet, board: CardSet) -> Vec<DrawType> {
+    let mut draws = Vec::new();
+    let cards = hand.union(board);
+    let on_flop = board.len() == 3;
+
+    // 同花听牌：某花色差一张（翻牌圈差两张为后门），且底牌中有该花色
+    let hand_suits = hand.suit_masks();
+    let suit_masks = cards.suit_masks();
+    if suit_masks.iter().all(|m| m.count_ones() < 5) {
+        for (mask, hand_mask) in suit_masks.iter().zip(hand_suits) {
+            match mask.count_ones() {
+                4 if hand_mask != 0 => draws.push(DrawType::FlushDraw),
+                3 if on_flop && hand_mask != 0 => draws.push(DrawType::BackdoorFlush),
+                _ => {}
+            }
+        }
+    }
+
+    // 顺子听牌：数出能补成顺子的点数，只靠公共牌就能成的顺子不算
+    let ranks = rank_mask(cards);
+    let board_ranks = rank_mask(board);
+    if straight_high(ranks).is_some() {
+        return draws;
+    }
+    let makes_straight = |extra: u16| {
+        straight_high(ranks | extra)
+            .is_some_and(|high| Some(high) > straight_high(board_ranks | extra))
+    };
+    let missing: Vec<u16> = (0..13)
+        .map(|i| 1 << i)
+        .filter(|bit| ranks & bit == 0)
+        .collect();
+    let completing = missing.iter().filter(|&&bit| makes_straight(bit)).count();
+    if completing >= 2 {
+        draws.push(DrawType::OpenEnded);
+    } else if completing == 1 {
+        draws.push(DrawType::Gutshot);
+    } else if on_flop {
+        let backdoor = missing
+            .iter()
+            .enumerate()
+            .any(|(i, &a)| missing[i + 1..].iter().any(|&b| makes_straight(a | b)));
+        if backdoor {
+            draws.push(DrawType::BackdoorStraight);
+        }
+    }
+
+    draws
+}
+
+/// 所有牌的点数掩码，第 0 位为 2
+fn rank_mask(cards: CardSet) -> u16 {
+    cards.suit_masks().iter().fold(0, |acc, m| acc | m)
+}
+
+/// 公共牌本身的牌型，不足五张时只看对子、三条和四条
+fn board_rank(board: CardSet) -> HandRank {
+    if board.len() >= 5 {
+        return strength_rank(evaluate_set(board));
+    }
+    let masks = board.suit_masks();
+    let counts = (0..13).map(|i| masks.iter().filter(|m| *m >> i & 1 == 1).count());
+    let (most, pairs) = counts.fold((0, 0), |(most, pairs), n| {
+        (most.max(n), pairs + (n == 2) as usize)
+    });
+    match (most, pairs) {
+        (4, _) => HandRank::FourOfAKind,
+        (3, _) => HandRank::ThreeOfAKind,
+        (_, 2) => HandRank::TwoPair,
+        (2, _) => HandRank::OnePair,
+        _ => HandRank::HighCard,
+    }
+}
@@ -0,0 +1,925 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::history::*;
+use super::replay::*;
+use super::shared::*;
+use super::state::TexasHoldem;
+
+/// 导出 PokerStars 格式手牌记录时使用的牌桌信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub hand_id: u64, // 第一手牌的编号，之后每手加一
+    pub table_name: String,
+    pub max_seats: usize,
+    pub timestamp: String,    // 如 "2024/01/31 20:15:00 ET"
+    pub hero: Option<String>, // 只显示该玩家的底牌，None 时显示所有人的底牌
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            hand_id: 1,
+            table_name: "Table 1".to_string(),
+            max_seats: 9,
+            timestamp: "1970/01/01 00:00:00 ET".to_string(),
+            hero: None,
+        }
+    }
+}
+
+/// 一位玩家在导出过程中的状态
+struct SeatState {
+    seat: usize, // 从 1 开始
+    name: String,
+    chips: u32,
+    street_bet: u32, // 本轮已投入，不含前注
+    folded_on: Option<GameStage>,
+    shown: Option<((Card, Card), Vec<HandEvaluation>)>,
+    won: u32,
+}
+
+/// 把事件记录中的每手牌导出为 PokerStars 格式，手牌之间空两行
+pub fn export_hands(events: &[HandEvent], options: &ExportOptions) -> Result<String, GameError> {
+    let mut output = String::new();
+    for (i, hand) in split_hands(events).into_iter().enumerate() {
+        let options = ExportOptions {
+            hand_id: options.hand_id + i as u64,
+            ..options.clone()
+        };
+        if i > 0 {
+            output.push_str("\n\n");
+        }
+        output.push_str(&export_hand(hand, &options)?);
+    }
+    Ok(output)
+}
+
+/// 把一手牌的事件记录导出为 PokerStars 格式的文本
+pub fn export_hand(events: &[HandEvent], options: &ExportOptions) -> Result<String, GameError> {
+    let Some(HandEvent::HandStarted {
+        dealer_position,
+        players,
+        small_blind,
+        big_blind,
+        variant,
+        betting,
+        boards,
+        ..
+    }) = events.first()
+    else {
+        return Err(GameError::InvalidAction);
+    };
+
+    let mut seats: Vec<SeatState> = players
+        .iter()
+        .enumerate()
+        .map(|(i, p)| SeatState {
+            seat: i + 1,
+            name: p.name.clone(),
+            chips: p.chips,
+            street_bet: 0,
+            folded_on: None,
+            shown: None,
+            won: 0,
+        })
+        .collect();
+    let index_of: HashMap<&str, usize> = players
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.id.as_str(), i))
+        .collect();
+    let seat_index = |id: &str| index_of.get(id).copied().ok_or(GameError::PlayerNotFound);
+
+    let mut lines = Vec::new();
+    let game = match variant {
+        GameVariant::Holdem => "Hold'em",
+        GameVariant::Pineapple => "Pineapple",
+        GameVariant::CrazyPineapple => "Crazy Pineapple",
+    };
+    // 固定限注的级别写作小注/大注
+    let (limit, stakes) = match betting {
+        BettingStructure::NoLimit => ("No Limit", (*small_blind, *big_blind)),
+        BettingStructure::PotLimit => ("Pot Limit", (*small_blind, *big_blind)),
+        BettingStructure::FixedLimit => ("Limit", (*big_blind, big_blind * 2)),
+    };
+    lines.push(format!(
+        "PokerStars Hand #{}: {} {} ({}/{}) - {}",
+        options.hand_id, game, limit, stakes.0, stakes.1, options.timestamp
+    ));
+    lines.push(format!(
+        "Table '{}' {}-max Seat #{} is the button",
+        options.table_name,
+        options.max_seats,
+        dealer_position + 1
+    ));
+    for seat in &seats {
+        lines.push(format!(
+            "Seat {}: {} ({} in chips)",
+            seat.seat, seat.name, seat.chips
+        ));
+    }
+
+    // 前注和盲注在发牌之前列出
+    let mut small_blind_seat = None;
+    let mut big_blind_seat = None;
+    let mut total_pot = 0;
+    for event in events {
+        match event {
+            HandEvent::AntePosted { player_id, amount } if *amount > 0 => {
+                let seat = &mut seats[seat_index(player_id)?];
+                seat.chips = seat
+                    .chips
+                    .checked_sub(*amount)
+                    .ok_or(GameError::InvalidAction)?;
+                total_pot += amount;
+                lines.push(format!("{}: posts the ante {}", seat.name, amount));
+            }
+            HandEvent::BlindPosted {
+                player_id,
+                blind,
+                amount,
+            } => {
+                let index = seat_index(player_id)?;
+                let seat = &mut seats[index];
+                seat.chips = seat
+                    .chips
+                    .checked_sub(*amount)
+                    .ok_or(GameError::InvalidAction)?;
+                seat.street_bet += amount;
+                total_pot += amount;
+                let name = match blind {
+                    Blind::Small => {
+                        small_blind_seat = Some(index);
+                        "small blind"
+                    }
+                    Blind::Big => {
+                        big_blind_seat = Some(index);
+                        "big blind"
+                    }
+                };
+                lines.push(format!("{}: posts {} {}", seat.name, name, amount));
+            }
+            _ => {}
+        }
+    }
+
+    lines.push("*** HOLE CARDS ***".to_string());
+    for event in events {
+        if let HandEvent::HoleCardsDealt { player_id, cards } = event {
+            if options.hero.as_ref().is_some_and(|hero| hero != player_id) {
+                continue;
+            }
+            let seat = &seats[seat_index(player_id)?];
+            lines.push(format!("Dealt to {} {}", seat.name, card_list(cards)));
+        }
+    }
+
+    let mut board_cards: Vec<Vec<Card>> = vec![Vec::new(); *boards];
+    let mut stage = GameStage::PreFlop;
+    let mut showdown = false;
+    for event in events {
+        match event {
+            HandEvent::ActionTaken {
+                player_id,
+                action,
+                amount,
+            } => {
+                let seat = &mut seats[seat_index(player_id)?];
+                seat.chips = seat
+                    .chips
+                    .checked_sub(*amount)
+                    .ok_or(GameError::InvalidAction)?;
+                seat.street_bet += amount;
+                total_pot += amount;
+                let text = match action {
+                    PlayerAction::Fold => {
+                        seat.folded_on = Some(stage);
+                        "folds".to_string()
+                    }
+                    PlayerAction::Check => "checks".to_string(),
+                    PlayerAction::Call => format!("calls {}", amount),
+                    PlayerAction::Bet(_) => format!("bets {}", amount),
+                    PlayerAction::Raise(by) => format!("raises {} to {}", by, seat.street_bet),
+                    PlayerAction::Discard(card) => format!("discards 1 card [{}]", card.code()),
+                };
+                let all_in = if *amount > 0 && seat.chips == 0 {
+                    " and is all-in"
+                } else {
+                    ""
+                };
+                lines.push(format!("{}: {}{}", seat.name, text, all_in));
+            }
+            HandEvent::BoardDealt { board, cards } => {
+                if let Some(board_cards) = board_cards.get_mut(*board) {
+                    board_cards.extend(cards);
+                }
+            }
+            HandEvent::StreetStarted { stage: next } => {
+                stage = *next;
+                for seat in &mut seats {
+                    seat.street_bet = 0;
+                }
+                let street = match next {
+                    GameStage::Flop => "FLOP",
+                    GameStage::Turn => "TURN",
+                    GameStage::River => "RIVER",
+                    _ => continue,
+                };
+                for (i, cards) in board_cards.iter().enumerate() {
+                    lines.push(format!(
+                        "*** {}{} *** {}",
+                        board_prefix(i, *boards),
+                        street,
+                        street_cards(cards, *next)
+                    ));
+                }
+            }
+            HandEvent::HandShown {
+                player_id,
+                cards,
+                hands,
+            } => {
+                if !showdown {
+                    showdown = true;
+                    lines.push("*** SHOW DOWN ***".to_string());
+                }
+                let seat = &mut seats[seat_index(player_id)?];
+                lines.push(format!(
+                    "{}: shows {} ({})",
+                    seat.name,
+                    card_list(&[cards.0, cards.1]),
+                    hands_description(hands)
+                ));
+                seat.shown = Some((*cards, hands.clone()));
+            }
+            HandEvent::BetReturned { player_id, amount } => {
+                let seat = &seats[seat_index(player_id)?];
+                total_pot = total_pot
+                    .checked_sub(*amount)
+                    .ok_or(GameError::InvalidAction)?;
+                lines.push(format!(
+                    "Uncalled bet ({}) returned to {}",
+                    amount, seat.name
+                ));
+            }
+            HandEvent::PotAwarded {
+                player_id, amount, ..
+            } => {
+                let seat = &mut seats[seat_index(player_id)?];
+                seat.won += amount;
+                lines.push(format!("{} collected {} from pot", seat.name, amount));
+            }
+            _ => {}
+        }
+    }
+
+    lines.push("*** SUMMARY ***".to_string());
+    lines.push(format!("Total pot {} | Rake 0", total_pot));
+    for (i, cards) in board_cards.iter().enumerate() {
+        if !cards.is_empty() {
+            lines.push(format!(
+                "{}Board {}",
+                board_prefix(i, *boards),
+                card_list(cards)
+            ));
+        }
+    }
+    for (i, seat) in seats.iter().enumerate() {
+        let mut position = String::new();
+        if i == *dealer_position {
+            position.push_str(" (button)");
+        }
+        if Some(i) == small_blind_seat {
+            position.push_str(" (small blind)");
+        }
+        if Some(i) == big_blind_seat {
+            position.push_str(" (big blind)");
+        }
+        let result = match (&seat.shown, seat.folded_on) {
+            (_, Some(GameStage::PreFlop)) => "folded before Flop".to_string(),
+            (_, Some(stage)) => format!("folded on the {}", street_name(stage)),
+            (Some((cards, hands)), None) if seat.won > 0 => format!(
+                "showed {} and won ({}) with {}",
+                card_list(&[cards.0, cards.1]),
+                seat.won,
+                hands_description(hands)
+            ),
+            (Some((cards, hands)), None) => format!(
+                "showed {} and lost with {}",
+                card_list(&[cards.0, cards.1]),
+                hands_description(hands)
+            ),
+            (None, None) => format!("collected ({})", seat.won),
+        };
+        lines.push(format!(
+            "Seat {}: {}{} {}",
+            seat.seat, seat.name, position, result
+        ));
+    }
+
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+/// 多块牌面时的前缀，如 "FIRST "
+fn board_prefix(index: usize, boards: usize) -> &'static str {
+    match (boards, index) {
+        (1, _) => "",
+        (_, 0) => "FIRST ",
+        (_, 1) => "SECOND ",
+        _ => "THIRD ",
+    }
+}
+
+fn street_name(stage: GameStage) -> &'static str {
+    match stage {
+        GameStage::PreFlop => "Pre-Flop",
+        GameStage::Flop => "Flop",
+        GameStage::Turn => "Turn",
+        GameStage::River => "River",
+        GameStage::Showdown => "Showdown",
+    }
+}
+
+/// 如 "[Ah Kd]"
+fn card_list(cards: &[Card]) -> String {
+    let codes: Vec<String> = cards.iter().map(|c| c.code()).collect();
+    format!("[{}]", codes.join(" "))
+}
+
+/// 翻牌后各阶段的公共牌，如 "[2c 3d 4h] [5s]"
+fn street_cards(cards: &[Card], stage: GameStage) -> String {
+    match stage {
+        GameStage::Turn | GameStage::River if cards.len() > 3 => {
+            let last = cards.len() - 1;
+            format!(
+                "{} {}",
+                card_list(&cards[..last]),
+                card_list(&cards[last..])
+            )
+        }
+        _ => card_list(cards),
+    }
+}
+
+/// 各块牌面上的牌型描述，多块牌面时以 " / " 分隔
+fn hands_description(hands: &[HandEvaluation]) -> String {
+    let descriptions: Vec<String> = hands.iter().map(hand_description).collect();
+    descriptions.join(" / ")
+}
+
+/// PokerStars 的牌型描述，如 "a pair of Aces"、"a straight, Six to Ten"
+fn hand_description(hand: &HandEvaluation) -> String {
+    let kicker = |i: usize| hand.kickers.get(i).copied().unwrap_or(Rank::Two);
+    // A-2-3-4-5 顺子的最小牌是 A
+    let straight_low = || match kicker(0) {
+        Rank::Five => Rank::Ace,
+        high => Rank::from_value(high.value() - 4),
+    };
+    match hand.rank {
+        HandRank::RoyalFlush => "a Royal Flush".to_string(),
+        HandRank::StraightFlush => format!(
+            "a straight flush, {} to {}",
+            straight_low().name(),
+            kicker(0).name()
+        ),
+        HandRank::FourOfAKind => format!("four of a kind, {}", kicker(0).plural_name()),
+        HandRank::FullHouse => format!(
+            "a full house, {} full of {}",
+            kicker(0).plural_name(),
+            kicker(1).plural_name()
+        ),
+        HandRank::Flush => format!("a flush, {} high", kicker(0).name()),
+        HandRank::Straight => format!(
+            "a straight, {} to {}",
+            straight_low().name(),
+            kicker(0).name()
+        ),
+        HandRank::ThreeOfAKind => format!("three of a kind, {}", kicker(0).plural_name()),
+        HandRank::TwoPair => format!(
+            "two pair, {} and {}",
+            kicker(0).plural_name(),
+            kicker(1).plural_name()
+        ),
+        HandRank::OnePair => format!("a pair of {}", kicker(0).plural_name()),
+        HandRank::HighCard => format!("high card {}", kicker(0).name()),
+    }
+}
+
+/// 解析 PokerStars 手牌记录时的错误，行号从 1 开始
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("line {line}: {message}")]
+pub struct HandParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// 导入的座位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedSeat {
+    pub seat: usize,
+    pub name: String,
+    pub chips: u32,
+}
+
+/// 导入的玩家行动，玩家以名字标识
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedAction {
+    pub player: String,
+    pub stage: GameStage,
+    pub action: PlayerAction,
+}
+
+/// 从 PokerStars 文本解析出的一手牌，金额以最小单位计（现金桌为分）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedHand {
+    pub hand_id: u64,
+    pub table_name: String,
+    pub max_seats: usize,
+    pub button_seat: usize,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    pub seats: Vec<ImportedSeat>,
+    pub hole_cards: Vec<(String, (Card, Card))>, // 发牌或摊牌时已知的底牌
+    pub actions: Vec<ImportedAction>,
+    pub board: Vec<Card>,
+    pub collected: Vec<(String, u32)>,
+}
+
+impl ImportedHand {
+    /// 对应的引擎设置，未知的底牌用剩下的牌补齐
+    pub fn setup(&self) -> Result<HandSetup, GameError> {
+        let dealer_position = self
+            .seats
+            .iter()
+            .position(|s| s.seat == self.button_seat)
+            .ok_or(GameError::PlayerNotFound)?;
+
+        let hole_cards: Vec<Option<(Card, Card)>> = self
+            .seats
+            .iter()
+            .map(|seat| {
+                self.hole_cards
+                    .iter()
+                    .find(|(name, _)| *name == seat.name)
+                    .map(|(_, cards)| *cards)
+            })
+            .collect();
+
+        Ok(HandSetup {
+            players: self
+                .seats
+                .iter()
+                .map(|s| SeatInfo {
+                    id: s.name.clone(),
+                    name: s.name.clone(),
+                    chips: s.chips,
+                })
+                .collect(),
+            config: GameConfig {
+                variant: GameVariant::Holdem,
+                small_blind: self.small_blind,
+                big_blind: self.big_blind,
+                ante: self.ante,
+                ..Default::default()
+            },
+            dealer_position,
+            deck: DeckSetup::from_known(&hole_cards, &self.board)?,
+        })
+    }
+
+    /// 按引擎记录格式列出的行动
+    pub fn recorded_actions(&self) -> Vec<RecordedAction> {
+        self.actions
+            .iter()
+            .map(|a| RecordedAction {
+                player_id: a.player.clone(),
+                action: a.action.clone(),
+            })
+            .collect()
+    }
+
+    /// 用引擎重放这手牌
+    pub fn replay(&self) -> Result<Replay, GameError> {
+        Replay::new(self.setup()?, self.recorded_actions())
+    }
+
+    /// 用引擎打完这手牌，到达摊牌时结算底池
+    pub fn play(&self) -> Result<TexasHoldem, GameError> {
+        let mut game = self.setup()?.start()?;
+        for action in &self.actions {
+            game.handle_action_for(&action.player, action.action.clone())?;
+        }
+        if game.state.stage == GameStage::Showdown {
+            game.resolve_showdown();
+        }
+        Ok(game)
+    }
+}
+
+/// 解析文本中的所有手牌，每手牌以 "PokerStars ... Hand #" 开头的行开始
+pub fn parse_hands(text: &str) -> Result<Vec<ImportedHand>, HandParseError> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .collect();
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, line))| is_hand_header(line))
+        .map(|(i, _)| i)
+        .collect();
+    if starts.is_empty() {
+        return Err(parse_error(1, "missing hand header"));
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+            parse_lines(&lines[start..end])
+        })
+        .collect()
+}
+
+/// 解析一手牌
+pub fn parse_hand(text: &str) -> Result<ImportedHand, HandParseError> {
+    let mut hands = parse_hands(text)?;
+    match hands.len() {
+        1 => Ok(hands.remove(0)),
+        _ => Err(parse_error(1, "expected exactly one hand")),
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.starts_with("PokerStars ") && line.contains("Hand #")
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> HandParseError {
+    HandParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// 解析过程中所处的部分
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Seats,
+    Street(GameStage),
+    Summary,
+}
+
+fn parse_lines(lines: &[(usize, &str)]) -> Result<ImportedHand, HandParseError> {
+    let (header_line, header) = lines[0];
+    let error = |message: &str| parse_error(header_line, message);
+    if !header.contains("Hold'em No Limit") {
+        return Err(error(
+            "unsupported game, only Hold'em No Limit is supported",
+        ));
+    }
+    let hand_id = header
+        .split_once("Hand #")
+        .and_then(|(_, rest)| rest.split(':').next())
+        .and_then(|id| id.trim().parse().ok())
+        .ok_or_else(|| error("invalid hand number"))?;
+    let stakes = header
+        .split_once("Hold'em No Limit")
+        .and_then(|(_, rest)| rest.split_once('('))
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(stakes, _)| stakes.split_whitespace().next().unwrap_or(""))
+        .ok_or_else(|| error("missing blinds"))?;
+    // 现金桌金额带货币符号，按分计
+    let scale = if stakes.contains(['$', '€', '£']) {
+        100
+    } else {
+        1
+    };
+    let amount = |line: usize, s: &str| {
+        parse_amount(s, scale).ok_or_else(|| parse_error(line, format!("invalid amount: {}", s)))
+    };
+    let (small_blind, big_blind) = stakes
+        .split_once('/')
+        .ok_or_else(|| error("missing blinds"))?;
+    let small_blind = amount(header_line, small_blind)?;
+    let big_blind = amount(header_line, big_blind)?;
+
+    let mut hand = ImportedHand {
+        hand_id,
+        table_name: String::new(),
+        max_seats: 0,
+        button_seat: 0,
+        small_blind,
+        big_blind,
+        ante: 0,
+        seats: Vec::new(),
+        hole_cards: Vec::new(),
+        actions: Vec::new(),
+        board: Vec::new(),
+        collected: Vec::new(),
+    };
+
+    let (table_line, table) = *lines.get(1).ok_or_else(|| error("missing table line"))?;
+    let table_error = || parse_error(table_line, "invalid table line");
+    let (name, rest) = table
+        .strip_prefix("Table '")
+        .and_then(|rest| rest.rsplit_once("' "))
+        .ok_or_else(table_error)?;
+    hand.table_name = name.to_string();
+    hand.max_seats = rest
+        .split_once("-max")
+        .and_then(|(max, _)| max.trim().parse().ok())
+        .unwrap_or(0);
+    hand.button_seat = rest
+        .split_once("Seat #")
+        .and_then(|(_, seat)| seat.split_whitespace().next())
+        .and_then(|seat| seat.parse().ok())
+        .ok_or_else(table_error)?;
+
+    let mut section = Section::Seats;
+    let mut street_bets: HashMap<String, u32> = HashMap::new();
+    let mut blinds: Vec<(usize, Blind, String)> = Vec::new();
+    for &(line, text) in &lines[2..] {
+        if text.is_empty() {
+            continue;
+        }
+        let error = |message: &str| parse_error(line, message);
+
+        if let Some(marker) = text.strip_prefix("*** ") {
+            let (name, cards) = marker
+                .split_once(" ***")
+                .ok_or_else(|| error("invalid section marker"))?;
+            section = match name {
+                "HOLE CARDS" => {
+                    check_blinds(&hand, &blinds)?;
+                    Section::Street(GameStage::PreFlop)
+                }
+                "FLOP" | "TURN" | "RIVER" => {
+                    let (stage, count, expected) = match name {
+                        "FLOP" => (GameStage::Flop, 3, 0),
+                        "TURN" => (GameStage::Turn, 1, 3),
+                        _ => (GameStage::River, 1, 4),
+                    };
+                    let new_cards = cards
+                        .rsplit_once('[')
+                        .and_then(|(_, cards)| cards.strip_suffix(']'))
+                        .ok_or_else(|| error("missing board cards"))?;
+                    let new_cards =
+                        parse_cards(new_cards).map_err(|e| parse_error(line, e.to_string()))?;
+                    if new_cards.len() != count || hand.board.len() != expected {
+                        return Err(error("unexpected board cards"));
+                    }
+                    hand.board.extend(new_cards);
+                    street_bets.clear();
+                    Section::Street(stage)
+                }
+                "SHOW DOWN" => Section::Street(GameStage::Showdown),
+                "SUMMARY" => Section::Summary,
+                _ => return Err(error(&format!("unsupported section: {}", name))),
+            };
+            continue;
+        }
+
+        if section == Section::Summary {
+            // 摘要中只读取亮出或盖掉的底牌
+            if let Some((seat, rest)) = text
+                .strip_prefix("Seat ")
+                .and_then(|rest| rest.split_once(": "))
+                && let Some((_, cards)) = rest
+                    .split_once("showed [")
+                    .or_else(|| rest.split_once("mucked ["))
+            {
+                let seat: usize = seat.parse().map_err(|_| error("invalid seat"))?;
+                let name = hand
+                    .seats
+                    .iter()
+                    .find(|s| s.seat == seat)
+                    .map(|s| s.name.clone())
+                    .ok_or_else(|| error("unknown seat"))?;
+                let cards = cards.split(']').next().unwrap_or("");
+                record_hole_cards(&mut hand, name, cards, line)?;
+            }
+            continue;
+        }
+
+        if section == Section::Seats
+            && let Some(rest) = text.strip_prefix("Seat ")
+            && let Some((seat, rest)) = rest.split_once(": ")
+        {
+            let seat = seat.parse().map_err(|_| error("invalid seat"))?;
+            let (name_and_chips, trailing) = rest
+                .split_once(" in chips)")
+                .ok_or_else(|| error("invalid seat line"))?;
+            if !trailing.trim().is_empty() {
+                return Err(error(&format!(
+                    "unsupported seat status: {}",
+                    trailing.trim()
+                )));
+            }
+            let (name, chips) = name_and_chips
+                .rsplit_once(" (")
+                .ok_or_else(|| error("invalid seat line"))?;
+            hand.seats.push(ImportedSeat {
+                seat,
+                name: name.to_string(),
+                chips: amount(line, chips)?,
+            });
+            continue;
+        }
+
+        if let Some(rest) = text.strip_prefix("Dealt to ") {
+            let (name, cards) = rest
+                .rsplit_once(" [")
+                .ok_or_else(|| error("invalid dealt cards"))?;
+            let cards = cards.strip_suffix(']').unwrap_or(cards);
+            record_hole_cards(&mut hand, name.to_string(), cards, line)?;
+            continue;
+        }
+
+        let Some(player) = hand
+            .seats
+            .iter()
+            .map(|s| s.name.as_str())
+            .filter(|name| text.starts_with(name))
+            .max_by_key(|name| name.len())
+            .map(str::to_string)
+        else {
+            // 与玩家无关的提示行，如退回未跟注的筹码
+            continue;
+        };
+        let rest = &text[player.len()..];
+
+        if let Some(rest) = rest.strip_prefix(" collected ") {
+            let collected = rest.split_whitespace().next().unwrap_or("");
+            hand.collected.push((player, amount(line, collected)?));
+            continue;
+        }
+        let Some(verb) = rest.strip_prefix(": ") else {
+            // 聊天、入座、断线等提示
+            continue;
+        };
+        let verb = verb.strip_suffix(" and is all-in").unwrap_or(verb);
+        let stage = match section {
+            Section::Street(stage) => stage,
+            _ => GameStage::PreFlop,
+        };
+        let street_bet = street_bets.get(&player).copied().unwrap_or(0);
+        let current_bet = street_bets.values().copied().max().unwrap_or(0);
+
+        if let Some(post) = verb.strip_prefix("posts ") {
+            if section != Section::Seats {
+                return Err(error("unexpected post"));
+            }
+            if let Some(ante) = post.strip_prefix("the ante ") {
+                hand.ante = hand.ante.max(amount(line, ante)?);
+                continue;
+            }
+            let (blind, posted) = if let Some(posted) = post.strip_prefix("small blind ") {
+                (Blind::Small, posted)
+            } else if let Some(posted) = post.strip_prefix("big blind ") {
+                (Blind::Big, posted)
+            } else {
+                return Err(error(&format!("unsupported post: {}", post)));
+            };
+            let posted = amount(line, posted)?;
+            street_bets.insert(player.clone(), street_bet + posted);
+            blinds.push((line, blind, player));
+            continue;
+        }
+
+        let action = match verb.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["folds", ..] => PlayerAction::Fold,
+            ["checks"] => PlayerAction::Check,
+            ["calls", called] => {
+                street_bets.insert(player.clone(), street_bet + amount(line, called)?);
+                PlayerAction::Call
+            }
+            ["bets", bet] => {
+                let bet = amount(line, bet)?;
+                street_bets.insert(player.clone(), street_bet + bet);
+                PlayerAction::Bet(bet)
+            }
+            ["raises", _, "to", total] => {
+                let total = amount(line, total)?;
+                if total <= current_bet {
+                    return Err(error("invalid raise"));
+                }
+                street_bets.insert(player.clone(), total);
+                PlayerAction::Raise(total - current_bet)
+            }
+            ["shows", ..] => {
+                let cards = verb
+                    .split_once('[')
+                    .and_then(|(_, cards)| cards.split_once(']'))
+                    .map(|(cards, _)| cards)
+                    .ok_or_else(|| error("invalid shown cards"))?;
+                record_hole_cards(&mut hand, player, cards, line)?;
+                continue;
+            }
+            ["mucks", ..]
+            | ["doesn't", "show", "hand"]
+            | ["sits", "out"]
+            | ["is", "sitting", "out"]
+            | ["is", "disconnected"]
+            | ["is", "connected"]
+            | ["has", "timed", "out"]
+            | ["has", "returned"] => continue,
+            _ => return Err(error(&format!("unsupported action: {}", verb))),
+        };
+        if section == Section::Seats {
+            return Err(error("action before hole cards"));
+        }
+        hand.actions.push(ImportedAction {
+            player,
+            stage,
+            action,
+        });
+    }
+
+    if section != Section::Summary {
+        let last = lines.last().map(|(line, _)| *line).unwrap_or(header_line);
+        return Err(parse_error(last, "missing summary"));
+    }
+    if hand.max_seats == 0 {
+        hand.max_seats = hand.seats.len();
+    }
+    Ok(hand)
+}
+
+/// 记录玩家的底牌，同一玩家重复出现时必须一致
+fn record_hole_cards(
+    hand: &mut ImportedHand,
+    name: String,
+    cards: &str,
+    line: usize,
+) -> Result<(), HandParseError> {
+    let cards = match parse_cards(cards)
+        .map_err(|e| parse_error(line, e.to_string()))?
+        .as_slice()
+    {
+        [card1, card2] => (*card1, *card2),
+        _ => return Err(parse_error(line, "only two hole cards are supported")),
+    };
+    match hand.hole_cards.iter().find(|(n, _)| *n == name) {
+        Some((_, known)) if *known == cards => Ok(()),
+        Some(_) => Err(parse_error(line, "conflicting hole cards")),
+        None => {
+            hand.hole_cards.push((name, cards));
+            Ok(())
+        }
+    }
+}
+
+/// 盲注必须由引擎约定的位置下：庄家下家小盲，再下家大盲
+fn check_blinds(
+    hand: &ImportedHand,
+    blinds: &[(usize, Blind, String)],
+) -> Result<(), HandParseError> {
+    let count = hand.seats.len();
+    let dealer = hand
+        .seats
+        .iter()
+        .position(|s| s.seat == hand.button_seat)
+        .ok_or_else(|| parse_error(2, "button seat is empty"))?;
+    for (line, blind, player) in blinds {
+        let offset = match blind {
+            Blind::Small => 1,
+            Blind::Big => 2,
+        };
+        if hand.seats[(dealer + offset) % count].name != *player {
+            return Err(parse_error(*line, "unsupported blind position"));
+        }
+    }
+    if blinds.len() != 2 {
+        let line = blinds.last().map(|(line, _, _)| *line).unwrap_or(2);
+        return Err(parse_error(
+            line,
+            "expected one small blind and one big blind",
+        ));
+    }
+    Ok(())
+}
+
+/// 解析金额，如 "20"、"$0.10"
+fn parse_amount(s: &str, scale: u32) -> Option<u32> {
+    let s = s.trim_start_matches(['$', '€', '£']).trim_end_matches(',');
+    match s.split_once('.') {
+        None => s.parse::<u32>().ok()?.checked_mul(scale),
+        Some((whole, fraction)) if scale == 100 && !fraction.is_empty() && fraction.len() <= 2 => {
+            let whole: u32 = if whole.is_empty() {
+                0
+            } else {
+                whole.parse().ok()?
+            };
+            let cents: u32 = format!("{:0<2}", fraction).parse().ok()?;
+            whole.checked_mul(100)?.checked_add(cents)
+        }
+        _ => None,
+    }
+}
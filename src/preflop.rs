@@ -0,0 +1,353 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::equity::{EquityOptions, EquityResult, EquityTally, sample_cards};
+use super::evaluator::evaluate_set;
+use super::range::HandClass;
+use super::shared::*;
+
+/// 起手牌种类数
+pub const STARTING_HAND_COUNT: usize = 169;
+
+/// 预先计算胜率的最多对手数
+pub const MAX_TABLE_OPPONENTS: usize = 9;
+
+/// 按点数和是否同花归类的起手牌，如 AKs、72o、TT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StartingHand {
+    pub high: Rank,
+    pub low: Rank,
+    pub suited: bool, // 对子总是 false
+}
+
+impl StartingHand {
+    /// 两张底牌所属的起手牌
+    pub fn from_cards(c1: Card, c2: Card) -> Self {
+        StartingHand {
+            high: c1.rank.max(c2.rank),
+            low: c1.rank.min(c2.rank),
+            suited: c1.suit == c2.suit && c1.rank != c2.rank,
+        }
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /// 名称，如 "AKs"、"72o"、"TT"
+    pub fn name(&self) -> String {
+        let suffix = match (self.is_pair(), self.suited) {
+            (true, _) => "",
+            (false, true) => "s",
+            (false, false) => "o",
+        };
+        format!("{}{}{}", self.high.to_char(), self.low.to_char(), suffix)
+    }
+
+    /// 这一类的所有具体底牌，点数大的在前（对子 6 种，同花 4 种，非同花 12 种）
+    pub fn combos(&self) -> Vec<(Card, Card)> {
+        HandClass {
+            high: self.high,
+            low: self.low,
+            suited: Some(self.suited),
+        }
+        .combos()
+    }
+
+    /// 在 13×13 矩阵中的位置 (行, 列)，A 在第 0 行/列
+    ///
+    /// 对角线为对子，右上方为同花，左下方为非同花。
+    pub fn matrix_position(&self) -> (usize, usize) {
+        let high = 14 - self.high.value() as usize;
+        let low = 14 - self.low.value() as usize;
+        if self.suited {
+            (high, low)
+        } else {
+            (low, high)
+        }
+    }
+
+    /// 在 `StartingHand::all` 中的序号
+    pub fn index(&self) -> usize {
+        let (row, col) = self.matrix_position();
+        row * 13 + col
+    }
+
+    /// 对 `opponents` 个随机手牌全下到底的胜率（百分比），查预先计算的表
+    pub fn equity(&self, opponents: usize) -> Result<f64, GameError> {
+        if !(1..=MAX_TABLE_OPPONENTS).contains(&opponents) {
+            return Err(GameError::InvalidCards);
+        }
+        Ok(EQUITY_VS_RANDOM[self.index()][opponents - 1] as f64)
+    }
+
+    /// 矩阵中指定位置的起手牌
+    pub fn at(row: usize, col: usize) -> Self {
+        assert!(row < 13 && col < 13, "矩阵下标超出范围");
+        let rank = |i: usize| Rank::from_value(14 - i as u8);
+        StartingHand {
+            high: rank(row.min(col)),
+            low: rank(row.max(col)),
+            suited: row < col,
+        }
+    }
+
+    /// 全部 169 种起手牌，按矩阵逐行排列
+    pub fn all() -> Vec<StartingHand> {
+        (0..13)
+            .flat_map(|row| (0..13).map(move |col| StartingHand::at(row, col)))
+            .collect()
+    }
+}
+
+impl fmt::Display for StartingHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for StartingHand {
+    type Err = GameError;
+
+    /// 解析 "AKs"、"72o"、"TT"，非对子必须带 s 或 o
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let class = HandClass::parse(s).ok_or(GameError::InvalidCards)?;
+        let suited = match class.suited {
+            Some(suited) => suited,
+            None if class.is_pair() => false,
+            None => return Err(GameError::InvalidCards),
+        };
+        Ok(StartingHand {
+            high: class.high,
+            low: class.low,
+            suited,
+        })
+    }
+}
+
+/// 用蒙特卡洛抽样计算一种起手牌对 `opponents` 个随机手牌全下到底的胜率
+///
+/// 每次抽样随机选择这一类中的一种具体底牌，再随机发对手底牌和公共牌；
+/// 组合数过大，因此总是使用抽样（`samples` 和 `seed`）。预先计算的表由此生成，
+/// 一般直接用 `StartingHand::equity` 查表。
+pub fn equity_vs_random(
+    hand: StartingHand,
+    opponents: usize,
+    options: &EquityOptions,
+) -> Result<EquityResult, GameError> {
+    // 所有人的底牌加五张公共牌不能超过一副牌
+    if opponents == 0 || 2 + 2 * opponents + 5 > 52 {
+        return Err(GameError::InvalidCards);
+    }
+
+    let combos = hand.combos();
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let mut tally = EquityTally::new(opponents + 1);
+    let mut strengths = vec![0; opponents + 1];
+
+    for _ in 0..options.samples {
+        let (c1, c2) = combos[rng.random_range(0..combos.len())];
+        let hero = CardSet::from_cards(&[c1, c2]);
+        let mut deck = CardSet::full().difference(hero).to_vec();
+        let board = sample_cards(&mut deck, 5, &mut rng);
+
+        // 抽出的牌依次排在牌堆前面，后面的牌再发给对手
+        let rest = &mut deck[5..];
+        sample_cards(rest, 2 * opponents, &mut rng);
+        strengths[0] = evaluate_set(board.union(hero));
+        for (strength, pair) in strengths[1..].iter_mut().zip(rest.chunks(2)) {
+            *strength = evaluate_set(board.union(CardSet::from_cards(pair)));
+        }
+        tally.record(&strengths, 1.0);
+    }
+
+    Ok(tally.finish(false).players[0])
+}
+
+/// 所有 169 种起手牌对 `opponents` 个随机手牌的胜率（百分比），顺序与 `StartingHand::all` 相同
+pub fn equity_table(opponents: usize) -> Result<Vec<(StartingHand, f64)>, GameError> {
+    StartingHand::all()
+        .into_iter()
+        .map(|hand| Ok((hand, hand.equity(opponents)?)))
+        .collect()
+}
+
+/// 每种起手牌对 1 到 9 个随机手牌的胜率（百分比），顺序与 `StartingHand::all` 相同
+///
+/// 由 `equity_vs_random` 每项抽样 200,000 次生成，误差约 ±0.3%。
+const EQUITY_VS_RANDOM: [[f32; MAX_TABLE_OPPONENTS]; STARTING_HAND_COUNT] = [
+    [85.3, 73.7, 63.9, 55.9, 49.5, 43.7, 38.8, 34.7, 31.1], // AA
+    [67.0, 50.7, 41.5, 35.4, 30.9, 27.7, 24.9, 22.7, 20.5], // AKs
+    [66.3, 49.3, 39.9, 33.7, 29.3, 25.9, 23.3, 21.1, 19.1], // AQs
+    [65.4, 48.0, 38.5, 32.2, 28.0, 24.6, 21.9, 20.0, 18.1], // AJs
+    [64.6, 46.9, 37.1, 31.0, 26.8, 23.6, 21.2, 19.0, 17.4], // ATs
+    [62.7, 44.7, 34.4, 28.0, 23.9, 21.1, 18.7, 16.9, 15.4], // A9s
+    [62.0, 43.6, 33.5, 27.4, 23.3, 20.4, 18.1, 16.1, 14.8], // A8s
+    [61.0, 42.4, 32.5, 26.4, 22.5, 19.6, 17.4, 15.6, 14.3], // A7s
+    [59.9, 41.3, 31.2, 25.3, 21.6, 18.7, 16.7, 15.1, 13.9], // A6s
+    [59.9, 41.3, 31.8, 25.9, 22.3, 19.5, 17.4, 15.8, 14.4], // A5s
+    [59.1, 40.5, 30.8, 25.4, 21.7, 19.0, 17.1, 15.4, 14.2], // A4s
+    [58.2, 39.5, 30.3, 24.8, 21.2, 18.7, 16.8, 15.1, 14.0], // A3s
+    [57.3, 38.6, 29.7, 24.2, 20.8, 18.2, 16.3, 14.9, 13.6], // A2s
+    [65.5, 48.2, 38.5, 32.2, 27.9, 24.4, 21.7, 19.5, 17.2], // AKo
+    [82.5, 68.9, 58.3, 49.8, 42.9, 37.4, 33.0, 29.2, 26.0], // KK
+    [63.5, 47.2, 38.2, 32.6, 28.3, 25.0, 22.5, 20.4, 18.8], // KQs
+    [62.6, 45.9, 36.8, 31.1, 26.9, 23.9, 21.3, 19.2, 17.7], // KJs
+    [61.9, 44.8, 35.6, 29.9, 25.8, 22.8, 20.3, 18.4, 16.9], // KTs
+    [59.9, 42.4, 32.9, 27.0, 23.3, 20.3, 18.0, 16.3, 14.8], // K9s
+    [58.3, 40.0, 30.6, 25.2, 21.2, 18.7, 16.5, 14.9, 13.6], // K8s
+    [57.5, 39.4, 29.9, 24.4, 21.0, 18.0, 16.1, 14.4, 13.2], // K7s
+    [56.8, 38.3, 29.1, 23.5, 19.9, 17.6, 15.5, 13.9, 12.8], // K6s
+    [55.8, 37.4, 28.3, 23.0, 19.6, 17.1, 15.2, 13.7, 12.4], // K5s
+    [54.9, 36.4, 27.5, 22.4, 18.9, 16.7, 14.8, 13.4, 12.3], // K4s
+    [54.0, 35.6, 26.8, 21.8, 18.5, 16.4, 14.6, 13.3, 12.1], // K3s
+    [53.3, 34.8, 26.2, 21.4, 18.2, 15.9, 14.3, 13.1, 12.0], // K2s
+    [64.4, 46.8, 36.8, 30.4, 25.9, 22.4, 19.6, 17.4, 15.6], // AQo
+    [61.5, 44.5, 35.2, 29.1, 25.1, 21.7, 19.0, 16.9, 15.1], // KQo
+    [79.8, 65.1, 53.4, 44.8, 37.7, 32.6, 28.3, 24.9, 22.2], // QQ
+    [60.2, 44.3, 35.8, 30.3, 26.3, 23.3, 20.8, 18.7, 17.3], // QJs
+    [59.6, 43.2, 34.5, 29.3, 25.2, 22.1, 19.9, 18.1, 16.6], // QTs
+    [57.7, 40.7, 31.7, 26.3, 22.5, 19.8, 17.6, 15.9, 14.6], // Q9s
+    [56.2, 38.4, 29.6, 24.4, 20.8, 18.1, 15.9, 14.3, 13.2], // Q8s
+    [54.3, 36.3, 27.8, 22.6, 19.0, 16.6, 14.8, 13.3, 12.1], // Q7s
+    [53.6, 35.8, 26.9, 21.8, 18.5, 16.1, 14.3, 13.0, 11.7], // Q6s
+    [52.8, 34.7, 26.2, 21.3, 18.1, 15.6, 13.9, 12.5, 11.5], // Q5s
+    [51.8, 34.0, 25.6, 20.8, 17.6, 15.2, 13.6, 12.3, 11.2], // Q4s
+    [51.0, 33.1, 24.8, 20.2, 17.1, 15.2, 13.4, 12.1, 11.2], // Q3s
+    [50.2, 32.4, 24.2, 19.7, 16.8, 14.7, 13.1, 12.0, 11.0], // Q2s
+    [63.5, 45.4, 35.3, 28.8, 24.4, 21.1, 18.3, 16.0, 14.2], // AJo
+    [60.6, 43.1, 33.8, 27.6, 23.5, 20.2, 17.6, 15.7, 14.1], // KJo
+    [58.1, 41.6, 32.7, 26.9, 22.9, 19.8, 17.2, 15.3, 13.8], // QJo
+    [77.4, 61.3, 49.4, 40.2, 33.6, 28.5, 24.6, 21.7, 19.4], // JJ
+    [57.6, 42.2, 34.0, 28.7, 24.7, 22.1, 19.8, 18.0, 16.7], // JTs
+    [55.7, 39.4, 31.4, 25.9, 22.3, 19.5, 17.6, 15.9, 14.7], // J9s
+    [54.1, 37.4, 29.0, 24.1, 20.7, 18.0, 16.0, 14.5, 13.2], // J8s
+    [52.3, 35.3, 27.1, 22.2, 18.8, 16.4, 14.5, 13.2, 12.0], // J7s
+    [50.5, 33.4, 25.1, 20.4, 17.3, 15.0, 13.4, 12.1, 10.9], // J6s
+    [49.9, 32.6, 24.6, 19.9, 16.9, 14.7, 13.0, 11.8, 10.7], // J5s
+    [49.0, 32.2, 23.8, 19.3, 16.5, 14.4, 12.7, 11.6, 10.6], // J4s
+    [48.1, 31.1, 23.5, 18.9, 16.0, 14.1, 12.6, 11.4, 10.6], // J3s
+    [47.4, 30.3, 22.5, 18.5, 15.6, 13.8, 12.3, 11.2, 10.2], // J2s
+    [62.7, 44.1, 33.8, 27.6, 23.1, 19.6, 17.2, 15.0, 13.4], // ATo
+    [59.8, 42.0, 32.3, 26.5, 22.2, 19.1, 16.7, 14.4, 13.0], // KTo
+    [57.3, 40.4, 31.2, 25.8, 21.7, 18.8, 16.5, 14.4, 12.8], // QTo
+    [55.4, 39.1, 30.7, 25.4, 21.4, 18.5, 16.3, 14.5, 13.1], // JTo
+    [75.1, 57.6, 45.1, 36.2, 29.9, 25.2, 21.6, 19.1, 17.2], // TT
+    [54.0, 38.5, 31.0, 26.1, 22.4, 19.8, 17.7, 16.0, 14.8], // T9s
+    [52.2, 36.5, 29.0, 24.0, 20.7, 18.0, 16.2, 14.8, 13.5], // T8s
+    [50.7, 34.7, 26.9, 22.1, 18.9, 16.7, 14.8, 13.4, 12.2], // T7s
+    [48.9, 32.7, 25.1, 20.4, 17.3, 15.1, 13.5, 12.2, 11.2], // T6s
+    [47.2, 30.8, 23.2, 19.0, 15.9, 14.0, 12.3, 11.3, 10.2], // T5s
+    [46.7, 30.3, 22.6, 18.4, 15.6, 13.5, 12.0, 11.0, 10.1], // T4s
+    [45.6, 29.4, 22.1, 18.1, 15.1, 13.1, 11.9, 10.9, 9.9],  // T3s
+    [44.9, 28.7, 21.5, 17.4, 15.0, 13.2, 11.8, 10.7, 9.7],  // T2s
+    [60.9, 41.5, 31.1, 24.6, 20.1, 17.0, 14.7, 12.7, 11.3], // A9o
+    [58.0, 39.1, 29.6, 23.7, 19.5, 16.5, 14.2, 12.3, 10.8], // K9o
+    [55.3, 37.4, 28.4, 22.7, 19.0, 16.1, 13.7, 11.9, 10.6], // Q9o
+    [53.2, 36.5, 27.7, 22.4, 18.7, 15.8, 13.9, 12.3, 11.0], // J9o
+    [51.4, 35.8, 27.6, 22.5, 18.9, 16.2, 14.2, 12.7, 11.4], // T9o
+    [71.9, 53.6, 41.3, 32.7, 26.7, 22.3, 19.4, 17.3, 15.5], // 99
+    [50.8, 36.0, 28.6, 23.5, 20.3, 17.8, 15.9, 14.5, 13.4], // 98s
+    [49.1, 34.1, 26.7, 22.0, 18.9, 16.6, 14.9, 13.5, 12.6], // 97s
+    [47.6, 32.2, 24.7, 20.1, 17.5, 15.1, 13.7, 12.4, 11.3], // 96s
+    [45.6, 30.3, 23.1, 18.7, 15.9, 13.9, 12.3, 11.3, 10.3], // 95s
+    [43.7, 28.3, 21.3, 17.2, 14.7, 12.7, 11.4, 10.3, 9.4],  // 94s
+    [43.1, 27.8, 20.8, 16.8, 14.3, 12.5, 11.0, 10.0, 9.2],  // 93s
+    [42.4, 27.2, 20.3, 16.5, 14.0, 12.1, 10.8, 9.9, 9.1],   // 92s
+    [59.9, 40.6, 29.9, 23.4, 19.2, 16.2, 13.8, 12.0, 10.4], // A8o
+    [56.0, 36.9, 27.0, 21.4, 17.4, 14.7, 12.6, 10.8, 9.3],  // K8o
+    [53.4, 35.2, 26.1, 20.6, 16.9, 14.2, 12.2, 10.5, 9.3],  // Q8o
+    [51.4, 34.2, 25.6, 20.4, 16.7, 14.1, 12.1, 10.7, 9.5],  // J8o
+    [49.7, 33.5, 25.3, 20.4, 16.9, 14.4, 12.4, 11.1, 9.9],  // T8o
+    [48.3, 32.9, 25.0, 20.1, 16.7, 14.1, 12.3, 10.8, 9.9],  // 98o
+    [69.2, 49.9, 37.5, 29.5, 24.0, 20.3, 17.9, 15.8, 14.4], // 88
+    [47.8, 33.9, 26.7, 22.1, 19.0, 16.8, 14.9, 13.7, 12.8], // 87s
+    [46.3, 32.0, 24.8, 20.5, 17.6, 15.5, 14.1, 12.8, 11.9], // 86s
+    [44.5, 30.1, 23.1, 19.1, 16.2, 14.3, 12.8, 11.8, 10.9], // 85s
+    [42.6, 28.2, 21.4, 17.6, 14.8, 13.0, 11.7, 10.6, 9.9],  // 84s
+    [40.9, 26.3, 19.8, 15.9, 13.5, 11.9, 10.7, 9.8, 8.9],   // 83s
+    [40.0, 25.9, 19.4, 15.7, 13.3, 11.7, 10.4, 9.5, 8.7],   // 82s
+    [58.6, 39.2, 28.8, 22.3, 18.3, 15.4, 13.0, 11.3, 9.9],  // A7o
+    [55.3, 36.0, 26.3, 20.3, 16.5, 13.9, 11.8, 10.3, 8.9],  // K7o
+    [51.9, 33.1, 23.9, 18.4, 14.9, 12.5, 10.5, 9.1, 8.0],   // Q7o
+    [49.6, 31.8, 23.5, 18.3, 14.9, 12.4, 10.7, 9.3, 8.1],   // J7o
+    [47.8, 31.1, 23.2, 18.3, 15.0, 12.7, 10.9, 9.5, 8.6],   // T7o
+    [46.1, 30.9, 23.0, 18.4, 15.2, 12.7, 11.1, 9.8, 8.8],   // 97o
+    [45.1, 30.5, 22.9, 18.5, 15.2, 13.0, 11.4, 10.3, 9.2],  // 87o
+    [66.4, 46.6, 34.5, 26.7, 21.7, 18.7, 16.4, 14.8, 13.6], // 77
+    [45.3, 31.8, 25.2, 20.7, 17.8, 15.7, 14.3, 13.4, 12.3], // 76s
+    [43.7, 30.0, 23.3, 19.5, 16.7, 14.9, 13.5, 12.3, 11.5], // 75s
+    [41.9, 28.2, 21.7, 17.8, 15.3, 13.5, 12.3, 11.1, 10.4], // 74s
+    [39.8, 26.5, 20.2, 16.3, 13.9, 12.3, 11.1, 10.3, 9.5],  // 73s
+    [38.0, 24.5, 18.4, 14.9, 12.8, 11.2, 10.1, 9.3, 8.5],   // 72s
+    [57.8, 37.9, 27.5, 21.4, 17.4, 14.7, 12.5, 10.8, 9.6],  // A6o
+    [54.2, 35.0, 25.1, 19.5, 15.8, 13.2, 11.4, 9.8, 8.4],   // K6o
+    [51.0, 32.3, 23.1, 18.1, 14.4, 12.1, 10.3, 8.7, 7.6],   // Q6o
+    [47.9, 29.9, 21.4, 16.4, 13.3, 11.0, 9.4, 8.0, 7.1],    // J6o
+    [46.2, 29.1, 21.3, 16.7, 13.2, 11.1, 9.6, 8.3, 7.4],    // T6o
+    [44.5, 28.7, 21.0, 16.5, 13.6, 11.3, 9.8, 8.6, 7.7],    // 96o
+    [43.2, 28.4, 21.1, 16.8, 13.9, 11.8, 10.2, 9.2, 8.3],   // 86o
+    [42.3, 28.5, 21.5, 16.9, 14.2, 12.0, 10.6, 9.7, 8.7],   // 76o
+    [63.4, 43.1, 31.5, 24.6, 20.0, 17.3, 15.5, 14.0, 13.0], // 66
+    [43.3, 30.1, 23.6, 19.8, 17.0, 15.2, 13.9, 12.7, 11.8], // 65s
+    [41.5, 28.4, 22.2, 18.4, 15.9, 14.3, 13.1, 12.0, 11.1], // 64s
+    [39.6, 26.8, 20.4, 17.0, 14.5, 13.0, 11.8, 10.9, 10.0], // 63s
+    [37.7, 24.7, 18.9, 15.3, 13.3, 11.7, 10.6, 9.8, 9.0],   // 62s
+    [57.8, 38.4, 28.2, 21.9, 18.2, 15.1, 13.1, 11.7, 10.2], // A5o
+    [53.2, 34.1, 24.4, 19.0, 15.2, 12.7, 10.9, 9.3, 8.2],   // K5o
+    [50.1, 31.4, 22.4, 17.2, 14.1, 11.6, 9.6, 8.4, 7.4],    // Q5o
+    [47.3, 29.2, 20.8, 16.0, 12.8, 10.5, 9.0, 7.7, 6.7],    // J5o
+    [44.4, 27.0, 19.3, 14.7, 12.0, 9.9, 8.4, 7.2, 6.3],     // T5o
+    [42.8, 26.6, 19.1, 14.7, 12.0, 9.9, 8.5, 7.4, 6.6],     // 95o
+    [41.4, 26.3, 19.4, 15.1, 12.1, 10.4, 9.0, 7.8, 7.2],    // 85o
+    [40.5, 26.4, 19.7, 15.6, 12.9, 10.9, 9.8, 8.7, 7.9],    // 75o
+    [40.0, 26.7, 20.1, 15.8, 13.3, 11.4, 10.2, 9.1, 8.5],   // 65o
+    [60.4, 40.0, 28.9, 22.5, 18.4, 16.2, 14.5, 13.3, 12.4], // 55
+    [41.4, 29.0, 22.7, 18.9, 16.5, 14.9, 13.6, 12.5, 11.8], // 54s
+    [39.5, 27.4, 21.1, 17.6, 15.5, 13.7, 12.6, 11.7, 11.0], // 53s
+    [38.0, 25.4, 19.5, 16.2, 13.9, 12.6, 11.3, 10.6, 9.8],  // 52s
+    [56.7, 37.3, 27.0, 21.2, 17.5, 14.8, 12.7, 11.2, 9.9],  // A4o
+    [52.4, 33.0, 23.5, 18.1, 14.7, 12.3, 10.4, 9.1, 8.1],   // K4o
+    [49.2, 30.3, 21.7, 16.4, 13.5, 11.0, 9.4, 8.1, 7.2],    // Q4o
+    [46.2, 28.4, 19.9, 15.3, 12.2, 10.1, 8.6, 7.5, 6.5],    // J4o
+    [43.4, 26.5, 18.7, 14.3, 11.6, 9.5, 8.0, 7.0, 6.1],     // T4o
+    [40.6, 24.4, 17.3, 13.1, 10.4, 8.5, 7.3, 6.4, 5.6],     // 94o
+    [39.6, 24.4, 17.6, 13.5, 10.8, 9.0, 7.9, 6.9, 6.1],     // 84o
+    [38.5, 24.5, 17.8, 13.7, 11.5, 9.7, 8.5, 7.5, 6.9],     // 74o
+    [38.0, 24.8, 18.4, 14.5, 12.1, 10.3, 9.1, 8.3, 7.5],    // 64o
+    [38.3, 25.5, 18.8, 15.1, 12.5, 11.0, 9.9, 8.9, 8.2],    // 54o
+    [57.2, 36.5, 26.3, 20.6, 17.1, 15.4, 13.8, 12.7, 12.2], // 44
+    [38.6, 26.6, 20.5, 17.1, 14.6, 13.2, 11.9, 11.2, 10.3], // 43s
+    [37.0, 24.8, 18.9, 15.5, 13.6, 12.1, 11.3, 10.3, 9.5],  // 42s
+    [55.8, 36.2, 26.5, 20.7, 16.9, 14.3, 12.4, 10.8, 9.6],  // A3o
+    [51.3, 31.9, 23.0, 17.6, 14.3, 12.1, 10.2, 8.9, 7.9],   // K3o
+    [48.2, 29.6, 20.9, 16.0, 12.8, 10.6, 9.1, 7.9, 6.8],    // Q3o
+    [45.2, 27.5, 19.2, 14.6, 11.8, 9.9, 8.3, 7.3, 6.3],     // J3o
+    [42.4, 25.5, 18.0, 13.7, 11.1, 9.1, 7.8, 6.8, 5.9],     // T3o
+    [39.9, 24.0, 16.8, 12.7, 10.0, 8.3, 7.0, 6.0, 5.4],     // 93o
+    [37.5, 22.3, 15.6, 11.8, 9.4, 7.9, 6.6, 5.8, 5.1],      // 83o
+    [36.6, 22.3, 16.0, 12.3, 10.0, 8.3, 7.2, 6.4, 5.8],     // 73o
+    [36.2, 22.7, 16.4, 12.9, 10.5, 9.0, 8.0, 7.1, 6.5],     // 63o
+    [36.3, 23.5, 17.0, 13.5, 11.4, 10.0, 8.9, 8.1, 7.4],    // 53o
+    [35.1, 22.6, 16.4, 13.0, 10.7, 9.4, 8.3, 7.5, 6.9],     // 43o
+    [53.7, 33.7, 24.1, 19.0, 16.2, 14.6, 13.6, 12.8, 12.0], // 33
+    [35.9, 23.9, 18.1, 14.9, 13.0, 11.7, 10.7, 9.9, 9.3],   // 32s
+    [54.9, 35.2, 25.5, 19.9, 16.1, 13.8, 11.8, 10.4, 9.2],  // A2o
+    [50.7, 31.3, 22.1, 17.0, 13.6, 11.7, 10.0, 8.7, 7.7],   // K2o
+    [47.3, 28.6, 20.0, 15.5, 12.4, 10.5, 9.0, 7.7, 6.7],    // Q2o
+    [44.5, 26.5, 18.5, 14.2, 11.4, 9.5, 8.1, 7.1, 6.2],     // J2o
+    [41.6, 24.6, 17.4, 13.3, 10.7, 8.8, 7.5, 6.6, 5.9],     // T2o
+    [39.1, 23.1, 16.0, 12.2, 9.8, 8.1, 6.8, 5.9, 5.2],      // 92o
+    [37.0, 21.9, 15.2, 11.4, 9.1, 7.5, 6.5, 5.6, 4.9],      // 82o
+    [34.5, 20.5, 14.3, 10.7, 8.6, 7.2, 6.2, 5.4, 4.8],      // 72o
+    [34.0, 20.7, 14.7, 11.2, 9.2, 7.8, 6.8, 6.0, 5.4],      // 62o
+    [34.2, 21.4, 15.4, 12.2, 10.0, 8.6, 7.7, 6.8, 6.3],     // 52o
+    [33.3, 20.7, 14.8, 11.6, 9.4, 8.2, 7.3, 6.6, 6.0],      // 42o
+    [32.3, 19.8, 13.8, 10.9, 8.9, 7.7, 6.8, 6.2, 5.6],      // 32o
+    [50.4, 30.7, 22.1, 17.8, 15.5, 14.1, 13.2, 12.4, 11.8], // 22
+];
@@ -0,0 +1,287 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::card_set::CardSet;
+use super::shared::*;
+
+/// 带权重的一手具体底牌
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeightedCombo {
+    pub cards: (Card, Card), // 点数大的在前
+    pub weight: f64,         // 0 到 1 之间
+}
+
+impl WeightedCombo {
+    pub fn card_set(&self) -> CardSet {
+        CardSet::from_cards(&[self.cards.0, self.cards.1])
+    }
+}
+
+/// 手牌范围，由若干带权重的具体底牌组成
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Range {
+    pub combos: Vec<WeightedCombo>,
+}
+
+/// 范围解析错误
+#[derive(Error, Debug, PartialEq)]
+pub enum RangeParseError {
+    #[error("Invalid range token: {0}")]
+    InvalidToken(String),
+    #[error("Invalid weight in range token: {0}")]
+    InvalidWeight(String),
+}
+
+/// 按点数和同花/非同花描述的一类起手牌，如 AKs、QJo、TT、AK
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HandClass {
+    pub(crate) high: Rank,
+    pub(crate) low: Rank,
+    pub(crate) suited: Option<bool>, // None 表示同花和非同花都包括
+}
+
+impl Range {
+    /// 空范围
+    pub fn new() -> Self {
+        Range::default()
+    }
+
+    /// 只包含一手具体底牌的范围
+    pub fn from_combo(cards: (Card, Card)) -> Self {
+        let mut range = Range::new();
+        range.add(cards, 1.0);
+        range
+    }
+
+    /// 解析范围表达式，如 "TT+, AKs, AQo-AJo, 76s-54s, 22-55, AhKh, KQs:0.5"
+    ///
+    /// 各项以逗号或空白分隔，`:权重` 后缀为该项所有组合设置权重；同一组合以最后出现的为准。
+    pub fn parse(notation: &str) -> Result<Self, RangeParseError> {
+        let mut range = Range::new();
+        for token in notation
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+        {
+            let (hands, weight) = match token.split_once(':') {
+                Some((hands, weight)) => {
+                    let weight: f64 = weight
+                        .parse()
+                        .map_err(|_| RangeParseError::InvalidWeight(token.to_string()))?;
+                    if !(0.0..=1.0).contains(&weight) {
+                        return Err(RangeParseError::InvalidWeight(token.to_string()));
+                    }
+                    (hands, weight)
+                }
+                None => (token, 1.0),
+            };
+
+            let invalid = || RangeParseError::InvalidToken(token.to_string());
+            if let Some(cards) = parse_combo(hands) {
+                range.add(cards, weight);
+                continue;
+            }
+            for class in expand_token(hands).ok_or_else(invalid)? {
+                for cards in class.combos() {
+                    range.add(cards, weight);
+                }
+            }
+        }
+        Ok(range)
+    }
+
+    /// 加入一手底牌，已存在时更新权重
+    pub fn add(&mut self, cards: (Card, Card), weight: f64) {
+        let cards = ordered(cards);
+        match self.combos.iter_mut().find(|c| c.cards == cards) {
+            Some(combo) => combo.weight = weight,
+            None => self.combos.push(WeightedCombo { cards, weight }),
+        }
+    }
+
+    /// 去掉与公共牌、死牌冲突的组合以及权重为 0 的组合
+    pub fn remove_blocked(&mut self, blocked: CardSet) {
+        self.combos
+            .retain(|c| c.weight > 0.0 && c.card_set().is_disjoint(blocked));
+    }
+
+    /// 组合数量
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// 加权后的组合数
+    pub fn weighted_len(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Range::parse(s)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, combo) in self.combos.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            let (c1, c2) = combo.cards;
+            write!(f, "{}{}", c1.code(), c2.code())?;
+            if combo.weight < 1.0 {
+                write!(f, ":{}", combo.weight)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl HandClass {
+    /// 解析 "AKs"、"AKo"、"AK"、"TT"
+    pub(crate) fn parse(s: &str) -> Option<HandClass> {
+        let chars: Vec<char> = s.chars().collect();
+        let (first, second) = (
+            Rank::from_char(*chars.first()?)?,
+            Rank::from_char(*chars.get(1)?)?,
+        );
+        let suited = match chars.get(2) {
+            None => None,
+            Some('s' | 'S') if chars.len() == 3 => Some(true),
+            Some('o' | 'O') if chars.len() == 3 => Some(false),
+            _ => return None,
+        };
+        // 对子不能带同花/非同花后缀
+        if first == second && suited.is_some() {
+            return None;
+        }
+        Some(HandClass {
+            high: first.max(second),
+            low: first.min(second),
+            suited,
+        })
+    }
+
+    pub(crate) fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /// 这一类起手牌的所有具体组合
+    pub(crate) fn combos(&self) -> Vec<(Card, Card)> {
+        let mut combos = Vec::new();
+        for (i, &suit1) in Suit::ALL.iter().enumerate() {
+            for (j, &suit2) in Suit::ALL.iter().enumerate() {
+                let keep = if self.is_pair() {
+                    i < j
+                } else {
+                    match self.suited {
+                        Some(true) => i == j,
+                        Some(false) => i != j,
+                        None => true,
+                    }
+                };
+                if keep {
+                    combos.push((
+                        Card {
+                            suit: suit1,
+                            rank: self.high,
+                        },
+                        Card {
+                            suit: suit2,
+                            rank: self.low,
+                        },
+                    ));
+                }
+            }
+        }
+        combos
+    }
+}
+
+/// 把 "TT+"、"A2s+"、"22-55"、"AQo-AJo"、"76s-54s" 或单个类展开为若干类
+fn expand_token(token: &str) -> Option<Vec<HandClass>> {
+    let with_high_low = |class: HandClass, high: u8, low: u8| HandClass {
+        high: Rank::from_value(high),
+        low: Rank::from_value(low),
+        ..class
+    };
+
+    if let Some(base) = token.strip_suffix('+') {
+        let class = HandClass::parse(base)?;
+        let (high, low) = (class.high.value(), class.low.value());
+        return Some(if class.is_pair() {
+            // TT+：TT 到 AA
+            (low..=14).map(|v| with_high_low(class, v, v)).collect()
+        } else {
+            // A2s+：踢脚从 2 到 K
+            (low..high).map(|v| with_high_low(class, high, v)).collect()
+        });
+    }
+
+    if let Some((from, to)) = token.split_once('-') {
+        let (a, b) = (HandClass::parse(from)?, HandClass::parse(to)?);
+        if a.suited != b.suited || a.is_pair() != b.is_pair() {
+            return None;
+        }
+        let (top, bottom) = if a.high > b.high || (a.high == b.high && a.low >= b.low) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let (high, low) = (top.high.value(), top.low.value());
+
+        if top.is_pair() {
+            // 22-55：两端之间的所有对子
+            return Some(
+                (bottom.low.value()..=low)
+                    .map(|v| with_high_low(top, v, v))
+                    .collect(),
+            );
+        }
+        if top.high == bottom.high {
+            // AQo-AJo：高牌相同，踢脚在两端之间
+            return Some(
+                (bottom.low.value()..=low)
+                    .map(|v| with_high_low(top, high, v))
+                    .collect(),
+            );
+        }
+        if high - low == bottom.high.value() - bottom.low.value() {
+            // 76s-54s：间隔相同，两张牌一起下降
+            return Some(
+                (0..=high - bottom.high.value())
+                    .map(|d| with_high_low(top, high - d, low - d))
+                    .collect(),
+            );
+        }
+        return None;
+    }
+
+    HandClass::parse(token).map(|class| vec![class])
+}
+
+/// 解析具体的两张牌，如 "AhKh"
+fn parse_combo(s: &str) -> Option<(Card, Card)> {
+    match parse_cards(s).ok()?.as_slice() {
+        [c1, c2] => Some((*c1, *c2)),
+        _ => None,
+    }
+}
+
+/// 点数大的牌在前，点数相同时按花色顺序
+fn ordered((c1, c2): (Card, Card)) -> (Card, Card) {
+    if (c1.rank, c1.suit.index()) >= (c2.rank, c2.suit.index()) {
+        (c1, c2)
+    } else {
+        (c2, c1)
+    }
+}
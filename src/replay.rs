@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::history::*;
+use super::rules::create_seeded_deck;
+use super::shared::*;
+use super::state::TexasHoldem;
+
+/// 牌堆的来源
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeckSetup {
+    Seed(u64),        // 用固定种子洗牌
+    Order(Vec<Card>), // 按发牌顺序排列的牌，未列出的牌按固定顺序接在后面
+}
+
+/// 一手牌开始前的全部设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandSetup {
+    pub players: Vec<SeatInfo>,
+    pub config: GameConfig,
+    pub dealer_position: usize,
+    pub deck: DeckSetup,
+}
+
+/// 记录下来的一次玩家行动
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAction {
+    pub player_id: String,
+    pub action: PlayerAction,
+}
+
+impl DeckSetup {
+    /// 由已知的底牌（按座位顺序）和公共牌排出发牌顺序，未知的底牌用其余的牌补齐
+    pub fn from_known(
+        hole_cards: &[Option<(Card, Card)>],
+        board: &[Card],
+    ) -> Result<DeckSetup, GameError> {
+        let mut known: Vec<Card> = board.to_vec();
+        for (card1, card2) in hole_cards.iter().flatten() {
+            known.extend([*card1, *card2]);
+        }
+        let known_set = CardSet::from_cards(&known);
+        if known_set.len() != known.len() {
+            return Err(GameError::InvalidCards);
+        }
+        let mut unknown = CardSet::full().difference(known_set).iter();
+
+        let mut order = Vec::new();
+        for cards in hole_cards {
+            match cards {
+                Some((card1, card2)) => order.extend([*card1, *card2]),
+                None => order.extend(unknown.by_ref().take(2)),
+            }
+        }
+        order.extend(board);
+        Ok(DeckSetup::Order(order))
+    }
+}
+
+impl HandSetup {
+    /// 按设置创建这手牌，尚未执行任何行动
+    pub fn start(&self) -> Result<TexasHoldem, GameError> {
+        let players = self
+            .players
+            .iter()
+            .map(|seat| Player {
+                id: seat.id.clone(),
+                name: seat.name.clone(),
+                chips: seat.chips,
+                cards: None,
+                is_active: true,
+                current_bet: 0,
+                has_acted: false,
+                total_bet_in_hand: 0,
+            })
+            .collect();
+        TexasHoldem::try_with_deck(
+            players,
+            &self.config,
+            self.dealer_position,
+            self.build_deck()?,
+        )
+    }
+
+    /// 生成引擎使用的牌堆（从末尾发牌）
+    fn build_deck(&self) -> Result<Vec<Card>, GameError> {
+        match &self.deck {
+            DeckSetup::Seed(seed) => Ok(create_seeded_deck(*seed)),
+            DeckSetup::Order(cards) => {
+                let listed = CardSet::from_cards(cards);
+                if listed.len() != cards.len() {
+                    return Err(GameError::InvalidCards);
+                }
+                let mut deck: Vec<Card> = cards.clone();
+                deck.extend(CardSet::full().difference(listed).iter());
+                deck.reverse();
+                Ok(deck)
+            }
+        }
+    }
+
+    /// 从一手牌的事件记录还原设置，牌堆顺序由发出的底牌和公共牌推出
+    pub fn from_history(events: &[HandEvent]) -> Option<HandSetup> {
+        let Some(HandEvent::HandStarted {
+            dealer_position,
+            players,
+            small_blind,
+            big_blind,
+            ante,
+            variant,
+            betting,
+            bomb_pot,
+            boards,
+        }) = events.first()
+        else {
+            return None;
+        };
+
+        // 发牌顺序：每人两张底牌，再每人一张待弃的底牌，然后是公共牌
+        let hole_cards: Vec<&Vec<Card>> = events
+            .iter()
+            .filter_map(|e| match e {
+                HandEvent::HoleCardsDealt { cards, .. } => Some(cards),
+                _ => None,
+            })
+            .collect();
+        let mut order: Vec<Card> = hole_cards
+            .iter()
+            .flat_map(|c| c.iter().take(2))
+            .copied()
+            .collect();
+        order.extend(hole_cards.iter().flat_map(|c| c.iter().skip(2)));
+        for event in events {
+            if let HandEvent::BoardDealt { cards, .. } = event {
+                order.extend(cards);
+            }
+        }
+
+        Some(HandSetup {
+            players: players.clone(),
+            config: GameConfig {
+                variant: *variant,
+                betting: *betting,
+                small_blind: *small_blind,
+                big_blind: *big_blind,
+                ante: *ante,
+                bomb_pot: *bomb_pot,
+                double_board: *boards > 1,
+            },
+            dealer_position: *dealer_position,
+            deck: DeckSetup::Order(order),
+        })
+    }
+}
+
+/// 一手牌中玩家的行动列表
+pub fn recorded_actions(events: &[HandEvent]) -> Vec<RecordedAction> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::ActionTaken {
+                player_id, action, ..
+            } => Some(RecordedAction {
+                player_id: player_id.clone(),
+                action: action.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 按设置和行动列表重现一手牌，可以前进、后退到任意一步
+pub struct Replay {
+    pub setup: HandSetup,
+    pub actions: Vec<RecordedAction>,
+    game: TexasHoldem,
+    position: usize, // 已执行的行动数
+}
+
+impl Replay {
+    /// 创建重放，停在第一个行动之前
+    pub fn new(setup: HandSetup, actions: Vec<RecordedAction>) -> Result<Self, GameError> {
+        let game = setup.start()?;
+        Ok(Replay {
+            setup,
+            actions,
+            game,
+            position: 0,
+        })
+    }
+
+    /// 从一手牌的事件记录创建重放
+    pub fn from_history(events: &[HandEvent]) -> Result<Self, GameError> {
+        let setup = HandSetup::from_history(events).ok_or(GameError::InvalidAction)?;
+        Self::new(setup, recorded_actions(events))
+    }
+
+    /// 当前这一步的牌局
+    pub fn game(&self) -> &TexasHoldem {
+        &self.game
+    }
+
+    /// 已执行的行动数
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position == self.actions.len()
+    }
+
+    /// 执行下一个行动，已到末尾时返回 false
+    pub fn step_forward(&mut self) -> Result<bool, GameError> {
+        let Some(recorded) = self.actions.get(self.position) else {
+            return Ok(false);
+        };
+        self.game
+            .handle_action_for(&recorded.player_id, recorded.action.clone())?;
+        self.position += 1;
+        Ok(true)
+    }
+
+    /// 退回上一步，已在开头时返回 false
+    pub fn step_back(&mut self) -> Result<bool, GameError> {
+        if self.position == 0 {
+            return Ok(false);
+        }
+        self.seek(self.position - 1)?;
+        Ok(true)
+    }
+
+    /// 跳到执行完前 position 个行动的状态
+    pub fn seek(&mut self, position: usize) -> Result<(), GameError> {
+        if position > self.actions.len() {
+            return Err(GameError::InvalidAction);
+        }
+        // 引擎状态不可逆，从头重新执行
+        if position < self.position {
+            self.game = self.setup.start()?;
+            self.position = 0;
+        }
+        while self.position < position {
+            self.step_forward()?;
+        }
+        Ok(())
+    }
+}
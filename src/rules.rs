@@ -1,43 +1,32 @@
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
+use super::card_set::CardSet;
+use super::evaluator::evaluate_strength;
 use super::shared::*;
 use itertools::Itertools;
-use rand::rng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{SeedableRng, rng};
 
 /// 创建一副洗好的牌
 pub fn create_shuffled_deck() -> Vec<Card> {
-    let mut deck = Vec::new();
-
-    let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
-    let ranks = [
-        Rank::Two,
-        Rank::Three,
-        Rank::Four,
-        Rank::Five,
-        Rank::Six,
-        Rank::Seven,
-        Rank::Eight,
-        Rank::Nine,
-        Rank::Ten,
-        Rank::Jack,
-        Rank::Queen,
-        Rank::King,
-        Rank::Ace,
-    ];
-
-    for &suit in &suits {
-        for &rank in &ranks {
-            deck.push(Card { suit, rank });
-        }
-    }
+    let mut deck = CardSet::full().to_vec();
 
     let mut rng = rng();
     deck.shuffle(&mut rng);
     deck
 }
 
+/// 用固定种子洗牌，相同种子得到相同顺序
+pub fn create_seeded_deck(seed: u64) -> Vec<Card> {
+    let mut deck = CardSet::full().to_vec();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    deck.shuffle(&mut rng);
+    deck
+}
+
 /// 评估玩家手牌强度
 pub fn evaluate_hand(player_cards: &(Card, Card), community_cards: &[Card]) -> HandEvaluation {
     // 合并所有牌
@@ -45,10 +34,34 @@ pub fn evaluate_hand(player_cards: &(Card, Card), community_cards: &[Card]) -> H
     all_cards.extend_from_slice(community_cards);
 
     // 找出最佳5张牌组合
-    let best_hand = find_best_five_card_hand(&all_cards);
+    let mut best_hand = find_best_five_card_hand(&all_cards);
 
     // 评估最佳5张牌组合
-    evaluate_five_cards(&best_hand)
+    let (rank, kickers) = evaluate_five_cards(&best_hand);
+    sort_hand_cards(&mut best_hand, rank);
+
+    HandEvaluation {
+        rank,
+        kickers,
+        cards: best_hand,
+    }
+}
+
+/// 按展示顺序排列牌型中的牌：多张同点的在前，点数大的在前
+fn sort_hand_cards(cards: &mut [Card], rank: HandRank) {
+    let count = |r: Rank| cards.iter().filter(|c| c.rank == r).count();
+    let mut keyed: Vec<_> = cards.iter().map(|c| (count(c.rank), *c)).collect();
+    keyed.sort_by(|(n1, c1), (n2, c2)| n2.cmp(n1).then(c2.rank.cmp(&c1.rank)));
+
+    // A-2-3-4-5 顺子中 A 当作 1，放在最后
+    let is_straight = matches!(rank, HandRank::Straight | HandRank::StraightFlush);
+    if is_straight && keyed[0].1.rank == Rank::Ace && keyed[1].1.rank == Rank::Five {
+        keyed.rotate_left(1);
+    }
+
+    for (slot, (_, card)) in cards.iter_mut().zip(keyed) {
+        *slot = card;
+    }
 }
 
 /// 从所有可用牌中找出最佳的5张牌组合
@@ -58,30 +71,17 @@ fn find_best_five_card_hand(cards: &[Card]) -> Vec<Card> {
         return cards.to_vec();
     }
 
-    // 生成所有可能的5张牌组合
-    let mut best_hand = vec![cards[0], cards[1], cards[2], cards[3], cards[4]];
-    let mut best_evaluation = evaluate_five_cards(&best_hand);
-
-    // 遍历所有5张牌组合
-    for hand in cards.iter().combinations(5) {
-        let hand_cloned = hand.into_iter().copied().collect::<Vec<_>>();
-        let evaluation = evaluate_five_cards(&hand_cloned);
-
-        // 如果找到更好的牌型，更新最佳组合
-        if evaluation.rank > best_evaluation.rank
-            || (evaluation.rank == best_evaluation.rank
-                && compare_kickers(&evaluation.kickers, &best_evaluation.kickers)
-                    == Ordering::Greater)
-        {
-            best_evaluation = evaluation;
-            best_hand = hand_cloned;
-        }
-    }
-    best_hand
+    // 用牌力值比较所有5张牌组合，避免逐个完整评估
+    cards
+        .iter()
+        .copied()
+        .combinations(5)
+        .max_by_key(|hand| evaluate_strength(hand))
+        .unwrap()
 }
 
-/// 评估5张牌的牌型
-fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
+/// 评估5张牌的牌型，返回牌型和关键牌序列
+fn evaluate_five_cards(cards: &[Card]) -> (HandRank, Vec<Rank>) {
     assert!(cards.len() == 5, "只能评估5张牌");
 
     // 按点数分组
@@ -104,18 +104,12 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
 
     // 检查皇家同花顺
     if is_flush && is_straight && straight_high == Rank::Ace {
-        return HandEvaluation {
-            rank: HandRank::RoyalFlush,
-            kickers: vec![Rank::Ace],
-        };
+        return (HandRank::RoyalFlush, vec![Rank::Ace]);
     }
 
     // 检查同花顺
     if is_flush && is_straight {
-        return HandEvaluation {
-            rank: HandRank::StraightFlush,
-            kickers: vec![straight_high],
-        };
+        return (HandRank::StraightFlush, vec![straight_high]);
     }
 
     // 检查四条
@@ -131,13 +125,11 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
             .max()
             .unwrap();
 
-        return HandEvaluation {
-            rank: HandRank::FourOfAKind,
-            kickers: vec![quad_rank, kicker],
-        };
+        return (HandRank::FourOfAKind, vec![quad_rank, kicker]);
     }
 
     // 检查葫芦（三条+对子）
+    #[allow(clippy::collapsible_if)]
     if let Some(three_rank) = rank_counts
         .iter()
         .find(|&(_, &count)| count == 3)
@@ -149,10 +141,7 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
             .find(|&(_, &count)| count >= 2)
             .map(|(r, _)| *r)
         {
-            return HandEvaluation {
-                rank: HandRank::FullHouse,
-                kickers: vec![three_rank, pair_rank],
-            };
+            return (HandRank::FullHouse, vec![three_rank, pair_rank]);
         }
     }
 
@@ -160,18 +149,12 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
     if is_flush {
         let mut kickers: Vec<Rank> = cards.iter().map(|c| c.rank).collect();
         kickers.sort_by(|a, b| b.cmp(a)); // 降序排序
-        return HandEvaluation {
-            rank: HandRank::Flush,
-            kickers,
-        };
+        return (HandRank::Flush, kickers);
     }
 
     // 检查顺子
     if is_straight {
-        return HandEvaluation {
-            rank: HandRank::Straight,
-            kickers: vec![straight_high],
-        };
+        return (HandRank::Straight, vec![straight_high]);
     }
 
     // 检查三条
@@ -189,10 +172,10 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
         kickers.sort_by(|a, b| b.cmp(a)); // 降序排序
         kickers.truncate(2); // 只保留最大的两个踢脚牌
 
-        return HandEvaluation {
-            rank: HandRank::ThreeOfAKind,
-            kickers: std::iter::once(three_rank).chain(kickers).collect(),
-        };
+        return (
+            HandRank::ThreeOfAKind,
+            std::iter::once(three_rank).chain(kickers).collect(),
+        );
     }
 
     // 检查两对
@@ -215,10 +198,7 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
             .max()
             .unwrap();
 
-        return HandEvaluation {
-            rank: HandRank::TwoPair,
-            kickers: vec![high_pair, low_pair, kicker],
-        };
+        return (HandRank::TwoPair, vec![high_pair, low_pair, kicker]);
     }
 
     // 检查一对
@@ -236,19 +216,16 @@ fn evaluate_five_cards(cards: &[Card]) -> HandEvaluation {
         kickers.sort_by(|a, b| b.cmp(a)); // 降序排序
         kickers.truncate(3); // 只保留最大的三个踢脚牌
 
-        return HandEvaluation {
-            rank: HandRank::OnePair,
-            kickers: std::iter::once(pair_rank).chain(kickers).collect(),
-        };
+        return (
+            HandRank::OnePair,
+            std::iter::once(pair_rank).chain(kickers).collect(),
+        );
     }
 
     // 高牌
     let mut kickers: Vec<Rank> = cards.iter().map(|c| c.rank).collect();
     kickers.sort_by(|a, b| b.cmp(a)); // 降序排序
-    HandEvaluation {
-        rank: HandRank::HighCard,
-        kickers,
-    }
+    (HandRank::HighCard, kickers)
 }
 
 /// 检查是否为顺子并返回最大牌
@@ -302,13 +279,21 @@ pub fn compare_hands(
     hand2: &(Card, Card),
     community_cards: &[Card],
 ) -> Ordering {
-    let eval1 = evaluate_hand(hand1, community_cards);
-    let eval2 = evaluate_hand(hand2, community_cards);
-
-    match eval1.rank.cmp(&eval2.rank) {
-        Ordering::Equal => compare_kickers(&eval1.kickers, &eval2.kickers),
-        other => other,
+    // 5 到 7 张牌走位掩码评估，其他张数按完整评估比较
+    if !(3..=5).contains(&community_cards.len()) {
+        let eval1 = evaluate_hand(hand1, community_cards);
+        let eval2 = evaluate_hand(hand2, community_cards);
+        return eval1.cmp(&eval2);
     }
+
+    let strength = |hand: &(Card, Card)| {
+        let mut cards = [hand.0; 7];
+        cards[1] = hand.1;
+        cards[2..2 + community_cards.len()].copy_from_slice(community_cards);
+        evaluate_strength(&cards[..2 + community_cards.len()])
+    };
+
+    strength(hand1).cmp(&strength(hand2))
 }
 
 /// 确定赢家
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use super::shared::*;
+use super::state::TexasHoldem;
+
+/// 玩法轮换规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rotation {
+    Hands(u32),    // 每 N 手牌换下一种玩法
+    Orbits(u32),   // 每 N 圈（每人当一次庄为一圈）换下一种玩法
+    DealersChoice, // 由庄家选择本手玩法
+}
+
+/// 混合玩法牌局：在多手牌之间轮换玩法，筹码和座位跨手保留
+pub struct Session {
+    pub players: Vec<Player>,
+    pub games: Vec<GameConfig>,
+    pub rotation: Rotation,
+    pub dealer_position: usize, // 庄家在 players 中的位置
+    pub hands_played: u32,
+    pub hand: Option<TexasHoldem>, // 进行中的一手牌
+    current_game: usize,
+    hands_in_game: u32, // 当前玩法已进行的手数
+}
+
+impl Session {
+    /// 创建混合玩法牌局
+    pub fn new(
+        players: Vec<Player>,
+        games: Vec<GameConfig>,
+        rotation: Rotation,
+    ) -> Result<Self, GameError> {
+        if games.is_empty() || players.len() < 2 {
+            return Err(GameError::InvalidAction);
+        }
+
+        Ok(Session {
+            players,
+            games,
+            rotation,
+            dealer_position: 0,
+            hands_played: 0,
+            hand: None,
+            current_game: 0,
+            hands_in_game: 0,
+        })
+    }
+
+    /// 当前（或下一手）使用的玩法配置
+    pub fn current_game(&self) -> &GameConfig {
+        &self.games[self.current_game]
+    }
+
+    /// 庄家选择下一手的玩法，仅限 DealersChoice 且两手牌之间
+    pub fn choose_game(&mut self, player_id: &str, game_index: usize) -> Result<(), GameError> {
+        if self.hand.is_some() {
+            return Err(GameError::StageError);
+        }
+
+        let player_index = self
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+
+        if self.rotation != Rotation::DealersChoice
+            || player_index != self.dealer_position
+            || game_index >= self.games.len()
+        {
+            return Err(GameError::InvalidAction);
+        }
+
+        self.current_game = game_index;
+        Ok(())
+    }
+
+    /// 用当前玩法开始新的一手牌，只有有筹码的玩家入座
+    pub fn start_hand(&mut self) -> Result<&mut TexasHoldem, GameError> {
+        if self.hand.is_some() {
+            return Err(GameError::StageError);
+        }
+
+        let seated: Vec<Player> = self
+            .players
+            .iter()
+            .filter(|p| p.chips > 0)
+            .cloned()
+            .collect();
+        if seated.len() < 2 {
+            return Err(GameError::InsufficientChips);
+        }
+
+        // 庄家在入座玩家中的位置
+        let dealer_id = &self.players[self.dealer_position].id;
+        let dealer = seated.iter().position(|p| &p.id == dealer_id).unwrap_or(0);
+
+        let config = self.games[self.current_game].clone();
+        Ok(self
+            .hand
+            .insert(TexasHoldem::with_config(seated, &config, dealer)))
+    }
+
+    /// 结束当前一手牌：结算筹码、移动庄家并按规则轮换玩法
+    pub fn finish_hand(&mut self) -> Result<(), GameError> {
+        match &self.hand {
+            Some(hand) if hand.state.stage == GameStage::Showdown => {}
+            _ => return Err(GameError::StageError),
+        }
+
+        let mut hand = self.hand.take().unwrap();
+        hand.resolve_showdown();
+
+        // 带回筹码
+        for seated in hand.state.players {
+            if let Some(player) = self.players.iter_mut().find(|p| p.id == seated.id) {
+                player.chips = seated.chips;
+            }
+        }
+
+        self.hands_played += 1;
+        self.hands_in_game += 1;
+        self.advance_dealer();
+        self.rotate_game();
+        Ok(())
+    }
+
+    /// 庄家移到下一位有筹码的玩家
+    fn advance_dealer(&mut self) {
+        let count = self.players.len();
+        if let Some(next) = (1..=count)
+            .map(|offset| (self.dealer_position + offset) % count)
+            .find(|&i| self.players[i].chips > 0)
+        {
+            self.dealer_position = next;
+        }
+    }
+
+    /// 按轮换规则切换到下一种玩法
+    fn rotate_game(&mut self) {
+        let hands_per_game = match self.rotation {
+            Rotation::Hands(n) => n,
+            Rotation::Orbits(n) => n * self.players.iter().filter(|p| p.chips > 0).count() as u32,
+            Rotation::DealersChoice => return,
+        };
+
+        if self.hands_in_game >= hands_per_game {
+            self.current_game = (self.current_game + 1) % self.games.len();
+            self.hands_in_game = 0;
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 
 /// 扑克牌花色
@@ -11,6 +12,42 @@ pub enum Suit {
     Spades,   // 黑桃
 }
 
+impl Suit {
+    /// 所有花色
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+    /// 花色下标（0..4）
+    pub fn index(&self) -> usize {
+        match self {
+            Suit::Hearts => 0,
+            Suit::Diamonds => 1,
+            Suit::Clubs => 2,
+            Suit::Spades => 3,
+        }
+    }
+
+    /// 由花色字符解析，支持 h/d/c/s（不区分大小写）和 ♥♦♣♠
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c {
+            'h' | 'H' | '♥' => Some(Suit::Hearts),
+            'd' | 'D' | '♦' => Some(Suit::Diamonds),
+            'c' | 'C' | '♣' => Some(Suit::Clubs),
+            's' | 'S' | '♠' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    /// 花色的 ASCII 字符（h/d/c/s）
+    pub fn to_char(&self) -> char {
+        match self {
+            Suit::Hearts => 'h',
+            Suit::Diamonds => 'd',
+            Suit::Clubs => 'c',
+            Suit::Spades => 's',
+        }
+    }
+}
+
 impl fmt::Display for Suit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -41,6 +78,23 @@ pub enum Rank {
 }
 
 impl Rank {
+    /// 所有点数，从小到大
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
     pub fn value(&self) -> u8 {
         match self {
             Rank::Two => 2,
@@ -59,22 +113,98 @@ impl Rank {
         }
     }
 
+    /// 由点数值创建，值无效时 panic，不确定输入时用 `try_from_value`
     pub fn from_value(value: u8) -> Self {
+        Rank::try_from_value(value).unwrap_or_else(|| panic!("Invalid value for Rank: {}", value))
+    }
+
+    /// 由点数值创建，值无效时返回 None
+    pub fn try_from_value(value: u8) -> Option<Self> {
         match value {
-            2 => Rank::Two,
-            3 => Rank::Three,
-            4 => Rank::Four,
-            5 => Rank::Five,
-            6 => Rank::Six,
-            7 => Rank::Seven,
-            8 => Rank::Eight,
-            9 => Rank::Nine,
-            10 => Rank::Ten,
-            11 => Rank::Jack,
-            12 => Rank::Queen,
-            13 => Rank::King,
-            14 | 1 => Rank::Ace, // Ace 表示 14 或 1
-            _ => panic!("Invalid value for Rank: {}", value),
+            2 => Some(Rank::Two),
+            3 => Some(Rank::Three),
+            4 => Some(Rank::Four),
+            5 => Some(Rank::Five),
+            6 => Some(Rank::Six),
+            7 => Some(Rank::Seven),
+            8 => Some(Rank::Eight),
+            9 => Some(Rank::Nine),
+            10 => Some(Rank::Ten),
+            11 => Some(Rank::Jack),
+            12 => Some(Rank::Queen),
+            13 => Some(Rank::King),
+            14 | 1 => Some(Rank::Ace), // Ace 表示 14 或 1
+            _ => None,
+        }
+    }
+
+    /// 由点数字符解析，支持 2-9、T/J/Q/K/A（不区分大小写）
+    pub fn from_char(c: char) -> Option<Rank> {
+        match c.to_ascii_uppercase() {
+            '2' => Some(Rank::Two),
+            '3' => Some(Rank::Three),
+            '4' => Some(Rank::Four),
+            '5' => Some(Rank::Five),
+            '6' => Some(Rank::Six),
+            '7' => Some(Rank::Seven),
+            '8' => Some(Rank::Eight),
+            '9' => Some(Rank::Nine),
+            'T' => Some(Rank::Ten),
+            'J' => Some(Rank::Jack),
+            'Q' => Some(Rank::Queen),
+            'K' => Some(Rank::King),
+            'A' => Some(Rank::Ace),
+            _ => None,
+        }
+    }
+
+    /// 点数的单字符表示（10 记为 T）
+    pub fn to_char(&self) -> char {
+        match self {
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+            _ => (b'0' + self.value()) as char,
+        }
+    }
+
+    /// 点数的英文名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        }
+    }
+
+    /// 点数英文名称的复数形式
+    pub fn plural_name(&self) -> &'static str {
+        match self {
+            Rank::Two => "Twos",
+            Rank::Three => "Threes",
+            Rank::Four => "Fours",
+            Rank::Five => "Fives",
+            Rank::Six => "Sixes",
+            Rank::Seven => "Sevens",
+            Rank::Eight => "Eights",
+            Rank::Nine => "Nines",
+            Rank::Ten => "Tens",
+            Rank::Jack => "Jacks",
+            Rank::Queen => "Queens",
+            Rank::King => "Kings",
+            Rank::Ace => "Aces",
         }
     }
 }
@@ -106,20 +236,106 @@ pub struct Card {
     pub rank: Rank,
 }
 
+impl Card {
+    /// 牌的 ASCII 表示，如 "Ah"、"Td"
+    pub fn code(&self) -> String {
+        format!("{}{}", self.rank.to_char(), self.suit.to_char())
+    }
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}{}", self.rank, self.suit)
     }
 }
 
+impl FromStr for Suit {
+    type Err = CardParseError;
+
+    /// 解析 "h"、"H" 或 "♥"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Suit::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| CardParseError::InvalidSuit(s.to_string()))
+    }
+}
+
+impl FromStr for Rank {
+    type Err = CardParseError;
+
+    /// 解析 "A"、"t"、"T" 或 `Display` 输出的 "10"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "10" {
+            return Ok(Rank::Ten);
+        }
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Rank::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| CardParseError::InvalidRank(s.to_string()))
+    }
+}
+
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    /// 解析 "Ah"、"Td"、"10♠" 这样的单张牌，点数和花色之外不能有其他字符
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CardParseError::InvalidCard(s.to_string());
+        let (suit_start, _) = s.char_indices().last().ok_or_else(invalid)?;
+        let (rank, suit) = s.split_at(suit_start);
+        Ok(Card {
+            suit: suit.parse().map_err(|_| invalid())?,
+            rank: rank.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// 解析一串牌，如 "AhKd Ts9s2c" 或 "A♥, K♦ 10♠"
+///
+/// 牌之间可以用空白或逗号分隔，也可以连写；分隔符不能出现在一张牌内部，
+/// 如 "A h" 是错误。同一张牌出现两次也视为错误。
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, CardParseError> {
+    let mut cards = Vec::new();
+    for token in s.split(|c: char| c.is_whitespace() || c == ',') {
+        let mut chars = token.chars().peekable();
+        while let Some(c) = chars.next() {
+            let rank = if c == '1' && chars.peek() == Some(&'0') {
+                chars.next();
+                Rank::Ten
+            } else {
+                Rank::from_char(c).ok_or_else(|| CardParseError::InvalidRank(c.to_string()))?
+            };
+            let suit = match chars.next() {
+                Some(c) => {
+                    Suit::from_char(c).ok_or_else(|| CardParseError::InvalidSuit(c.to_string()))?
+                }
+                None => return Err(CardParseError::InvalidCard(token.to_string())),
+            };
+
+            let card = Card { suit, rank };
+            if cards.contains(&card) {
+                return Err(CardParseError::DuplicateCard(card));
+            }
+            cards.push(card);
+        }
+    }
+    Ok(cards)
+}
+
 /// 玩家行动
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PlayerAction {
-    Fold,       // 弃牌
-    Check,      // 过牌
-    Bet(u32),   // 下注
-    Raise(u32), // 加注
-    Call,       // 跟注
+    Fold,          // 弃牌
+    Check,         // 过牌
+    Bet(u32),      // 下注
+    Raise(u32),    // 加注
+    Call,          // 跟注
+    Discard(Card), // 弃掉一张底牌（大菠萝玩法）
 }
 
 /// 游戏阶段
@@ -132,6 +348,61 @@ pub enum GameStage {
     Showdown, // 摊牌
 }
 
+/// 牌局玩法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameVariant {
+    #[default]
+    Holdem, // 德州扑克
+    Pineapple,      // 大菠萝：发三张底牌，翻牌前弃一张
+    CrazyPineapple, // 疯狂大菠萝：发三张底牌，翻牌圈后弃一张
+}
+
+impl GameVariant {
+    /// 每位玩家发到的底牌数
+    pub fn hole_card_count(&self) -> usize {
+        match self {
+            GameVariant::Holdem => 2,
+            GameVariant::Pineapple | GameVariant::CrazyPineapple => 3,
+        }
+    }
+
+    /// 需要弃牌的阶段（该阶段下注结束后弃牌）
+    pub fn discard_stage(&self) -> Option<GameStage> {
+        match self {
+            GameVariant::Holdem => None,
+            GameVariant::Pineapple => Some(GameStage::PreFlop),
+            GameVariant::CrazyPineapple => Some(GameStage::Flop),
+        }
+    }
+}
+
+/// 下注结构
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BettingStructure {
+    #[default]
+    NoLimit, // 无限注
+    PotLimit,   // 底池限注：加注后的总下注额不超过跟注后的底池
+    FixedLimit, // 固定限注：翻牌前和翻牌圈每次下注一个大盲，转牌和河牌两个大盲
+}
+
+/// 固定限注每轮最多的下注次数（一次下注加三次加注，翻牌前大盲算作第一次下注）
+pub const FIXED_LIMIT_BET_CAP: u32 = 4;
+
+/// 单种玩法的配置（玩法、下注结构、盲注与前注）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub variant: GameVariant,
+    #[serde(default)]
+    pub betting: BettingStructure,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32, // 每位玩家的前注
+    #[serde(default)]
+    pub bomb_pot: bool, // 炸弹底池：所有人下前注后直接从翻牌开始
+    #[serde(default)]
+    pub double_board: bool, // 发两块公共牌面，每个底池由两块牌面的赢家平分
+}
+
 /// 玩家信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
@@ -146,7 +417,7 @@ pub struct Player {
 }
 
 /// 牌型
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HandRank {
     HighCard = 0,
     OnePair = 1,
@@ -161,10 +432,67 @@ pub enum HandRank {
 }
 
 /// 牌型评估结果
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// 比较时只看牌型和关键牌，`cards` 不参与比较。
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HandEvaluation {
     pub rank: HandRank,
     pub kickers: Vec<Rank>, // 用于比较的关键牌序列
+    pub cards: Vec<Card>,   // 组成牌型的最佳5张牌
+}
+
+impl HandEvaluation {
+    /// 牌型的文字描述，牌型名称首字母大写，如 "Full House, Kings full of Fives"、
+    /// "Ace-high Flush"、"One Pair, Queens"
+    pub fn description(&self) -> String {
+        let kicker = |i: usize| self.kickers.get(i).copied().unwrap_or(Rank::Two);
+        match self.rank {
+            HandRank::RoyalFlush => "Royal Flush".to_string(),
+            HandRank::StraightFlush => format!("{}-high Straight Flush", kicker(0).name()),
+            HandRank::FourOfAKind => format!("Four of a Kind, {}", kicker(0).plural_name()),
+            HandRank::FullHouse => format!(
+                "Full House, {} full of {}",
+                kicker(0).plural_name(),
+                kicker(1).plural_name()
+            ),
+            HandRank::Flush => format!("{}-high Flush", kicker(0).name()),
+            HandRank::Straight => format!("{}-high Straight", kicker(0).name()),
+            HandRank::ThreeOfAKind => format!("Three of a Kind, {}", kicker(0).plural_name()),
+            HandRank::TwoPair => format!(
+                "Two Pair, {} and {}",
+                kicker(0).plural_name(),
+                kicker(1).plural_name()
+            ),
+            HandRank::OnePair => format!("One Pair, {}", kicker(0).plural_name()),
+            HandRank::HighCard => format!("High Card, {}", kicker(0).name()),
+        }
+    }
+}
+
+impl fmt::Display for HandEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl PartialEq for HandEvaluation {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank && self.kickers == other.kickers
+    }
+}
+
+impl Eq for HandEvaluation {}
+
+impl PartialOrd for HandEvaluation {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HandEvaluation {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.rank, &self.kickers).cmp(&(other.rank, &other.kickers))
+    }
 }
 
 /// 游戏状态
@@ -178,6 +506,20 @@ pub struct GameState {
     pub dealer_position: usize, // 庄家位置
     pub small_blind: u32,
     pub big_blind: u32,
+    #[serde(default)]
+    pub ante: u32,
+    #[serde(default)]
+    pub variant: GameVariant,
+    #[serde(default)]
+    pub extra_hole_cards: Vec<Option<Card>>, // 大菠萝玩法中尚未弃掉的第三张底牌
+    #[serde(default)]
+    pub bomb_pot: bool, // 炸弹底池，跳过翻牌前
+    #[serde(default)]
+    pub extra_boards: Vec<Vec<Card>>, // 第一块牌面之外的公共牌
+    #[serde(default)]
+    pub betting: BettingStructure,
+    #[serde(default)]
+    pub bets_this_round: u32, // 本轮的下注和加注次数，用于固定限注封顶
 }
 
 /// 错误类型
@@ -191,4 +533,21 @@ pub enum GameError {
     StageError,
     #[error("Player not found")]
     PlayerNotFound,
+    #[error("Not this player's turn")]
+    NotYourTurn,
+    #[error("Invalid or duplicate cards")]
+    InvalidCards,
+}
+
+/// 牌的解析错误
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CardParseError {
+    #[error("Invalid rank: {0}")]
+    InvalidRank(String),
+    #[error("Invalid suit: {0}")]
+    InvalidSuit(String),
+    #[error("Invalid card: {0}")]
+    InvalidCard(String),
+    #[error("Duplicate card: {0}")]
+    DuplicateCard(Card),
 }
@@ -1,10 +1,13 @@
+use super::history::*;
 use super::rules::*;
 use super::shared::*;
 
 /// 德州扑克游戏状态机
+#[derive(Debug, Clone)]
 pub struct TexasHoldem {
     pub state: GameState,
     pub deck: Vec<Card>,
+    pub history: Vec<HandEvent>, // 所有手牌的事件记录，每手牌以 HandStarted 开头
 }
 
 /// Side pot 表示一个筹码池（主池或边池）
@@ -17,6 +20,49 @@ struct SidePot {
 impl TexasHoldem {
     /// 创建新游戏
     pub fn new(players: Vec<Player>, small_blind: u32, big_blind: u32) -> Self {
+        Self::with_variant(players, small_blind, big_blind, GameVariant::Holdem)
+    }
+
+    /// 创建指定玩法的新游戏
+    pub fn with_variant(
+        players: Vec<Player>,
+        small_blind: u32,
+        big_blind: u32,
+        variant: GameVariant,
+    ) -> Self {
+        let config = GameConfig {
+            variant,
+            small_blind,
+            big_blind,
+            ..Default::default()
+        };
+        Self::with_config(players, &config, 0)
+    }
+
+    /// 按玩法配置和庄家位置创建新游戏
+    pub fn with_config(players: Vec<Player>, config: &GameConfig, dealer_position: usize) -> Self {
+        Self::with_deck(players, config, dealer_position, create_shuffled_deck())
+    }
+
+    /// 使用指定牌堆创建新游戏，牌从牌堆末尾开始发；牌堆不够炸弹底池发翻牌时 panic，
+    /// 需要处理这种情况时用 `try_with_deck`
+    pub fn with_deck(
+        players: Vec<Player>,
+        config: &GameConfig,
+        dealer_position: usize,
+        deck: Vec<Card>,
+    ) -> Self {
+        Self::try_with_deck(players, config, dealer_position, deck)
+            .expect("牌堆不够发出炸弹底池的翻牌")
+    }
+
+    /// 使用指定牌堆创建新游戏，开局需要发的公共牌不够时返回 `StageError`
+    pub fn try_with_deck(
+        players: Vec<Player>,
+        config: &GameConfig,
+        dealer_position: usize,
+        deck: Vec<Card>,
+    ) -> Result<Self, GameError> {
         let mut game = TexasHoldem {
             state: GameState {
                 players,
@@ -24,23 +70,40 @@ impl TexasHoldem {
                 pot: 0,
                 current_player_index: 0,
                 stage: GameStage::PreFlop,
-                dealer_position: 0,
-                small_blind,
-                big_blind,
+                dealer_position,
+                small_blind: config.small_blind,
+                big_blind: config.big_blind,
+                ante: config.ante,
+                variant: config.variant,
+                extra_hole_cards: Vec::new(),
+                bomb_pot: config.bomb_pot,
+                extra_boards: if config.double_board {
+                    vec![Vec::new()]
+                } else {
+                    Vec::new()
+                },
+                betting: config.betting,
+                bets_this_round: 0,
             },
-            deck: create_shuffled_deck(),
+            deck,
+            history: Vec::new(),
         };
 
-        game.setup_new_hand();
-        game
+        game.setup_new_hand()?;
+        Ok(game)
     }
 
     /// 设置新的一局
-    fn setup_new_hand(&mut self) {
+    fn setup_new_hand(&mut self) -> Result<(), GameError> {
         // 重置状态
         self.state.community_cards.clear();
+        for board in &mut self.state.extra_boards {
+            board.clear();
+        }
         self.state.pot = 0;
         self.state.stage = GameStage::PreFlop;
+        self.state.extra_hole_cards = vec![None; self.state.players.len()];
+        self.state.bets_this_round = 0;
 
         // 重置玩家状态
         for player in &mut self.state.players {
@@ -51,6 +114,27 @@ impl TexasHoldem {
             player.total_bet_in_hand = 0;
         }
 
+        self.history.push(HandEvent::HandStarted {
+            dealer_position: self.state.dealer_position,
+            players: self
+                .state
+                .players
+                .iter()
+                .map(|p| SeatInfo {
+                    id: p.id.clone(),
+                    name: p.name.clone(),
+                    chips: p.chips,
+                })
+                .collect(),
+            small_blind: self.state.small_blind,
+            big_blind: self.state.big_blind,
+            ante: self.state.ante,
+            variant: self.state.variant,
+            betting: self.state.betting,
+            bomb_pot: self.state.bomb_pot,
+            boards: 1 + self.state.extra_boards.len(),
+        });
+
         // 发牌
         self.deal_cards();
 
@@ -58,8 +142,41 @@ impl TexasHoldem {
         self.state.current_player_index =
             (self.state.dealer_position + 3) % self.state.players.len();
 
-        // 下盲注
+        // 炸弹底池：只下前注，没有翻牌前下注；需要在翻牌前弃牌的玩法先弃牌再发翻牌
+        if self.state.bomb_pot {
+            self.post_antes();
+            for player in &mut self.state.players {
+                player.has_acted = true;
+            }
+            if self.awaiting_discard() {
+                self.state.current_player_index = self.next_pending_discard().unwrap();
+                return Ok(());
+            }
+            return self.advance_street();
+        }
+
+        // 下前注和盲注
+        self.post_antes();
         self.post_blinds();
+        Ok(())
+    }
+
+    /// 下前注（不计入本轮下注额）
+    fn post_antes(&mut self) {
+        if self.state.ante == 0 {
+            return;
+        }
+
+        for player in &mut self.state.players {
+            let amount = player.chips.min(self.state.ante);
+            player.chips -= amount;
+            player.total_bet_in_hand += amount;
+            self.state.pot += amount;
+            self.history.push(HandEvent::AntePosted {
+                player_id: player.id.clone(),
+                amount,
+            });
+        }
     }
 
     /// 下盲注
@@ -73,6 +190,11 @@ impl TexasHoldem {
             player.current_bet = amount;
             player.total_bet_in_hand += amount;
             self.state.pot += amount;
+            self.history.push(HandEvent::BlindPosted {
+                player_id: player.id.clone(),
+                blind: Blind::Small,
+                amount,
+            });
         }
 
         if let Some(player) = self.state.players.get_mut(big_blind_pos) {
@@ -81,7 +203,13 @@ impl TexasHoldem {
             player.current_bet = amount;
             player.total_bet_in_hand += amount;
             self.state.pot += amount;
+            self.history.push(HandEvent::BlindPosted {
+                player_id: player.id.clone(),
+                blind: Blind::Big,
+                amount,
+            });
         }
+        self.state.bets_this_round = 1;
     }
 
     /// 发牌给玩家
@@ -93,22 +221,66 @@ impl TexasHoldem {
                 player.cards = Some((card1, card2));
             }
         }
+
+        // 大菠萝玩法再发一张待弃的底牌
+        if self.state.variant.hole_card_count() > 2 {
+            for (i, player) in self.state.players.iter().enumerate() {
+                if player.cards.is_some() {
+                    self.state.extra_hole_cards[i] = self.deck.pop();
+                }
+            }
+        }
+
+        for (i, player) in self.state.players.iter().enumerate() {
+            if let Some((card1, card2)) = player.cards {
+                let mut cards = vec![card1, card2];
+                cards.extend(self.state.extra_hole_cards.get(i).copied().flatten());
+                self.history.push(HandEvent::HoleCardsDealt {
+                    player_id: player.id.clone(),
+                    cards,
+                });
+            }
+        }
+    }
+
+    /// 以指定玩家的身份行动，不是该玩家的回合时返回 `NotYourTurn`
+    pub fn handle_action_for(
+        &mut self,
+        player_id: &str,
+        action: PlayerAction,
+    ) -> Result<(), GameError> {
+        let index = self
+            .state
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        if index != self.state.current_player_index || self.state.stage == GameStage::Showdown {
+            return Err(GameError::NotYourTurn);
+        }
+        self.handle_action(action)
     }
 
-    /// 处理玩家行动
+    /// 处理当前玩家的行动
     pub fn handle_action(&mut self, action: PlayerAction) -> Result<(), GameError> {
-        // 提前计算当前轮次的下注额
-        let current_bet_round = self.current_bet_round();
-        let player_index = self.state.current_player_index;
+        if let PlayerAction::Discard(card) = action {
+            return self.handle_discard(card);
+        }
 
-        // 对于需要重置has_acted的动作，提前调用
-        match &action {
-            PlayerAction::Bet(_) | PlayerAction::Raise(_) => {
-                self.reset_has_acted();
-            }
-            _ => {}
+        // 等待弃牌时不能继续下注
+        if self.awaiting_discard() {
+            return Err(GameError::InvalidAction);
         }
 
+        // 提前计算当前轮次的下注额
+        let current_bet_round = self.state.current_bet_round();
+        let player_index = self.state.current_player_index;
+        let raise_range = self.state.raise_range(player_index);
+        // 全下时可以少于下注结构的最小下注或加注额，但不能超过上限
+        let within_structure = |amount: u32, all_in: bool| {
+            raise_range.is_some_and(|(min, max)| amount <= max && (amount >= min || all_in))
+        };
+
         let player = self
             .state
             .players
@@ -119,7 +291,9 @@ impl TexasHoldem {
             return Err(GameError::InvalidAction);
         }
 
-        match action {
+        let player_id = player.id.clone();
+        let pot_before = self.state.pot;
+        match action.clone() {
             PlayerAction::Fold => {
                 player.is_active = false;
                 player.has_acted = true;
@@ -139,11 +313,14 @@ impl TexasHoldem {
                 if amount > player.chips {
                     return Err(GameError::InsufficientChips);
                 }
+                if !within_structure(amount, amount == player.chips) {
+                    return Err(GameError::InvalidAction);
+                }
                 player.chips -= amount;
                 player.current_bet += amount;
                 player.total_bet_in_hand += amount;
                 self.state.pot += amount;
-                player.has_acted = true;
+                self.reopen_action(player_index);
                 self.advance_to_next_player();
             }
             PlayerAction::Raise(amount) => {
@@ -155,24 +332,25 @@ impl TexasHoldem {
                 if total_needed > player.chips + player.current_bet {
                     return Err(GameError::InsufficientChips);
                 }
+                if !within_structure(amount, total_needed == player.chips + player.current_bet) {
+                    return Err(GameError::InvalidAction);
+                }
 
                 let chips_to_put = total_needed - player.current_bet;
                 player.chips -= chips_to_put;
                 player.current_bet += chips_to_put;
                 player.total_bet_in_hand += chips_to_put;
                 self.state.pot += chips_to_put;
-                player.has_acted = true;
+                self.reopen_action(player_index);
                 self.advance_to_next_player();
             }
             PlayerAction::Call => {
-                let amount_to_call = current_bet_round - player.current_bet;
-                if amount_to_call == 0 {
+                if current_bet_round <= player.current_bet {
                     return Err(GameError::InvalidAction); // 无需跟注
                 }
 
-                if amount_to_call > player.chips {
-                    return Err(GameError::InsufficientChips);
-                }
+                // 筹码不足时全下跟注
+                let amount_to_call = (current_bet_round - player.current_bet).min(player.chips);
 
                 player.chips -= amount_to_call;
                 player.current_bet += amount_to_call;
@@ -181,20 +359,92 @@ impl TexasHoldem {
                 player.has_acted = true;
                 self.advance_to_next_player();
             }
+            PlayerAction::Discard(_) => unreachable!(),
         }
 
+        if matches!(action, PlayerAction::Bet(_) | PlayerAction::Raise(_)) {
+            self.state.bets_this_round += 1;
+        }
+        self.history.push(HandEvent::ActionTaken {
+            player_id,
+            action,
+            amount: self.state.pot - pot_before,
+        });
         self.check_round_completion()?;
         Ok(())
     }
 
-    /// 获取当前轮次的下注额（本轮最高下注额）
-    fn current_bet_round(&self) -> u32 {
-        self.state
+    /// 当前这手牌的事件记录
+    pub fn current_hand(&self) -> &[HandEvent] {
+        split_hands(&self.history).last().copied().unwrap_or(&[])
+    }
+
+    /// 是否正在等待玩家弃牌（大菠萝玩法中弃牌阶段的下注已结束）
+    pub fn awaiting_discard(&self) -> bool {
+        self.state.variant.discard_stage() == Some(self.state.stage)
+            && self.next_pending_discard().is_some()
+            && self.state.betting_round_complete()
+    }
+
+    /// 处理当前玩家的弃牌
+    fn handle_discard(&mut self, card: Card) -> Result<(), GameError> {
+        if !self.awaiting_discard() {
+            return Err(GameError::InvalidAction);
+        }
+
+        let player_index = self.state.current_player_index;
+        let extra = self
+            .state
+            .extra_hole_cards
+            .get(player_index)
+            .copied()
+            .flatten()
+            .ok_or(GameError::InvalidAction)?;
+        let player = self
+            .state
             .players
-            .iter()
-            .map(|p| p.current_bet)
-            .max()
-            .unwrap_or(0)
+            .get_mut(player_index)
+            .ok_or(GameError::PlayerNotFound)?;
+        let (card1, card2) = player.cards.ok_or(GameError::InvalidAction)?;
+
+        // 弃掉的牌必须是手中三张之一，剩下的两张作为底牌
+        player.cards = if card == extra {
+            Some((card1, card2))
+        } else if card == card1 {
+            Some((extra, card2))
+        } else if card == card2 {
+            Some((card1, extra))
+        } else {
+            return Err(GameError::InvalidAction);
+        };
+        self.state.extra_hole_cards[player_index] = None;
+        self.history.push(HandEvent::ActionTaken {
+            player_id: player.id.clone(),
+            action: PlayerAction::Discard(card),
+            amount: 0,
+        });
+
+        match self.next_pending_discard() {
+            Some(next_index) => self.state.current_player_index = next_index,
+            None => self.advance_street()?,
+        }
+
+        Ok(())
+    }
+
+    /// 从庄家下家开始，找到下一位需要弃牌的玩家
+    fn next_pending_discard(&self) -> Option<usize> {
+        let count = self.state.players.len();
+        (1..=count)
+            .map(|offset| (self.state.dealer_position + offset) % count)
+            .find(|&i| {
+                self.state.players[i].is_active
+                    && self
+                        .state
+                        .extra_hole_cards
+                        .get(i)
+                        .is_some_and(|c| c.is_some())
+            })
     }
 
     /// 推进到下一位玩家
@@ -220,36 +470,59 @@ impl TexasHoldem {
 
     /// 检查当前阶段是否完成
     fn check_round_completion(&mut self) -> Result<(), GameError> {
-        let active_players: Vec<_> = self.state.players.iter().filter(|p| p.is_active).collect();
+        let active_count = self.state.players.iter().filter(|p| p.is_active).count();
 
-        if active_players.len() <= 1 {
-            self.state.stage = GameStage::Showdown;
+        if active_count <= 1 {
+            if self.state.stage != GameStage::Showdown {
+                self.state.stage = GameStage::Showdown;
+                self.history.push(HandEvent::StreetStarted {
+                    stage: GameStage::Showdown,
+                });
+            }
             return Ok(());
         }
 
-        let current_bet_round = self.current_bet_round();
-
-        // 检查所有活跃玩家是否已完成本轮下注
-        let all_acted = active_players.iter().all(|p| {
-            // 玩家已行动或没有筹码
-            p.has_acted || p.chips == 0
-        });
+        if self.state.betting_round_complete() {
+            // 本轮下注结束后，若有玩家需要弃牌则先进入弃牌
+            if self.awaiting_discard() {
+                self.state.current_player_index = self.next_pending_discard().unwrap();
+            } else {
+                self.advance_street()?;
+            }
+        }
 
-        // 检查所有玩家是否跟注或全下
-        let all_called = active_players
-            .iter()
-            .all(|p| p.current_bet == current_bet_round || p.chips == 0);
+        Ok(())
+    }
 
-        if all_acted && all_called {
+    /// 本轮下注结束后推进到下一阶段；最多一名玩家还有筹码时不会再有下注，
+    /// 直接发完公共牌进入摊牌（需要弃牌的玩法先停下等待弃牌）
+    fn advance_street(&mut self) -> Result<(), GameError> {
+        self.advance_to_next_stage()?;
+        while self.state.stage != GameStage::Showdown
+            && self
+                .state
+                .players
+                .iter()
+                .filter(|p| p.is_active && p.chips > 0)
+                .count()
+                <= 1
+        {
+            for player in &mut self.state.players {
+                player.has_acted = true;
+            }
+            if self.awaiting_discard() {
+                self.state.current_player_index = self.next_pending_discard().unwrap();
+                return Ok(());
+            }
             self.advance_to_next_stage()?;
         }
-
         Ok(())
     }
 
-    fn reset_has_acted(&mut self) {
-        for player in &mut self.state.players {
-            player.has_acted = false;
+    /// 下注或加注后其他玩家需要重新行动
+    fn reopen_action(&mut self, bettor: usize) {
+        for (i, player) in self.state.players.iter_mut().enumerate() {
+            player.has_acted = i == bettor;
         }
     }
 
@@ -260,32 +533,22 @@ impl TexasHoldem {
             player.current_bet = 0;
             player.has_acted = false;
         }
+        self.state.bets_this_round = 0;
 
         match self.state.stage {
             GameStage::PreFlop => {
                 // 发三张公共牌（翻牌）
-                if self.deck.len() < 3 {
-                    return Err(GameError::StageError);
-                }
-                for _ in 0..3 {
-                    self.state.community_cards.push(self.deck.pop().unwrap());
-                }
+                self.deal_community(3)?;
                 self.state.stage = GameStage::Flop;
             }
             GameStage::Flop => {
                 // 发一张公共牌（转牌）
-                if self.deck.is_empty() {
-                    return Err(GameError::StageError);
-                }
-                self.state.community_cards.push(self.deck.pop().unwrap());
+                self.deal_community(1)?;
                 self.state.stage = GameStage::Turn;
             }
             GameStage::Turn => {
                 // 发一张公共牌（河牌）
-                if self.deck.is_empty() {
-                    return Err(GameError::StageError);
-                }
-                self.state.community_cards.push(self.deck.pop().unwrap());
+                self.deal_community(1)?;
                 self.state.stage = GameStage::River;
             }
             GameStage::River => {
@@ -294,19 +557,55 @@ impl TexasHoldem {
             GameStage::Showdown => {
                 self.resolve_showdown();
                 // 游戏结束，准备新一局
-                self.setup_new_hand();
+                self.setup_new_hand()?;
             }
         }
+        if self.state.stage != GameStage::PreFlop {
+            self.history.push(HandEvent::StreetStarted {
+                stage: self.state.stage,
+            });
+        }
 
         // 设置行动玩家
-        self.state.current_player_index =
-            (self.state.dealer_position + 1) % self.state.players.len();
-        self.advance_to_next_player(); // 找到第一个有效玩家
+        self.start_postflop_round();
+
+        Ok(())
+    }
+
+    /// 翻牌后从庄家下家开始找第一个有效玩家行动；
+    /// 单挑时庄家下的是大盲，由庄家先行动
+    fn start_postflop_round(&mut self) {
+        let count = self.state.players.len();
+        self.state.current_player_index = if count == 2 {
+            (self.state.dealer_position + 1) % count
+        } else {
+            self.state.dealer_position
+        };
+        self.advance_to_next_player();
+    }
+
+    /// 给每块牌面各发 count 张公共牌
+    fn deal_community(&mut self, count: usize) -> Result<(), GameError> {
+        let board_count = 1 + self.state.extra_boards.len();
+        if self.deck.len() < count * board_count {
+            return Err(GameError::StageError);
+        }
 
+        let boards = std::iter::once(&mut self.state.community_cards)
+            .chain(&mut self.state.extra_boards)
+            .enumerate();
+        for (index, board) in boards {
+            let cards: Vec<Card> = (0..count).map(|_| self.deck.pop().unwrap()).collect();
+            board.extend_from_slice(&cards);
+            self.history.push(HandEvent::BoardDealt {
+                board: index,
+                cards,
+            });
+        }
         Ok(())
     }
 
-    /// 计算边池
+    /// 计算边池，依次为主池和各个边池
     fn compute_side_pots(&self) -> Vec<SidePot> {
         // 收集所有玩家的总下注额
         let mut bets: Vec<_> = self
@@ -320,7 +619,7 @@ impl TexasHoldem {
         // 按总下注额排序
         bets.sort_by_key(|(_, bet)| *bet);
 
-        let mut pots = Vec::new();
+        let mut pots: Vec<SidePot> = Vec::new();
         let mut last_bet = 0;
 
         for &(_, bet) in &bets {
@@ -338,10 +637,29 @@ impl TexasHoldem {
                 // 当前层边池的金额
                 let amount = increment * eligible_players.len() as u32;
 
-                pots.push(SidePot {
-                    amount,
-                    eligible_players,
-                });
+                // 争夺者（未弃牌的合格玩家）与上一层相同时并入上一层，已弃牌玩家的投入不单独成池；
+                // 只有一人投入的一层是没人跟的下注，单独留出来退还
+                let contenders = |players: &[usize]| -> Vec<usize> {
+                    players
+                        .iter()
+                        .copied()
+                        .filter(|&i| self.state.players[i].is_active)
+                        .collect()
+                };
+                match pots.last_mut() {
+                    Some(last)
+                        if eligible_players.len() > 1
+                            && !contenders(&eligible_players).is_empty()
+                            && contenders(&last.eligible_players)
+                                == contenders(&eligible_players) =>
+                    {
+                        last.amount += amount;
+                    }
+                    _ => pots.push(SidePot {
+                        amount,
+                        eligible_players,
+                    }),
+                }
 
                 last_bet = bet;
             }
@@ -350,77 +668,206 @@ impl TexasHoldem {
         pots
     }
 
-    /// 在 Showdown 阶段结算赢家，分配筹码
+    /// 在 Showdown 阶段结算赢家，分配筹码；底池分完后清零，重复调用不会再次分配
     pub fn resolve_showdown(&mut self) {
-        let side_pots = self.compute_side_pots();
-        let evaluations = self.evaluate_all_hands();
-        let mut winnings = vec![0; self.state.players.len()];
-
-        // 处理每个边池
-        for pot in side_pots {
-            let mut best_eval: Option<&HandEvaluation> = None;
-            let mut winners = Vec::new();
-
-            // 找出此边池的最佳手牌
-            for &player_index in &pot.eligible_players {
-                if let Some(eval) = &evaluations[player_index] {
-                    match best_eval {
-                        None => {
+        if self.state.pot == 0 {
+            return;
+        }
+
+        // 其他玩家都已弃牌时，剩下的玩家直接赢得底池
+        let mut active = self.state.players.iter_mut().filter(|p| p.is_active);
+        if let (Some(winner), None) = (active.next(), active.next()) {
+            winner.chips += self.state.pot;
+            self.history.push(HandEvent::PotAwarded {
+                player_id: winner.id.clone(),
+                amount: self.state.pot,
+                pot: 0,
+            });
+            self.state.pot = 0;
+            return;
+        }
+
+        // 只有一人投入的一层是没人跟的下注，直接退还
+        let (returned, side_pots): (Vec<_>, Vec<_>) = self
+            .compute_side_pots()
+            .into_iter()
+            .partition(|pot| pot.eligible_players.len() == 1);
+        for pot in returned {
+            let player = &mut self.state.players[pot.eligible_players[0]];
+            player.chips += pot.amount;
+            self.history.push(HandEvent::BetReturned {
+                player_id: player.id.clone(),
+                amount: pot.amount,
+            });
+        }
+
+        let board_evaluations: Vec<_> = std::iter::once(&self.state.community_cards)
+            .chain(&self.state.extra_boards)
+            .map(|board| self.evaluate_all_hands(board))
+            .collect();
+        for (i, player) in self.state.players.iter().enumerate() {
+            if let (Some(cards), true) = (player.cards, player.is_active) {
+                self.history.push(HandEvent::HandShown {
+                    player_id: player.id.clone(),
+                    cards,
+                    hands: board_evaluations
+                        .iter()
+                        .filter_map(|evaluations| evaluations[i].clone())
+                        .collect(),
+                });
+            }
+        }
+
+        // 处理每个边池，多块牌面时按牌面平分，余数归第一块牌面
+        for (pot_index, pot) in side_pots.iter().enumerate() {
+            let mut winnings = vec![0; self.state.players.len()];
+            let board_share = pot.amount / board_evaluations.len() as u32;
+            let remainder = pot.amount - board_share * board_evaluations.len() as u32;
+            for (board_index, evaluations) in board_evaluations.iter().enumerate() {
+                let amount = if board_index == 0 {
+                    board_share + remainder
+                } else {
+                    board_share
+                };
+                self.award_pot(amount, &pot.eligible_players, evaluations, &mut winnings);
+            }
+
+            // 应用筹码分配
+            for (i, amount) in winnings.into_iter().enumerate() {
+                if amount > 0 {
+                    self.state.players[i].chips += amount;
+                    self.history.push(HandEvent::PotAwarded {
+                        player_id: self.state.players[i].id.clone(),
+                        amount,
+                        pot: pot_index,
+                    });
+                }
+            }
+        }
+        self.state.pot = 0;
+    }
+
+    /// 把一份底池分给合格玩家中牌最大的人，平分的余数从庄家左手边起每人一个；
+    /// 合格玩家都已弃牌（如没人跟的下注）时退还给他们
+    fn award_pot(
+        &self,
+        amount: u32,
+        eligible_players: &[usize],
+        evaluations: &[Option<HandEvaluation>],
+        winnings: &mut [u32],
+    ) {
+        let mut best_eval: Option<&HandEvaluation> = None;
+        let mut winners = Vec::new();
+
+        // 找出此边池的最佳手牌
+        for &player_index in eligible_players {
+            if let Some(eval) = &evaluations[player_index] {
+                match best_eval {
+                    None => {
+                        best_eval = Some(eval);
+                        winners = vec![player_index];
+                    }
+                    Some(current_best) => match eval.rank.cmp(&current_best.rank) {
+                        std::cmp::Ordering::Greater => {
                             best_eval = Some(eval);
                             winners = vec![player_index];
                         }
-                        Some(current_best) => match eval.rank.cmp(&current_best.rank) {
-                            std::cmp::Ordering::Greater => {
+                        std::cmp::Ordering::Equal => {
+                            if compare_kickers(&eval.kickers, &current_best.kickers)
+                                == std::cmp::Ordering::Equal
+                            {
+                                winners.push(player_index);
+                            } else if compare_kickers(&eval.kickers, &current_best.kickers)
+                                == std::cmp::Ordering::Greater
+                            {
                                 best_eval = Some(eval);
                                 winners = vec![player_index];
                             }
-                            std::cmp::Ordering::Equal => {
-                                if compare_kickers(&eval.kickers, &current_best.kickers)
-                                    == std::cmp::Ordering::Equal
-                                {
-                                    winners.push(player_index);
-                                } else if compare_kickers(&eval.kickers, &current_best.kickers)
-                                    == std::cmp::Ordering::Greater
-                                {
-                                    best_eval = Some(eval);
-                                    winners = vec![player_index];
-                                }
-                            }
-                            _ => {}
-                        },
-                    }
+                        }
+                        _ => {}
+                    },
                 }
             }
+        }
 
-            // 分配边池筹码
-            if !winners.is_empty() {
-                let share = pot.amount / winners.len() as u32;
-                for &winner in &winners {
-                    winnings[winner] += share;
-                }
-            }
+        if winners.is_empty() {
+            winners = eligible_players.to_vec();
+        }
+        if winners.is_empty() {
+            return;
         }
 
-        // 应用筹码分配
-        for (i, amount) in winnings.into_iter().enumerate() {
-            self.state.players[i].chips += amount;
+        // 分配边池筹码
+        let count = self.state.players.len();
+        let dealer = self.state.dealer_position;
+        winners.sort_by_key(|&i| (i + count - dealer - 1) % count);
+        let share = amount / winners.len() as u32;
+        let remainder = (amount - share * winners.len() as u32) as usize;
+        for (n, &winner) in winners.iter().enumerate() {
+            winnings[winner] += share + u32::from(n < remainder);
         }
     }
 
-    fn evaluate_all_hands(&self) -> Vec<Option<HandEvaluation>> {
+    fn evaluate_all_hands(&self, board: &[Card]) -> Vec<Option<HandEvaluation>> {
         self.state
             .players
             .iter()
-            .map(|p| {
-                if p.is_active && p.cards.is_some() {
-                    Some(evaluate_hand(
-                        &p.cards.unwrap(),
-                        &self.state.community_cards,
-                    ))
-                } else {
-                    None
-                }
+            .map(|p| match p.cards {
+                Some(cards) if p.is_active => Some(evaluate_hand(&cards, board)),
+                _ => None,
             })
             .collect()
     }
 }
+
+impl GameState {
+    /// 获取当前轮次的下注额（本轮最高下注额）
+    pub(crate) fn current_bet_round(&self) -> u32 {
+        self.players
+            .iter()
+            .map(|p| p.current_bet)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 按下注结构，指定玩家在本轮最高下注额之上可以下注或加注的范围（不考虑筹码，
+    /// 筹码不足下限时可以全下）；无限注和底池限注最少加注一个大盲，固定限注封顶后为 None
+    pub(crate) fn raise_range(&self, seat: usize) -> Option<(u32, u32)> {
+        let to_call = self
+            .current_bet_round()
+            .saturating_sub(self.players.get(seat).map_or(0, |p| p.current_bet));
+        match self.betting {
+            BettingStructure::NoLimit => Some((self.big_blind.max(1), u32::MAX)),
+            BettingStructure::PotLimit => Some((self.big_blind.max(1), self.pot + to_call)),
+            BettingStructure::FixedLimit => {
+                if self.bets_this_round >= FIXED_LIMIT_BET_CAP {
+                    return None;
+                }
+                let size = match self.stage {
+                    GameStage::Turn | GameStage::River => self.big_blind * 2,
+                    _ => self.big_blind,
+                };
+                Some((size, size))
+            }
+        }
+    }
+
+    /// 本轮下注是否已完成
+    pub(crate) fn betting_round_complete(&self) -> bool {
+        let active_players: Vec<_> = self.players.iter().filter(|p| p.is_active).collect();
+        let current_bet_round = self.current_bet_round();
+
+        // 检查所有活跃玩家是否已完成本轮下注
+        let all_acted = active_players.iter().all(|p| {
+            // 玩家已行动或没有筹码
+            p.has_acted || p.chips == 0
+        });
+
+        // 检查所有玩家是否跟注或全下
+        let all_called = active_players
+            .iter()
+            .all(|p| p.current_bet == current_bet_round || p.chips == 0);
+
+        all_acted && all_called
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::history::*;
+use super::shared::*;
+
+/// 一位玩家累计的 HUD 统计计数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub hands: u32,
+    pub vpip: u32,                    // 翻牌前主动投入筹码（跟注或加注）的手数
+    pub pfr: u32,                     // 翻牌前加注的手数
+    pub three_bet_opportunities: u32, // 翻牌前面对一次加注时行动的手数
+    pub three_bets: u32,              // 面对一次加注时再加注的手数
+    pub postflop_aggressions: u32,    // 翻牌后下注和加注的次数
+    pub postflop_calls: u32,          // 翻牌后跟注的次数
+    pub saw_flop: u32,
+    pub went_to_showdown: u32,
+    pub won_at_showdown: u32,
+}
+
+/// 用于显示的统计值，百分比为 0 到 100，样本为 0 时为 None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HudStats {
+    pub hands: u32,
+    pub vpip: Option<f64>,
+    pub pfr: Option<f64>,
+    pub three_bet: Option<f64>,
+    pub af: Option<f64>, // 翻牌后（下注 + 加注）/ 跟注，没有跟注时为 None
+    pub wtsd: Option<f64>,
+    pub wsd: Option<f64>,
+}
+
+fn percentage(count: u32, total: u32) -> Option<f64> {
+    (total > 0).then(|| count as f64 * 100.0 / total as f64)
+}
+
+impl PlayerStats {
+    pub fn vpip(&self) -> Option<f64> {
+        percentage(self.vpip, self.hands)
+    }
+
+    pub fn pfr(&self) -> Option<f64> {
+        percentage(self.pfr, self.hands)
+    }
+
+    pub fn three_bet(&self) -> Option<f64> {
+        percentage(self.three_bets, self.three_bet_opportunities)
+    }
+
+    /// 激进度：翻牌后（下注 + 加注）/ 跟注
+    pub fn af(&self) -> Option<f64> {
+        (self.postflop_calls > 0)
+            .then(|| self.postflop_aggressions as f64 / self.postflop_calls as f64)
+    }
+
+    /// 看到翻牌后进入摊牌的比例
+    pub fn wtsd(&self) -> Option<f64> {
+        percentage(self.went_to_showdown, self.saw_flop)
+    }
+
+    /// 摊牌时赢得筹码的比例
+    pub fn wsd(&self) -> Option<f64> {
+        percentage(self.won_at_showdown, self.went_to_showdown)
+    }
+
+    pub fn hud(&self) -> HudStats {
+        HudStats {
+            hands: self.hands,
+            vpip: self.vpip(),
+            pfr: self.pfr(),
+            three_bet: self.three_bet(),
+            af: self.af(),
+            wtsd: self.wtsd(),
+            wsd: self.wsd(),
+        }
+    }
+
+    /// 合并另一份计数，如另一场牌局的统计
+    pub fn merge(&mut self, other: &PlayerStats) {
+        self.hands += other.hands;
+        self.vpip += other.vpip;
+        self.pfr += other.pfr;
+        self.three_bet_opportunities += other.three_bet_opportunities;
+        self.three_bets += other.three_bets;
+        self.postflop_aggressions += other.postflop_aggressions;
+        self.postflop_calls += other.postflop_calls;
+        self.saw_flop += other.saw_flop;
+        self.went_to_showdown += other.went_to_showdown;
+        self.won_at_showdown += other.won_at_showdown;
+    }
+}
+
+/// 按玩家 id 保存的统计，可以序列化后跨牌局保存
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsStore {
+    pub players: BTreeMap<String, PlayerStats>,
+}
+
+/// 一手牌中某位玩家的统计标记
+#[derive(Default)]
+struct HandFlags {
+    vpip: bool,
+    pfr: bool,
+    three_bet_opportunity: bool,
+    three_bet: bool,
+    saw_flop: bool,
+    went_to_showdown: bool,
+    won_at_showdown: bool,
+}
+
+impl StatsStore {
+    pub fn new() -> Self {
+        StatsStore::default()
+    }
+
+    pub fn get(&self, player_id: &str) -> Option<&PlayerStats> {
+        self.players.get(player_id)
+    }
+
+    pub fn hud(&self, player_id: &str) -> Option<HudStats> {
+        self.get(player_id).map(PlayerStats::hud)
+    }
+
+    /// 统计事件记录中的每一手牌
+    pub fn record_history(&mut self, events: &[HandEvent]) {
+        for hand in split_hands(events) {
+            self.record_hand(hand);
+        }
+    }
+
+    /// 统计一手牌，只计入发到底牌的玩家
+    pub fn record_hand(&mut self, events: &[HandEvent]) {
+        let mut flags: HashMap<&str, HandFlags> = HashMap::new();
+        let mut folded: HashSet<&str> = HashSet::new();
+        let mut stage = GameStage::PreFlop;
+        let mut preflop_raises = 0;
+
+        for event in events {
+            match event {
+                HandEvent::HoleCardsDealt { player_id, .. } => {
+                    flags.entry(player_id).or_default();
+                }
+                HandEvent::StreetStarted { stage: next } => {
+                    stage = *next;
+                    if stage == GameStage::Flop {
+                        for (player_id, flag) in &mut flags {
+                            flag.saw_flop = !folded.contains(player_id);
+                        }
+                    }
+                }
+                HandEvent::ActionTaken {
+                    player_id, action, ..
+                } => {
+                    // 大菠萝的弃牌不是下注行动，不计入统计
+                    if matches!(action, PlayerAction::Discard(_)) {
+                        continue;
+                    }
+                    let Some(flag) = flags.get_mut(player_id.as_str()) else {
+                        continue;
+                    };
+                    let aggressive =
+                        matches!(action, PlayerAction::Bet(_) | PlayerAction::Raise(_));
+                    if *action == PlayerAction::Fold {
+                        folded.insert(player_id);
+                    }
+                    if stage == GameStage::PreFlop {
+                        if preflop_raises == 1 {
+                            flag.three_bet_opportunity = true;
+                            flag.three_bet |= aggressive;
+                        }
+                        flag.vpip |= aggressive || *action == PlayerAction::Call;
+                        flag.pfr |= aggressive;
+                        if aggressive {
+                            preflop_raises += 1;
+                        }
+                    } else if aggressive {
+                        self.entry(player_id).postflop_aggressions += 1;
+                    } else if *action == PlayerAction::Call {
+                        self.entry(player_id).postflop_calls += 1;
+                    }
+                }
+                HandEvent::HandShown { player_id, .. } => {
+                    if let Some(flag) = flags.get_mut(player_id.as_str()) {
+                        flag.went_to_showdown = true;
+                    }
+                }
+                HandEvent::PotAwarded {
+                    player_id, amount, ..
+                } => {
+                    if let Some(flag) = flags.get_mut(player_id.as_str()) {
+                        flag.won_at_showdown |= flag.went_to_showdown && *amount > 0;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (player_id, flag) in flags {
+            let stats = self.entry(player_id);
+            stats.hands += 1;
+            stats.vpip += flag.vpip as u32;
+            stats.pfr += flag.pfr as u32;
+            stats.three_bet_opportunities += flag.three_bet_opportunity as u32;
+            stats.three_bets += flag.three_bet as u32;
+            stats.saw_flop += flag.saw_flop as u32;
+            stats.went_to_showdown += flag.went_to_showdown as u32;
+            stats.won_at_showdown += flag.won_at_showdown as u32;
+        }
+    }
+
+    /// 合并另一份统计
+    pub fn merge(&mut self, other: &StatsStore) {
+        for (player_id, stats) in &other.players {
+            self.entry(player_id).merge(stats);
+        }
+    }
+
+    fn entry(&mut self, player_id: &str) -> &mut PlayerStats {
+        self.players.entry(player_id.to_string()).or_default()
+    }
+}
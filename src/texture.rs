@@ -0,0 +1,174 @@
+use std::fmt;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::card_set::CardSet;
+use super::evaluator::{evaluate_set, straight_high, strength_rank};
+use super::shared::*;
+
+/// 公共牌的成对情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BoardPairing {
+    Unpaired,
+    Paired,
+    TwoPaired,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+/// 公共牌的花色分布
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SuitTexture {
+    Rainbow,  // 没有两张同花色
+    TwoTone,  // 有同花色的牌，但不全是同一花色
+    Monotone, // 全部同一花色
+}
+
+/// 公共牌面结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardTexture {
+    pub pairing: BoardPairing,
+    pub suits: SuitTexture,
+    pub max_suit_count: usize,          // 同一花色最多的张数
+    pub connectedness: u8,              // 任意 5 个连续点数内最多有几个不同点数
+    pub highest_straight: Option<Rank>, // 可能成的最大顺子的最大牌
+    pub flush_suit: Option<Suit>,       // 可能成同花的花色
+    pub nut_flush_card: Option<Rank>,   // 组成最大同花需要持有的最大同花色底牌
+    pub possible_hands: Vec<HandRank>,  // 任意两张底牌能组成的牌型，从小到大
+    pub nut_hand: HandRank,             // 最大可能牌型
+}
+
+impl BoardTexture {
+    /// 任意两张底牌能否组成顺子
+    pub fn straight_possible(&self) -> bool {
+        self.highest_straight.is_some()
+    }
+
+    pub fn flush_possible(&self) -> bool {
+        self.flush_suit.is_some()
+    }
+
+    /// 牌面是否连接紧密（5 个连续点数内有 3 个或更多）
+    pub fn is_connected(&self) -> bool {
+        self.connectedness >= 3
+    }
+
+    pub fn is_paired(&self) -> bool {
+        self.pairing != BoardPairing::Unpaired
+    }
+}
+
+impl fmt::Display for BoardTexture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairing = match self.pairing {
+            BoardPairing::Unpaired => "Unpaired",
+            BoardPairing::Paired => "Paired",
+            BoardPairing::TwoPaired => "Two-paired",
+            BoardPairing::Trips => "Trips",
+            BoardPairing::FullHouse => "Full house",
+            BoardPairing::Quads => "Quads",
+        };
+        let suits = match self.suits {
+            SuitTexture::Rainbow => "rainbow",
+            SuitTexture::TwoTone => "two-tone",
+            SuitTexture::Monotone => "monotone",
+        };
+        let connected = if self.is_connected() {
+            "connected"
+        } else {
+            "disconnected"
+        };
+        write!(f, "{}, {}, {}", pairing, suits, connected)
+    }
+}
+
+/// 分析翻牌、转牌或河牌的牌面结构
+pub fn analyze_board(board: &[Card]) -> Result<BoardTexture, GameError> {
+    let board_set = CardSet::from_cards(board);
+    if !(3..=5).contains(&board.len()) || board_set.len() != board.len() {
+        return Err(GameError::InvalidCards);
+    }
+
+    let suit_masks = board_set.suit_masks();
+    let rank_mask = suit_masks.iter().fold(0u16, |acc, m| acc | m);
+
+    // 按点数出现次数判断成对情况
+    let mut counts: Vec<u32> = (0..13)
+        .map(|i| suit_masks.iter().filter(|m| *m & (1 << i) != 0).count() as u32)
+        .filter(|&count| count > 1)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let pairing = match counts.as_slice() {
+        [] => BoardPairing::Unpaired,
+        [4, ..] => BoardPairing::Quads,
+        [3, _, ..] => BoardPairing::FullHouse,
+        [3] => BoardPairing::Trips,
+        [2] => BoardPairing::Paired,
+        _ => BoardPairing::TwoPaired,
+    };
+
+    let max_suit_count = suit_masks.iter().map(|m| m.count_ones()).max().unwrap_or(0) as usize;
+    let suits = if max_suit_count == board.len() {
+        SuitTexture::Monotone
+    } else if max_suit_count == 1 {
+        SuitTexture::Rainbow
+    } else {
+        SuitTexture::TwoTone
+    };
+
+    // A 同时作为最小的牌，与 `straight_high` 相同
+    let extended = (rank_mask << 1) | ((rank_mask >> 12) & 1);
+    let connectedness = (0..=9)
+        .map(|low| (extended >> low & 0b11111).count_ones() as u8)
+        .max()
+        .unwrap_or(0);
+
+    // 任意两个点数加入牌面后能组成的最大顺子
+    let highest_straight = (0..13)
+        .tuple_combinations()
+        .chain((0..13).map(|i| (i, i)))
+        .filter_map(|(a, b): (u16, u16)| straight_high(rank_mask | 1 << a | 1 << b))
+        .max();
+
+    // 最多 5 张公共牌，至多一种花色能成同花
+    let flush = Suit::ALL
+        .into_iter()
+        .zip(suit_masks)
+        .find(|(_, mask)| mask.count_ones() >= 3);
+    let flush_suit = flush.map(|(suit, _)| suit);
+    let nut_flush_card = flush.and_then(|(_, mask)| {
+        Rank::ALL
+            .into_iter()
+            .rev()
+            .find(|rank| mask & (1 << (rank.value() - 2)) == 0)
+    });
+
+    // 枚举所有可能的底牌，统计能组成的牌型
+    let unseen = CardSet::full().difference(board_set).to_vec();
+    let possible_hands: Vec<HandRank> = unseen
+        .iter()
+        .tuple_combinations()
+        .map(|(&c1, &c2)| {
+            strength_rank(evaluate_set(
+                board_set.union(CardSet::from_cards(&[c1, c2])),
+            ))
+        })
+        .unique()
+        .sorted()
+        .collect();
+    let nut_hand = *possible_hands.last().unwrap_or(&HandRank::HighCard);
+
+    Ok(BoardTexture {
+        pairing,
+        suits,
+        max_suit_count,
+        connectedness,
+        highest_straight,
+        flush_suit,
+        nut_flush_card,
+        possible_hands,
+        nut_hand,
+    })
+}
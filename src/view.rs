@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use super::shared::*;
+
+/// 当前玩家可以采取的行动
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LegalAction {
+    Fold,
+    Check,
+    Call { amount: u32 },         // 需要补齐的筹码
+    Bet { min: u32, max: u32 },   // 下注额范围
+    Raise { min: u32, max: u32 }, // 在本轮最高下注额之上的加注额范围
+    Discard { cards: Vec<Card> }, // 可以弃掉的三张牌之一（大菠萝玩法）
+}
+
+/// 从某个玩家的角度看到的其他玩家
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatView {
+    pub id: String,
+    pub name: String,
+    pub chips: u32,
+    pub is_active: bool,
+    pub current_bet: u32,
+    pub total_bet_in_hand: u32,
+    pub has_acted: bool,
+    pub has_cards: bool,             // 是否持有底牌
+    pub cards: Option<(Card, Card)>, // 只有自己的底牌或摊牌时亮出的底牌可见
+}
+
+/// 某个玩家能看到的牌局信息，不包含其他玩家的底牌
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub player_id: String,
+    pub seat: usize, // 自己在 players 中的位置
+    pub hole_cards: Option<(Card, Card)>,
+    pub extra_hole_card: Option<Card>, // 大菠萝玩法中尚未弃掉的第三张底牌
+    pub board: Vec<Card>,
+    pub extra_boards: Vec<Vec<Card>>,
+    pub pot: u32,
+    pub stage: GameStage,
+    pub dealer_position: usize,
+    pub current_player_index: usize,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub players: Vec<SeatView>,
+    pub legal_actions: Vec<LegalAction>, // 轮到自己时才不为空
+}
+
+impl PlayerView {
+    pub fn is_my_turn(&self) -> bool {
+        !self.legal_actions.is_empty()
+    }
+}
+
+impl GameState {
+    /// 指定玩家的观察视图，其他玩家的底牌只在摊牌时亮出
+    pub fn view_for(&self, player_id: &str) -> Result<PlayerView, GameError> {
+        let seat = self
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+
+        // 至少两人进入摊牌时才亮牌，其余人弃牌获胜的不必亮牌
+        let showdown = self.stage == GameStage::Showdown
+            && self.players.iter().filter(|p| p.is_active).count() >= 2;
+        let players = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| SeatView {
+                id: p.id.clone(),
+                name: p.name.clone(),
+                chips: p.chips,
+                is_active: p.is_active,
+                current_bet: p.current_bet,
+                total_bet_in_hand: p.total_bet_in_hand,
+                has_acted: p.has_acted,
+                has_cards: p.cards.is_some(),
+                cards: if i == seat || (showdown && p.is_active) {
+                    p.cards
+                } else {
+                    None
+                },
+            })
+            .collect();
+
+        Ok(PlayerView {
+            player_id: player_id.to_string(),
+            seat,
+            hole_cards: self.players[seat].cards,
+            extra_hole_card: self.extra_hole_cards.get(seat).copied().flatten(),
+            board: self.community_cards.clone(),
+            extra_boards: self.extra_boards.clone(),
+            pot: self.pot,
+            stage: self.stage,
+            dealer_position: self.dealer_position,
+            current_player_index: self.current_player_index,
+            small_blind: self.small_blind,
+            big_blind: self.big_blind,
+            players,
+            legal_actions: self.legal_actions(seat),
+        })
+    }
+
+    /// 指定位置的玩家按 `TexasHoldem::handle_action` 的规则可以采取的行动
+    pub fn legal_actions(&self, seat: usize) -> Vec<LegalAction> {
+        let Some(player) = self.players.get(seat) else {
+            return Vec::new();
+        };
+        if seat != self.current_player_index
+            || !player.is_active
+            || self.stage == GameStage::Showdown
+        {
+            return Vec::new();
+        }
+
+        // 弃牌阶段只能弃掉一张底牌
+        if self.variant.discard_stage() == Some(self.stage) && self.betting_round_complete() {
+            return match (
+                player.cards,
+                self.extra_hole_cards.get(seat).copied().flatten(),
+            ) {
+                (Some((c1, c2)), Some(extra)) => vec![LegalAction::Discard {
+                    cards: vec![c1, c2, extra],
+                }],
+                _ => Vec::new(),
+            };
+        }
+
+        let bet = self.current_bet_round();
+        let mut actions = vec![LegalAction::Fold];
+        if bet <= player.current_bet {
+            actions.push(LegalAction::Check);
+        }
+        // 筹码不足时全下跟注
+        if bet > player.current_bet && player.chips > 0 {
+            actions.push(LegalAction::Call {
+                amount: (bet - player.current_bet).min(player.chips),
+            });
+        }
+        let Some((min, max)) = self.raise_range(seat) else {
+            return actions;
+        };
+        // 筹码不足最小额时只能全下
+        if bet == 0 && player.chips > 0 {
+            actions.push(LegalAction::Bet {
+                min: min.min(player.chips),
+                max: max.min(player.chips),
+            });
+        }
+        let max_raise = (player.chips + player.current_bet).saturating_sub(bet);
+        if bet > 0 && max_raise > 0 {
+            actions.push(LegalAction::Raise {
+                min: min.min(max_raise),
+                max: max.min(max_raise),
+            });
+        }
+        actions
+    }
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use common::*;
+use texas_engine::*;
+
+fn bomb_pot_config(double_board: bool) -> GameConfig {
+    GameConfig {
+        small_blind: 10,
+        big_blind: 20,
+        ante: 50,
+        bomb_pot: true,
+        double_board,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bomb_pot_starts_on_flop() {
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    let game = TexasHoldem::with_config(players, &bomb_pot_config(false), 0);
+
+    assert_eq!(game.state.stage, GameStage::Flop);
+    assert_eq!(game.state.community_cards.len(), 3);
+    assert!(game.state.extra_boards.is_empty());
+    assert_eq!(game.state.pot, 150);
+
+    // 没有盲注，本轮从零开始下注
+    for p in &game.state.players {
+        assert_eq!(p.chips, 950);
+        assert_eq!(p.current_bet, 0);
+        assert_eq!(p.total_bet_in_hand, 50);
+    }
+}
+
+#[test]
+fn test_double_board_deals_both_boards() {
+    let players = vec![player("a", 1000), player("b", 1000)];
+    let mut game = TexasHoldem::with_config(players, &bomb_pot_config(true), 0);
+
+    assert_eq!(game.state.community_cards.len(), 3);
+    assert_eq!(game.state.extra_boards.len(), 1);
+    assert_eq!(game.state.extra_boards[0].len(), 3);
+
+    for _ in 0..3 {
+        game.handle_action(PlayerAction::Check).unwrap();
+        game.handle_action(PlayerAction::Check).unwrap();
+    }
+
+    assert_eq!(game.state.stage, GameStage::Showdown);
+    assert_eq!(game.state.community_cards.len(), 5);
+    assert_eq!(game.state.extra_boards[0].len(), 5);
+    assert_eq!(game.deck.len(), 52 - 4 - 10);
+}
+
+#[test]
+fn test_double_board_splits_pot() {
+    let mut a = player("a", 0);
+    a.cards = Some((card("Ah"), card("As")));
+    a.total_bet_in_hand = 100;
+    let mut b = player("b", 0);
+    b.cards = Some((card("7c"), card("7d")));
+    b.total_bet_in_hand = 100;
+    let mut c = player("c", 0);
+    c.cards = Some((card("2c"), card("3d")));
+    c.total_bet_in_hand = 101;
+    c.is_active = false;
+
+    let mut game = TexasHoldem {
+        state: GameState {
+            players: vec![a, b, c],
+            // 第一块牌面 A 的一对 A 最大
+            community_cards: parse_cards("Kc 9d 4h Js 2h").unwrap(),
+            pot: 301,
+            current_player_index: 0,
+            stage: GameStage::Showdown,
+            dealer_position: 0,
+            small_blind: 10,
+            big_blind: 20,
+            ante: 0,
+            variant: GameVariant::Holdem,
+            extra_hole_cards: Vec::new(),
+            bomb_pot: false,
+            // 第二块牌面 B 成三条
+            extra_boards: vec![parse_cards("7h Qd 5s Tc 3h").unwrap()],
+            betting: BettingStructure::NoLimit,
+            bets_this_round: 0,
+        },
+        deck: vec![],
+        history: Vec::new(),
+    };
+
+    game.resolve_showdown();
+
+    // 主池 300 两块牌面各 150；只有弃牌玩家参与的 1 筹码边池没人跟，退还给 c
+    assert_eq!(game.state.players[0].chips, 150);
+    assert_eq!(game.state.players[1].chips, 150);
+    assert_eq!(game.state.players[2].chips, 1);
+    assert_eq!(game.state.pot, 0);
+    assert_eq!(total_chips(&game), 301);
+}
+
+fn total_chips(game: &TexasHoldem) -> u32 {
+    game.state.players.iter().map(|p| p.chips).sum::<u32>() + game.state.pot
+}
+
+#[test]
+fn test_odd_chip_goes_left_of_dealer() {
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    let config = GameConfig {
+        ante: 1,
+        ..bomb_pot_config(true)
+    };
+    let mut game = TexasHoldem::with_config(players, &config, 0);
+    // 两块牌面都是同花顺，a 和 b 平分，c 已弃牌
+    game.state.community_cards = parse_cards("Ts Js Qs Ks As").unwrap();
+    game.state.extra_boards = vec![parse_cards("7h 8h 9h Th Jh").unwrap()];
+    let hole = parse_cards("2c 3c 2d 3d 4c 5d").unwrap();
+    for (player, cards) in game.state.players.iter_mut().zip(hole.chunks(2)) {
+        player.cards = Some((cards[0], cards[1]));
+    }
+    game.state.players[2].is_active = false;
+    game.state.stage = GameStage::Showdown;
+    game.resolve_showdown();
+
+    // 3 筹码分两块牌面：第一块 2 给 a、b 各 1，第二块 1 给庄家左手边的 b
+    let chips: Vec<_> = game.state.players.iter().map(|p| p.chips).collect();
+    assert_eq!(chips, vec![1000, 1001, 999]);
+    assert_eq!(total_chips(&game), 3000);
+}
+
+#[test]
+fn test_chips_are_conserved_through_a_bomb_pot() {
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    let mut game = TexasHoldem::with_config(players, &bomb_pot_config(true), 0);
+    game.handle_action(PlayerAction::Bet(100)).unwrap();
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    while game.state.stage != GameStage::Showdown {
+        game.handle_action(PlayerAction::Check).unwrap();
+    }
+    game.resolve_showdown();
+    assert_eq!(game.state.pot, 0);
+    assert_eq!(total_chips(&game), 3000);
+
+    // 再次结算不会重复分配
+    game.resolve_showdown();
+    assert_eq!(total_chips(&game), 3000);
+}
+
+#[test]
+fn test_fold_win_empties_pot() {
+    let players = vec![player("a", 1000), player("b", 1000)];
+    let mut game = TexasHoldem::with_config(players, &bomb_pot_config(false), 0);
+    game.handle_action(PlayerAction::Bet(100)).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    game.resolve_showdown();
+    assert_eq!(game.state.pot, 0);
+    assert_eq!(total_chips(&game), 2000);
+}
+
+#[rstest::rstest]
+#[case(GameVariant::Pineapple, 0)]
+#[case(GameVariant::CrazyPineapple, 3)]
+fn test_pineapple_bomb_pot_discards(#[case] variant: GameVariant, #[case] board_before: usize) {
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    let config = GameConfig {
+        variant,
+        ..bomb_pot_config(false)
+    };
+    let mut game = TexasHoldem::with_config(players, &config, 0);
+
+    // 疯狂大菠萝先打完翻牌圈
+    if variant == GameVariant::CrazyPineapple {
+        for _ in 0..3 {
+            game.handle_action(PlayerAction::Check).unwrap();
+        }
+    }
+    assert!(game.awaiting_discard());
+    assert_eq!(game.state.community_cards.len(), board_before);
+    for _ in 0..3 {
+        let extra = game.state.extra_hole_cards[game.state.current_player_index].unwrap();
+        game.handle_action(PlayerAction::Discard(extra)).unwrap();
+    }
+    assert!(game.state.extra_hole_cards.iter().all(Option::is_none));
+    // 弃牌后进入下一条街：大菠萝发翻牌，疯狂大菠萝发转牌
+    let board_after = if board_before == 0 { 3 } else { 4 };
+    assert_eq!(game.state.community_cards.len(), board_after);
+}
+
+#[test]
+fn test_short_deck_bomb_pot_is_an_error() {
+    let players = || vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    // 只够发底牌，发不出翻牌
+    let deck = parse_cards("As Ad Kh Kd 7c 2d 9s").unwrap();
+    assert_eq!(
+        TexasHoldem::try_with_deck(players(), &bomb_pot_config(false), 0, deck).err(),
+        Some(GameError::StageError)
+    );
+
+    let deck = rules::create_seeded_deck(1);
+    let game = TexasHoldem::try_with_deck(players(), &bomb_pot_config(false), 0, deck).unwrap();
+    assert_eq!(game.state.stage, GameStage::Flop);
+}
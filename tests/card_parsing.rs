@@ -0,0 +1,123 @@
+mod common;
+
+use common::*;
+use texas_engine::*;
+
+#[test]
+fn test_parse_rank_and_suit() {
+    assert_eq!("A".parse::<Rank>(), Ok(Rank::Ace));
+    assert_eq!("t".parse::<Rank>(), Ok(Rank::Ten));
+    assert_eq!("10".parse::<Rank>(), Ok(Rank::Ten));
+    assert_eq!("2".parse::<Rank>(), Ok(Rank::Two));
+    assert_eq!(
+        "1".parse::<Rank>(),
+        Err(CardParseError::InvalidRank("1".to_string()))
+    );
+    assert!("AK".parse::<Rank>().is_err());
+
+    assert_eq!("h".parse::<Suit>(), Ok(Suit::Hearts));
+    assert_eq!("S".parse::<Suit>(), Ok(Suit::Spades));
+    assert_eq!("♦".parse::<Suit>(), Ok(Suit::Diamonds));
+    assert_eq!(
+        "x".parse::<Suit>(),
+        Err(CardParseError::InvalidSuit("x".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_card() {
+    assert_eq!(
+        "Ah".parse::<Card>(),
+        Ok(Card {
+            rank: Rank::Ace,
+            suit: Suit::Hearts
+        })
+    );
+    assert_eq!(
+        "Td".parse::<Card>(),
+        Ok(Card {
+            rank: Rank::Ten,
+            suit: Suit::Diamonds
+        })
+    );
+    assert_eq!(
+        "10♠".parse::<Card>(),
+        Ok(Card {
+            rank: Rank::Ten,
+            suit: Suit::Spades
+        })
+    );
+    assert!("AhKd".parse::<Card>().is_err());
+    assert!("A".parse::<Card>().is_err());
+    for bad in ["Ah,", ",Ah", " Ah ", "A h", ""] {
+        assert!(bad.parse::<Card>().is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn test_display_round_trip() {
+    for c in CardSet::full() {
+        assert_eq!(c.to_string().parse::<Card>(), Ok(c));
+        assert_eq!(c.rank.to_string().parse::<Rank>(), Ok(c.rank));
+        assert_eq!(c.suit.to_string().parse::<Suit>(), Ok(c.suit));
+    }
+}
+
+#[test]
+fn test_parse_card_list() {
+    assert_eq!(
+        parse_cards("AhKd Ts9s2c").unwrap(),
+        ["Ah", "Kd", "Ts", "9s", "2c"].map(card)
+    );
+    assert_eq!(
+        parse_cards("A♥, 10♦ 3c").unwrap(),
+        ["Ah", "Td", "3c"].map(card)
+    );
+    assert_eq!(parse_cards("").unwrap(), vec![]);
+    assert_eq!(parse_cards(" ,\t\n").unwrap(), vec![]);
+    assert_eq!(
+        parse_cards("Ah,Kd\tTs\n9s").unwrap(),
+        parse_cards("AhKdTs9s").unwrap()
+    );
+}
+
+#[test]
+fn test_parse_card_list_errors() {
+    assert_eq!(
+        parse_cards("AhXd"),
+        Err(CardParseError::InvalidRank("X".to_string()))
+    );
+    assert_eq!(
+        parse_cards("AhKx"),
+        Err(CardParseError::InvalidSuit("x".to_string()))
+    );
+    assert_eq!(
+        parse_cards("AhK"),
+        Err(CardParseError::InvalidCard("AhK".to_string()))
+    );
+    // 分隔符只能出现在牌之间
+    assert_eq!(
+        parse_cards("A h"),
+        Err(CardParseError::InvalidCard("A".to_string()))
+    );
+    assert_eq!(
+        parse_cards("1 0h"),
+        Err(CardParseError::InvalidRank("1".to_string()))
+    );
+    assert_eq!(
+        parse_cards("Ah;Kd"),
+        Err(CardParseError::InvalidRank(";".to_string()))
+    );
+    assert_eq!(
+        parse_cards("Ah Ah"),
+        Err(CardParseError::DuplicateCard(card("Ah")))
+    );
+}
+
+#[test]
+fn test_try_from_value() {
+    assert_eq!(Rank::try_from_value(14), Some(Rank::Ace));
+    assert_eq!(Rank::try_from_value(1), Some(Rank::Ace));
+    assert_eq!(Rank::try_from_value(0), None);
+    assert_eq!(Rank::try_from_value(15), None);
+}
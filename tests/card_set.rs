@@ -0,0 +1,87 @@
+mod common;
+
+use common::*;
+use texas_engine::evaluator::{evaluate_set, evaluate_strength};
+use texas_engine::*;
+
+#[test]
+fn test_full_deck() {
+    let deck = CardSet::full();
+    assert_eq!(deck.len(), 52);
+
+    let cards = deck.to_vec();
+    assert_eq!(cards.len(), 52);
+    assert_eq!(CardSet::from_cards(&cards), deck);
+    assert_eq!(cards[0], card("2h"));
+    assert_eq!(cards[51], card("As"));
+}
+
+#[test]
+fn test_insert_remove_contains() {
+    let ace = card("As");
+    let mut set = CardSet::EMPTY;
+
+    assert!(set.is_empty());
+    assert!(set.insert(ace));
+    assert!(!set.insert(ace));
+    assert!(set.contains(ace));
+    assert_eq!(set.len(), 1);
+    assert_eq!(set, CardSet::from(ace));
+
+    assert!(set.remove(ace));
+    assert!(!set.remove(ace));
+    assert!(!set.contains(ace));
+}
+
+#[test]
+fn test_set_operations() {
+    let a = CardSet::from_cards(&parse_cards("Ah Kh").unwrap());
+    let b = CardSet::from_cards(&parse_cards("Kh 2c").unwrap());
+
+    assert_eq!(a.union(b).len(), 3);
+    assert_eq!(a.intersection(b).to_vec(), vec![card("Kh")]);
+    assert_eq!(a.difference(b).to_vec(), vec![card("Ah")]);
+    assert!(!a.is_disjoint(b));
+    assert!(a.difference(b).is_disjoint(b));
+    assert_eq!(CardSet::full().difference(a).len(), 50);
+}
+
+#[test]
+fn test_bit_index_round_trip() {
+    for card in CardSet::full() {
+        assert_eq!(Card::from_bit_index(card.bit_index()), Some(card));
+    }
+    assert_eq!(Card::from_bit_index(13), None);
+    assert_eq!(Card::from_bit_index(64), None);
+}
+
+#[test]
+fn test_evaluate_set_matches_slice() {
+    let cards = parse_cards("Ah Ac Kd Ks Kh 2c 7c").unwrap();
+
+    assert_eq!(
+        evaluate_set(CardSet::from_cards(&cards)),
+        evaluate_strength(&cards)
+    );
+}
+
+#[test]
+fn test_invalid_bits_are_masked() {
+    // 每种花色的第 13 到 15 位不对应任何牌
+    let set = CardSet::from_bits(u64::MAX);
+    assert_eq!(set, CardSet::full());
+    assert_eq!(set.len(), set.iter().count());
+    assert_eq!(CardSet::from_bits(1 << 13 | 1 << 62), CardSet::EMPTY);
+
+    let parsed: CardSet = serde_json::from_str(&u64::MAX.to_string()).unwrap();
+    assert_eq!(parsed.len(), 52);
+    let ace = CardSet::from(card("As"));
+    assert_eq!(serde_json::to_string(&ace).unwrap(), ace.bits().to_string());
+}
+
+#[test]
+#[should_panic(expected = "只能评估5到7张牌")]
+fn test_evaluate_set_rejects_four_cards() {
+    let cards = parse_cards("Ah Ac Kd Ks").unwrap();
+    evaluate_set(CardSet::from_cards(&cards));
+}
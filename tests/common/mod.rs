@@ -0,0 +1,31 @@
+//! 集成测试共用的玩家和牌局构造
+#![allow(dead_code)]
+
+use texas_engine::*;
+
+pub fn player(id: &str, chips: u32) -> Player {
+    Player {
+        id: id.to_string(),
+        name: id.to_string(),
+        chips,
+        cards: None,
+        is_active: true,
+        current_bet: 0,
+        has_acted: false,
+        total_bet_in_hand: 0,
+    }
+}
+
+/// 三名各有 1000 筹码的玩家，盲注 10/20
+pub fn new_game() -> TexasHoldem {
+    TexasHoldem::new(
+        vec![player("a", 1000), player("b", 1000), player("c", 1000)],
+        10,
+        20,
+    )
+}
+
+/// 解析单张牌，如 "Ah"
+pub fn card(s: &str) -> Card {
+    s.parse().unwrap()
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+use texas_engine::equity::*;
+use texas_engine::*;
+
+#[test]
+fn test_river_is_decided() {
+    let hands = [(card("Ah"), card("As")), (card("Kh"), card("Ks"))];
+    let board = parse_cards("2c 7d 9c Td 4h").unwrap();
+
+    let equity = calculate_equity(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    assert!(equity.exhaustive);
+    assert_eq!(equity.boards, 1);
+    assert_eq!(equity.players[0].win, 100.0);
+    assert_eq!(equity.players[1].equity, 0.0);
+}
+
+#[test]
+fn test_turn_flush_draw_outs() {
+    // 同花听牌加两张高牌：9 张红心 + 3 张 A + 3 张 K，共 15 张补牌
+    let hands = [(card("Ah"), card("Kh")), (card("Qs"), card("Qc"))];
+    let board = parse_cards("2h 7h 9c Td").unwrap();
+
+    let equity = calculate_equity(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    assert!(equity.exhaustive);
+    assert_eq!(equity.boards, 44);
+    assert!((equity.players[0].win - 15.0 * 100.0 / 44.0).abs() < 1e-9);
+    assert!((equity.players[1].win - 29.0 * 100.0 / 44.0).abs() < 1e-9);
+
+    // 死牌中的红心不再是补牌
+    let dead = [card("3h")];
+    let equity = calculate_equity(&hands, &board, &dead, &EquityOptions::default()).unwrap();
+    assert_eq!(equity.boards, 43);
+    assert!((equity.players[0].win - 14.0 * 100.0 / 43.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_split_pot_on_board() {
+    let hands = [(card("2h"), card("3s")), (card("2c"), card("3d"))];
+    let board = parse_cards("Ac Kd Qc Jd Th").unwrap();
+
+    let equity = calculate_equity(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    for player in &equity.players {
+        assert_eq!(player.win, 0.0);
+        assert_eq!(player.tie, 100.0);
+        assert_eq!(player.equity, 50.0);
+    }
+}
+
+#[test]
+fn test_preflop_monte_carlo_is_seeded() {
+    let hands = [(card("Ah"), card("As")), (card("Kd"), card("Kc"))];
+    let options = EquityOptions {
+        samples: 20_000,
+        seed: 42,
+        ..Default::default()
+    };
+
+    let equity = calculate_equity(&hands, &[], &[], &options).unwrap();
+    assert!(!equity.exhaustive);
+    assert_eq!(equity.boards, 20_000);
+    // AA 对 KK 约 82%
+    assert!((equity.players[0].equity - 82.0).abs() < 1.5);
+    let total: f64 = equity.players.iter().map(|p| p.equity).sum();
+    assert!((total - 100.0).abs() < 1e-9);
+
+    let again = calculate_equity(&hands, &[], &[], &options).unwrap();
+    assert_eq!(equity, again);
+}
+
+#[test]
+fn test_three_way_flop_is_exhaustive() {
+    let hands = [
+        (card("Ah"), card("As")),
+        (card("Kd"), card("Kc")),
+        (card("8s"), card("7s")),
+    ];
+    let board = parse_cards("6s 5c 2h").unwrap();
+
+    let equity = calculate_equity(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    assert!(equity.exhaustive);
+    assert_eq!(equity.boards, 43 * 42 / 2);
+    let total: f64 = equity.players.iter().map(|p| p.equity).sum();
+    assert!((total - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_rejects_invalid_cards() {
+    let ace = card("Ah");
+    let king = card("Kh");
+    let options = EquityOptions::default();
+
+    // 重复的牌
+    let hands = [(ace, king), (ace, card("2c"))];
+    assert_eq!(
+        calculate_equity(&hands, &[], &[], &options),
+        Err(GameError::InvalidCards)
+    );
+
+    // 死牌与手牌重复
+    let hands = [(ace, king), (card("2c"), card("3c"))];
+    assert_eq!(
+        calculate_equity(&hands, &[], &[king], &options),
+        Err(GameError::InvalidCards)
+    );
+
+    // 少于两名玩家
+    assert_eq!(
+        calculate_equity(&hands[..1], &[], &[], &options),
+        Err(GameError::InvalidCards)
+    );
+
+    // 死牌太多，剩下的牌发不完公共牌
+    let dead: Vec<Card> = CardSet::full()
+        .iter()
+        .filter(|c| ![ace, king, hands[1].0, hands[1].1].contains(c))
+        .skip(4)
+        .collect();
+    assert_eq!(
+        calculate_equity(&hands, &[], &dead, &options),
+        Err(GameError::InvalidCards)
+    );
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use texas_engine::evaluator::*;
+use texas_engine::*;
+
+fn full_deck() -> Vec<Card> {
+    let mut deck = rules::create_shuffled_deck();
+    deck.sort_by_key(|c| (c.rank, c.suit as u8));
+    deck
+}
+
+#[test]
+fn test_matches_rules_on_every_five_card_hand() {
+    let deck = full_deck();
+    let mut strengths = HashSet::new();
+
+    for hand in deck.iter().copied().combinations(5) {
+        let strength = evaluate_strength(&hand);
+        let evaluation = rules::evaluate_hand(&(hand[0], hand[1]), &hand[2..]);
+
+        assert_eq!(strength_rank(strength), evaluation.rank, "{:?}", hand);
+        assert_eq!(strength_kickers(strength), evaluation.kickers, "{:?}", hand);
+        assert_eq!(evaluation.strength(), strength);
+        strengths.insert(strength);
+    }
+
+    // 五张牌共有 7462 种不同的牌力，排名与牌力值顺序相反且连续
+    assert_eq!(strengths.len(), HAND_CLASS_COUNT as usize);
+    let mut strengths: Vec<u32> = strengths.into_iter().collect();
+    strengths.sort_unstable_by(|a, b| b.cmp(a));
+    for (i, &strength) in strengths.iter().enumerate() {
+        assert_eq!(strength_index(strength), i as u16 + 1);
+        assert_eq!(strength_from_index(i as u16 + 1), Some(strength));
+    }
+}
+
+#[test]
+fn test_strength_index_landmarks() {
+    let hand = |ranks: [Rank; 5], flush: bool| -> Vec<Card> {
+        ranks
+            .iter()
+            .enumerate()
+            .map(|(i, &rank)| Card {
+                rank,
+                suit: if flush {
+                    Suit::Spades
+                } else {
+                    Suit::ALL[i % 4]
+                },
+            })
+            .collect()
+    };
+    let index = |cards: Vec<Card>| strength_index(evaluate_strength(&cards));
+
+    use Rank::*;
+    assert_eq!(index(hand([Ace, King, Queen, Jack, Ten], true)), 1);
+    assert_eq!(index(hand([Five, Four, Three, Two, Ace], true)), 10);
+    assert_eq!(index(hand([Ace, Ace, Ace, Ace, King], false)), 11);
+    assert_eq!(index(hand([Ace, Ace, Ace, King, King], false)), 167);
+    assert_eq!(index(hand([Ace, King, Queen, Jack, Nine], true)), 323);
+    assert_eq!(index(hand([Ace, King, Queen, Jack, Ten], false)), 1600);
+    assert_eq!(index(hand([Seven, Five, Four, Three, Two], false)), 7462);
+
+    assert_eq!(strength_from_index(0), None);
+    assert_eq!(strength_from_index(7463), None);
+}
+
+#[test]
+fn test_matches_rules_on_random_seven_card_hands() {
+    let mut deck = full_deck();
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..20_000 {
+        deck.shuffle(&mut rng);
+        let strength = evaluate_strength(&deck[..7]);
+        let evaluation = rules::evaluate_hand(&(deck[0], deck[1]), &deck[2..7]);
+
+        assert_eq!(strength_rank(strength), evaluation.rank, "{:?}", &deck[..7]);
+        assert_eq!(strength_kickers(strength), evaluation.kickers);
+    }
+}
+
+#[test]
+fn test_wheel_is_lowest_straight() {
+    let cards = |ranks: [Rank; 5]| -> Vec<Card> {
+        ranks
+            .iter()
+            .enumerate()
+            .map(|(i, &rank)| Card {
+                rank,
+                suit: if i == 0 { Suit::Hearts } else { Suit::Clubs },
+            })
+            .collect()
+    };
+    let wheel = evaluate_strength(&cards([
+        Rank::Ace,
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+    ]));
+    let six_high = evaluate_strength(&cards([
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+    ]));
+
+    assert_eq!(strength_rank(wheel), HandRank::Straight);
+    assert_eq!(strength_kickers(wheel), vec![Rank::Five]);
+    assert!(wheel < six_high);
+}
+
+#[test]
+fn test_compare_hands_outside_seven_cards() {
+    let hand = |s: &str| {
+        let cards = parse_cards(s).unwrap();
+        (cards[0], cards[1])
+    };
+    let (aces, kings) = (hand("As Ad"), hand("Ks Kd"));
+
+    // 六张公共牌时不能走快速路径，按完整评估比较
+    let board = parse_cards("2c 7h 9d Jc 4s 3h").unwrap();
+    assert_eq!(
+        rules::compare_hands(&aces, &kings, &board),
+        std::cmp::Ordering::Greater
+    );
+    let board = parse_cards("2c 7h 9d Jc 4s 3h Qh").unwrap();
+    assert_eq!(
+        rules::compare_hands(&kings, &aces, &board),
+        std::cmp::Ordering::Less
+    );
+}
@@ -1,40 +1,17 @@
 use texas_engine::*;
 
+fn hole(s: &str) -> (Card, Card) {
+    match parse_cards(s).unwrap().as_slice() {
+        [c1, c2] => (*c1, *c2),
+        _ => panic!("需要两张底牌: {s}"),
+    }
+}
+
 #[test]
 fn test_royal_flush() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::King,
-        },
-    );
+    let player_cards = hole("AhKh");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ten,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Qh Jh Th 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::RoyalFlush);
@@ -42,39 +19,9 @@ fn test_royal_flush() {
 
 #[test]
 fn test_straight_flush() {
-    let player_cards = (
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Nine,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Eight,
-        },
-    );
+    let player_cards = hole("9s8s");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Seven,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Six,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Five,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Queen,
-        },
-    ];
+    let community_cards = parse_cards("7s 6s 5s Kd Qc").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::StraightFlush);
@@ -83,39 +30,9 @@ fn test_straight_flush() {
 
 #[test]
 fn test_four_of_a_kind() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Ace,
-        },
-    );
+    let player_cards = hole("AhAd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Ac As Kh 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::FourOfAKind);
@@ -124,39 +41,9 @@ fn test_four_of_a_kind() {
 
 #[test]
 fn test_full_house() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::King,
-        },
-    );
+    let player_cards = hole("KhKd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Kc Qs Qh 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::FullHouse);
@@ -165,39 +52,9 @@ fn test_full_house() {
 
 #[test]
 fn test_flush() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ten,
-        },
-    );
+    let player_cards = hole("AhTh");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Kh Qh Jd 2h 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::Flush);
@@ -209,39 +66,9 @@ fn test_flush() {
 
 #[test]
 fn test_straight() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ten,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Nine,
-        },
-    );
+    let player_cards = hole("Th9d");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Eight,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Seven,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Six,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("8c 7s 6h 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::Straight);
@@ -250,39 +77,9 @@ fn test_straight() {
 
 #[test]
 fn test_straight_ace_low() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-    );
+    let player_cards = hole("Ah2d");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Four,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Five,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Queen,
-        },
-    ];
+    let community_cards = parse_cards("3c 4s 5h Kd Qc").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::Straight);
@@ -291,39 +88,9 @@ fn test_straight_ace_low() {
 
 #[test]
 fn test_three_of_a_kind() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Jack,
-        },
-    );
+    let player_cards = hole("JhJd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Ten,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Nine,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Jc Ts 9h 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::ThreeOfAKind);
@@ -332,39 +99,9 @@ fn test_three_of_a_kind() {
 
 #[test]
 fn test_two_pair() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Queen,
-        },
-    );
+    let player_cards = hole("QhQd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Ten,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Ten,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Nine,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Tc Ts 9h 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::TwoPair);
@@ -373,39 +110,9 @@ fn test_two_pair() {
 
 #[test]
 fn test_one_pair() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Ace,
-        },
-    );
+    let player_cards = hole("AhAd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::King,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Kc Qs Jh 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::OnePair);
@@ -417,39 +124,9 @@ fn test_one_pair() {
 
 #[test]
 fn test_high_card() {
-    let player_cards = (
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Ace,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::King,
-        },
-    );
+    let player_cards = hole("AhKd");
 
-    let community_cards = vec![
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Queen,
-        },
-        Card {
-            suit: Suit::Spades,
-            rank: Rank::Jack,
-        },
-        Card {
-            suit: Suit::Hearts,
-            rank: Rank::Nine,
-        },
-        Card {
-            suit: Suit::Diamonds,
-            rank: Rank::Two,
-        },
-        Card {
-            suit: Suit::Clubs,
-            rank: Rank::Three,
-        },
-    ];
+    let community_cards = parse_cards("Qc Js 9h 2d 3c").unwrap();
 
     let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
     assert_eq!(evaluation.rank, HandRank::HighCard);
@@ -458,3 +135,65 @@ fn test_high_card() {
         vec![Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Nine]
     );
 }
+
+#[test]
+fn test_full_house_best_cards_and_description() {
+    let player_cards = hole("Kh5d");
+
+    let community_cards = parse_cards("Kc Ks 5h 2d 9c").unwrap();
+
+    let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
+    assert_eq!(evaluation.description(), "Full House, Kings full of Fives");
+    assert_eq!(
+        evaluation.cards.iter().map(|c| c.rank).collect::<Vec<_>>(),
+        vec![Rank::King, Rank::King, Rank::King, Rank::Five, Rank::Five]
+    );
+}
+
+#[test]
+fn test_wheel_best_cards_and_description() {
+    let player_cards = hole("Ah2d");
+
+    let community_cards = parse_cards("3c 4s 5h Kd Kc").unwrap();
+
+    let evaluation = rules::evaluate_hand(&player_cards, &community_cards);
+    assert_eq!(evaluation.to_string(), "Five-high Straight");
+    assert_eq!(
+        evaluation.cards.iter().map(|c| c.rank).collect::<Vec<_>>(),
+        vec![Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace]
+    );
+}
+
+#[test]
+fn test_descriptions() {
+    let hand = |rank: HandRank, kickers: Vec<Rank>| HandEvaluation {
+        rank,
+        kickers,
+        cards: vec![],
+    };
+
+    assert_eq!(
+        hand(HandRank::Flush, vec![Rank::Ace, Rank::Ten]).description(),
+        "Ace-high Flush"
+    );
+    assert_eq!(
+        hand(HandRank::TwoPair, vec![Rank::Jack, Rank::Six, Rank::Two]).description(),
+        "Two Pair, Jacks and Sixes"
+    );
+    assert_eq!(
+        hand(HandRank::OnePair, vec![Rank::Queen]).description(),
+        "One Pair, Queens"
+    );
+    assert_eq!(
+        hand(HandRank::FourOfAKind, vec![Rank::Two]).description(),
+        "Four of a Kind, Twos"
+    );
+    assert_eq!(
+        hand(HandRank::RoyalFlush, vec![Rank::Ace]).description(),
+        "Royal Flush"
+    );
+    assert_eq!(
+        hand(HandRank::HighCard, vec![Rank::King]).description(),
+        "High Card, King"
+    );
+}
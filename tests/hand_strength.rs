@@ -0,0 +1,63 @@
+mod common;
+
+use common::*;
+use texas_engine::hand_strength::*;
+use texas_engine::*;
+
+fn flop() -> Vec<Card> {
+    parse_cards("3h 4c Jh").unwrap()
+}
+
+#[test]
+fn test_flop_strength_and_potential() {
+    // Billings 等人论文中的例子：A♦Q♣ 在 3♥4♣J♥ 上
+    let hole = (card("Ad"), card("Qc"));
+
+    let hs = hand_strength(hole, &flop()).unwrap();
+    assert!((hs - 0.585).abs() < 0.001);
+
+    let (ppot, npot) = hand_potential(hole, &flop()).unwrap();
+    assert!((ppot - 0.208).abs() < 0.001);
+    assert!((npot - 0.274).abs() < 0.001);
+}
+
+#[test]
+fn test_metrics_for_multiple_opponents() {
+    let hole = (card("Ad"), card("Qc"));
+
+    let one = hand_strength_metrics(hole, &flop(), 1).unwrap();
+    let three = hand_strength_metrics(hole, &flop(), 3).unwrap();
+    assert!((three.hs_n - one.hs.powi(3)).abs() < 1e-12);
+    assert!(three.ehs < one.ehs);
+    assert!((one.ehs - (one.hs_n * (1.0 - one.npot) + (1.0 - one.hs_n) * one.ppot)).abs() < 1e-12);
+    assert!(one.hs_squared > 0.0 && one.hs_squared < 1.0);
+
+    assert_eq!(
+        hand_strength_metrics(hole, &flop(), 0),
+        Err(GameError::InvalidCards)
+    );
+}
+
+#[test]
+fn test_river_has_no_potential() {
+    // 皇家同花顺没有对手能追平
+    let hole = (card("Ah"), card("Kh"));
+    let board = parse_cards("Qh Jh Th 2c 3d").unwrap();
+
+    let metrics = hand_strength_metrics(hole, &board, 2).unwrap();
+    assert_eq!(metrics.hs, 1.0);
+    assert_eq!(metrics.ppot, 0.0);
+    assert_eq!(metrics.npot, 0.0);
+    assert_eq!(metrics.ehs, 1.0);
+    assert_eq!(metrics.hs_squared, 1.0);
+}
+
+#[test]
+fn test_invalid_cards() {
+    let hole = (card("3h"), card("Qc"));
+    assert_eq!(hand_strength(hole, &flop()), Err(GameError::InvalidCards));
+    assert_eq!(
+        hand_potential(hole, &flop()[..2]),
+        Err(GameError::InvalidCards)
+    );
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::*;
+use texas_engine::history::*;
+use texas_engine::*;
+
+#[test]
+fn test_hand_start_is_recorded() {
+    let game = new_game();
+    let events = game.current_hand();
+
+    match &events[0] {
+        HandEvent::HandStarted {
+            players,
+            small_blind,
+            big_blind,
+            boards,
+            ..
+        } => {
+            assert_eq!(players.len(), 3);
+            assert!(players.iter().all(|p| p.chips == 1000));
+            assert_eq!((*small_blind, *big_blind, *boards), (10, 20, 1));
+        }
+        event => panic!("unexpected first event: {:?}", event),
+    }
+
+    let dealt = events
+        .iter()
+        .filter(|e| matches!(e, HandEvent::HoleCardsDealt { cards, .. } if cards.len() == 2))
+        .count();
+    assert_eq!(dealt, 3);
+
+    let blinds: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::BlindPosted {
+                player_id,
+                blind,
+                amount,
+            } => Some((player_id.as_str(), *blind, *amount)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(blinds, vec![("b", Blind::Small, 10), ("c", Blind::Big, 20)]);
+}
+
+#[test]
+fn test_actions_streets_and_showdown_are_recorded() {
+    let mut game = new_game();
+    game.handle_action(PlayerAction::Raise(40)).unwrap(); // a 加注到 60
+    game.handle_action(PlayerAction::Fold).unwrap(); // b
+    game.handle_action(PlayerAction::Call).unwrap(); // c 补 40
+    for _ in 0..3 {
+        game.handle_action(PlayerAction::Check).unwrap();
+        game.handle_action(PlayerAction::Check).unwrap();
+    }
+    assert_eq!(game.state.stage, GameStage::Showdown);
+    game.resolve_showdown();
+
+    let events = game.current_hand();
+    let actions: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::ActionTaken {
+                player_id,
+                action,
+                amount,
+            } => Some((player_id.as_str(), action.clone(), *amount)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        actions[..3],
+        [
+            ("a", PlayerAction::Raise(40), 60),
+            ("b", PlayerAction::Fold, 0),
+            ("c", PlayerAction::Call, 40),
+        ]
+    );
+    assert_eq!(actions.len(), 9);
+
+    let streets: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::StreetStarted { stage } => Some(*stage),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        streets,
+        vec![
+            GameStage::Flop,
+            GameStage::Turn,
+            GameStage::River,
+            GameStage::Showdown
+        ]
+    );
+
+    let board: Vec<Card> = events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::BoardDealt { board: 0, cards } => Some(cards.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(board, game.state.community_cards);
+
+    let shown = events
+        .iter()
+        .filter(|e| matches!(e, HandEvent::HandShown { hands, .. } if hands.len() == 1))
+        .count();
+    assert_eq!(shown, 2);
+
+    let awarded: u32 = events
+        .iter()
+        .filter_map(|e| match e {
+            HandEvent::PotAwarded { amount, .. } => Some(*amount),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(awarded, 130);
+}
+
+#[test]
+fn test_uncontested_pot_is_recorded() {
+    let mut game = new_game();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    assert_eq!(game.state.stage, GameStage::Showdown);
+    game.resolve_showdown();
+
+    let events = game.current_hand();
+    assert_eq!(
+        events[events.len() - 2..],
+        [
+            HandEvent::StreetStarted {
+                stage: GameStage::Showdown
+            },
+            HandEvent::PotAwarded {
+                player_id: "c".to_string(),
+                amount: 30,
+                pot: 0
+            },
+        ]
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e, HandEvent::HandShown { .. }))
+    );
+    assert_eq!(split_hands(&game.history).len(), 1);
+}
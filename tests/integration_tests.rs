@@ -1,4 +1,8 @@
+mod common;
+
+use common::*;
 use rstest::rstest;
+use texas_engine::view::LegalAction;
 use texas_engine::*;
 
 #[test]
@@ -89,8 +93,16 @@ fn test_preflop_actions(
             dealer_position: 0,
             small_blind: 10,
             big_blind: 20,
+            ante: 0,
+            variant: GameVariant::Holdem,
+            extra_hole_cards: Vec::new(),
+            bomb_pot: false,
+            extra_boards: Vec::new(),
+            betting: BettingStructure::NoLimit,
+            bets_this_round: 0,
         },
         deck: rules::create_shuffled_deck(),
+        history: Vec::new(),
     };
 
     // Alice行动
@@ -260,8 +272,16 @@ fn test_showdown_split_sidepot() {
             small_blind: 10,
             big_blind: 20,
             stage: GameStage::Showdown,
+            ante: 0,
+            variant: GameVariant::Holdem,
+            extra_hole_cards: Vec::new(),
+            bomb_pot: false,
+            extra_boards: Vec::new(),
+            betting: BettingStructure::NoLimit,
+            bets_this_round: 0,
         },
         deck: vec![],
+        history: Vec::new(),
     };
 
     game.resolve_showdown();
@@ -273,3 +293,51 @@ fn test_showdown_split_sidepot() {
     // 边池2（100）C自留 -> 100
     assert_eq!(winnings, vec![300, 200, 100]);
 }
+
+#[test]
+fn test_all_in_runs_out_the_board() {
+    let players = vec![player("a", 100), player("b", 500), player("c", 500)];
+    let mut game = TexasHoldem::new(players, 10, 20);
+
+    // a 全下，b 跟注，c 弃牌：只剩 b 有筹码，不再下注，直接发完公共牌
+    game.handle_action(PlayerAction::Raise(80)).unwrap();
+    game.handle_action(PlayerAction::Call).unwrap();
+    assert_eq!(game.state.stage, GameStage::PreFlop);
+    game.handle_action(PlayerAction::Fold).unwrap();
+    assert_eq!(game.state.stage, GameStage::Showdown);
+    assert_eq!(game.state.community_cards.len(), 5);
+
+    game.resolve_showdown();
+    let total: u32 = game.state.players.iter().map(|p| p.chips).sum();
+    assert_eq!(total, 1100);
+}
+
+#[test]
+fn test_short_call_and_minimum_raise() {
+    let players = vec![player("a", 1000), player("b", 500), player("c", 60)];
+    let mut game = TexasHoldem::new(players, 10, 20);
+
+    // 加注至少一个大盲
+    assert_eq!(
+        game.handle_action(PlayerAction::Raise(19)),
+        Err(GameError::InvalidAction)
+    );
+    game.handle_action(PlayerAction::Raise(80)).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+
+    // c 只有 40 不够跟注 80，全下跟注
+    assert_eq!(
+        game.state.legal_actions(2),
+        vec![LegalAction::Fold, LegalAction::Call { amount: 40 }]
+    );
+    game.handle_action(PlayerAction::Call).unwrap();
+    assert_eq!(game.state.players[2].chips, 0);
+    assert_eq!(game.state.players[2].total_bet_in_hand, 60);
+    assert_eq!(game.state.stage, GameStage::Showdown);
+
+    // 超出 c 的 40 退还给 a
+    game.resolve_showdown();
+    let total: u32 = game.state.players.iter().map(|p| p.chips).sum();
+    assert_eq!(total, 1560);
+    assert!(game.state.players[0].chips >= 940);
+}
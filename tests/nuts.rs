@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use texas_engine::nuts::*;
+use texas_engine::*;
+
+fn rainbow_board() -> Vec<Card> {
+    parse_cards("Kh 7d 2c 9s 4h").unwrap()
+}
+
+#[test]
+fn test_nut_ranking_order() {
+    let tiers = nut_ranking(&rainbow_board()).unwrap();
+
+    // 没有顺子和同花，坚果是三条 K
+    assert_eq!(tiers[0].rank, HandRank::ThreeOfAKind);
+    assert_eq!(tiers[0].combos.len(), 3);
+    assert!(
+        tiers[0]
+            .combos
+            .iter()
+            .all(|(c1, c2)| c1.rank == Rank::King && c2.rank == Rank::King)
+    );
+    assert!(tiers.windows(2).all(|w| w[0].strength > w[1].strength));
+    assert_eq!(
+        tiers.iter().map(|t| t.combos.len()).sum::<usize>(),
+        47 * 46 / 2
+    );
+}
+
+#[test]
+fn test_rank_hand() {
+    let board = rainbow_board();
+    let set_of_kings = (card("Kc"), card("Ks"));
+    let ranking = rank_hand(set_of_kings, &board).unwrap();
+    assert!(ranking.is_nuts());
+    assert_eq!(ranking.better_combos, 0);
+    assert_eq!(ranking.tied_combos, 2);
+    let total = 47 * 46 / 2;
+    assert_eq!(ranking.total_combos, total);
+    assert!((ranking.percentile() - (total - 3) as f64 * 100.0 / (total - 1) as f64).abs() < 1e-9);
+
+    // 三条 9 排在三条 K 之后、三条 7 之前
+    let set_of_nines = (card("9c"), card("9h"));
+    let ranking = rank_hand(set_of_nines, &board).unwrap();
+    assert_eq!(ranking.tier, 2);
+    assert_eq!(ranking.better_combos, 3);
+    assert_eq!(ranking.tied_combos, 2);
+}
+
+#[test]
+fn test_straight_flush_board() {
+    let board = parse_cards("9s Ts Js").unwrap();
+    let tiers = nut_ranking(&board).unwrap();
+    // 皇家同花顺还差三张，坚果是 K 高同花顺
+    assert_eq!(tiers[0].rank, HandRank::StraightFlush);
+    assert_eq!(tiers[0].combos, vec![(card("Ks"), card("Qs"))]);
+    assert_eq!(tiers[1].rank, HandRank::StraightFlush);
+    assert_eq!(tiers[1].combos, vec![(card("Qs"), card("8s"))]);
+}
+
+#[test]
+fn test_invalid_input() {
+    let board = rainbow_board();
+    let hand = (card("Kh"), card("2s"));
+    assert_eq!(rank_hand(hand, &board), Err(GameError::InvalidCards));
+    assert_eq!(nut_ranking(&board[..2]), Err(GameError::InvalidCards));
+}
@@ -0,0 +1,349 @@
+use texas_engine::history::*;
+use texas_engine::ohh::*;
+use texas_engine::replay::*;
+use texas_engine::*;
+
+fn seat(id: &str, name: &str) -> SeatInfo {
+    SeatInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        chips: 1000,
+    }
+}
+
+fn chips(game: &TexasHoldem) -> Vec<u32> {
+    game.state.players.iter().map(|p| p.chips).collect()
+}
+
+/// 打完一手到摊牌的牌：a 加注，b 跟注，c 弃牌，翻牌 b 下注 a 跟注，之后都过牌
+fn showdown_hand() -> TexasHoldem {
+    let setup = HandSetup {
+        players: vec![seat("a", "Alice"), seat("b", "Bob"), seat("c", "Carol")],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    let actions = [
+        ("a", PlayerAction::Raise(40)),
+        ("b", PlayerAction::Call),
+        ("c", PlayerAction::Fold),
+        ("b", PlayerAction::Bet(100)),
+        ("a", PlayerAction::Call),
+        ("b", PlayerAction::Check),
+        ("a", PlayerAction::Check),
+        ("b", PlayerAction::Check),
+        ("a", PlayerAction::Check),
+    ];
+    for (player_id, action) in actions {
+        game.handle_action_for(player_id, action).unwrap();
+    }
+    game.resolve_showdown();
+    game
+}
+
+#[test]
+fn test_export_rounds_and_pots() {
+    let game = showdown_hand();
+    let ohh = OpenHandHistory::from_history(game.current_hand(), &OhhOptions::default()).unwrap();
+
+    assert_eq!(ohh.dealer_seat, 1);
+    assert_eq!(ohh.players[1].name, "b");
+    assert_eq!(ohh.players[1].display.as_deref(), Some("Bob"));
+    let streets: Vec<_> = ohh.rounds.iter().map(|r| r.street.as_str()).collect();
+    assert_eq!(
+        streets,
+        vec!["Preflop", "Flop", "Turn", "River", "Showdown"]
+    );
+    assert_eq!(ohh.rounds[1].cards, parse_cards("Qs 9h 5c").unwrap());
+
+    let preflop: Vec<_> = ohh.rounds[0]
+        .actions
+        .iter()
+        .map(|a| (a.player_id, a.action.as_str(), a.amount))
+        .collect();
+    assert_eq!(
+        preflop,
+        vec![
+            (1, "Post SB", 10.0),
+            (2, "Post BB", 20.0),
+            (0, "Dealt Cards", 0.0),
+            (1, "Dealt Cards", 0.0),
+            (2, "Dealt Cards", 0.0),
+            (0, "Raise", 60.0),
+            (1, "Call", 50.0),
+            (2, "Fold", 0.0),
+        ]
+    );
+    let numbers: Vec<_> = ohh
+        .rounds
+        .iter()
+        .flat_map(|r| r.actions.iter().map(|a| a.action_number))
+        .collect();
+    assert_eq!(numbers, (1..=numbers.len()).collect::<Vec<_>>());
+
+    assert_eq!(ohh.pots.len(), 1);
+    assert_eq!(ohh.pots[0].amount, 340.0);
+    assert_eq!(ohh.pots[0].player_wins[0].player_id, 0);
+}
+
+#[test]
+fn test_json_round_trip_and_replay() {
+    let game = showdown_hand();
+    let ohh = OpenHandHistory::from_history(game.current_hand(), &OhhOptions::default()).unwrap();
+    let json = ohh.to_json().unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["ohh"]["spec_version"], OHH_SPEC_VERSION);
+    assert_eq!(value["ohh"]["bet_limit"]["bet_type"], "NL");
+    assert_eq!(
+        value["ohh"]["rounds"][0]["actions"][2]["cards"],
+        serde_json::json!(["As", "Ad"])
+    );
+
+    let parsed = OpenHandHistory::from_json(&json).unwrap();
+    assert_eq!(parsed, ohh);
+
+    let mut replay = parsed.replay().unwrap();
+    replay.seek(replay.actions.len()).unwrap();
+    assert_eq!(
+        replay.game().state.community_cards,
+        game.state.community_cards
+    );
+    let mut result = replay.setup.start().unwrap();
+    for recorded in &replay.actions {
+        result
+            .handle_action_for(&recorded.player_id, recorded.action.clone())
+            .unwrap();
+    }
+    result.resolve_showdown();
+    assert_eq!(chips(&result), chips(&game));
+}
+
+#[test]
+fn test_hero_export_keeps_other_cards_hidden() {
+    let game = showdown_hand();
+    let options = OhhOptions {
+        hero: Some("c".to_string()),
+        ..Default::default()
+    };
+    let ohh = OpenHandHistory::from_history(game.current_hand(), &options).unwrap();
+    assert_eq!(ohh.hero_player_id, Some(2));
+    let dealt: Vec<_> = ohh.rounds[0]
+        .actions
+        .iter()
+        .filter(|a| a.action == "Dealt Cards")
+        .map(|a| a.player_id)
+        .collect();
+    assert_eq!(dealt, vec![2]);
+
+    // 摊牌亮出的底牌仍然足以重放整手牌
+    let setup = ohh.setup().unwrap();
+    assert_eq!(
+        setup.start().unwrap().state.players[0].cards,
+        game.state.players[0].cards
+    );
+}
+
+#[test]
+fn test_import_minimal_json() {
+    let json = r#"{"ohh": {
+        "spec_version": "1.4.6",
+        "site_name": "notebook",
+        "network_name": "notebook",
+        "internal_version": "0",
+        "tournament": false,
+        "game_number": "7",
+        "start_date_utc": "2024-01-31T20:15:00Z",
+        "table_name": "T",
+        "game_type": "Holdem",
+        "bet_limit": {"bet_type": "NL"},
+        "table_size": 6,
+        "currency": "",
+        "dealer_seat": 3,
+        "small_blind_amount": 1,
+        "big_blind_amount": 2,
+        "ante_amount": 0,
+        "players": [
+            {"id": 10, "seat": 1, "name": "x", "starting_stack": 100},
+            {"id": 11, "seat": 3, "name": "y", "starting_stack": 100}
+        ],
+        "rounds": [
+            {"id": 0, "street": "Preflop", "actions": [
+                {"action_number": 1, "player_id": 10, "action": "Post SB", "amount": 1},
+                {"action_number": 2, "player_id": 11, "action": "Post BB", "amount": 2},
+                {"action_number": 3, "player_id": 10, "action": "Raise", "amount": 6},
+                {"action_number": 4, "player_id": 11, "action": "Fold"}
+            ]}
+        ],
+        "pots": []
+    }}"#;
+    let ohh = OpenHandHistory::from_json(json).unwrap();
+    let setup = ohh.setup().unwrap();
+    assert_eq!(setup.dealer_position, 1);
+    assert_eq!(setup.players[0].id, "x");
+    assert_eq!(
+        ohh.recorded_actions().unwrap(),
+        vec![
+            RecordedAction {
+                player_id: "x".to_string(),
+                action: PlayerAction::Raise(4),
+            },
+            RecordedAction {
+                player_id: "y".to_string(),
+                action: PlayerAction::Fold,
+            },
+        ]
+    );
+
+    let mut replay = ohh.replay().unwrap();
+    while replay.step_forward().unwrap() {}
+    assert_eq!(replay.game().state.stage, GameStage::Showdown);
+
+    let limit = json.replace(r#""bet_type": "NL""#, r#""bet_type": "FL""#);
+    let limit = OpenHandHistory::from_json(&limit).unwrap();
+    assert_eq!(
+        limit.setup().unwrap().config.betting,
+        BettingStructure::FixedLimit
+    );
+    let unknown = json.replace(r#""bet_type": "NL""#, r#""bet_type": "XX""#);
+    let unknown = OpenHandHistory::from_json(&unknown).unwrap();
+    assert_eq!(unknown.setup(), Err(GameError::InvalidAction));
+    assert!(OpenHandHistory::from_json(&json.replace("\"Holdem\"", "1")).is_err());
+}
+
+#[test]
+fn test_export_one_pot_per_side_pot() {
+    // a 只有 200 筹码全下，b 和 c 跟注后在翻牌继续下注：a 赢主池，b 赢边池
+    let mut players = vec![seat("a", "Alice"), seat("b", "Bob"), seat("c", "Carol")];
+    players[0].chips = 200;
+    let setup = HandSetup {
+        players,
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    let actions = [
+        ("a", PlayerAction::Raise(180)),
+        ("b", PlayerAction::Call),
+        ("c", PlayerAction::Call),
+        ("b", PlayerAction::Bet(100)),
+        ("c", PlayerAction::Call),
+        ("b", PlayerAction::Check),
+        ("c", PlayerAction::Check),
+        ("b", PlayerAction::Check),
+        ("c", PlayerAction::Check),
+    ];
+    for (player_id, action) in actions {
+        game.handle_action_for(player_id, action).unwrap();
+    }
+    game.resolve_showdown();
+
+    let ohh = OpenHandHistory::from_history(game.current_hand(), &OhhOptions::default()).unwrap();
+    let pots: Vec<_> = ohh
+        .pots
+        .iter()
+        .map(|p| {
+            let wins: Vec<_> = p
+                .player_wins
+                .iter()
+                .map(|w| (w.player_id, w.win_amount))
+                .collect();
+            (p.number, p.amount, wins)
+        })
+        .collect();
+    assert_eq!(
+        pots,
+        vec![(0, 600.0, vec![(0, 600.0)]), (1, 200.0, vec![(1, 200.0)])]
+    );
+}
+
+#[test]
+fn test_bomb_pot_round_trip() {
+    let setup = HandSetup {
+        players: vec![seat("a", "Alice"), seat("b", "Bob"), seat("c", "Carol")],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ante: 50,
+            bomb_pot: true,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    assert_eq!(game.state.stage, GameStage::Flop);
+    game.handle_action(PlayerAction::Bet(100)).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    game.resolve_showdown();
+
+    let ohh = OpenHandHistory::from_history(game.current_hand(), &OhhOptions::default()).unwrap();
+    assert_eq!(ohh.flags, vec![OHH_BOMB_POT_FLAG.to_string()]);
+    let parsed = OpenHandHistory::from_json(&ohh.to_json().unwrap()).unwrap();
+    assert!(parsed.setup().unwrap().config.bomb_pot);
+
+    let replay = parsed.replay().unwrap();
+    let mut result = replay.setup.start().unwrap();
+    for recorded in &replay.actions {
+        result
+            .handle_action_for(&recorded.player_id, recorded.action.clone())
+            .unwrap();
+    }
+    result.resolve_showdown();
+    assert_eq!(chips(&result), chips(&game));
+
+    // 没有 flag 的同一手牌按普通牌局导入，开局停在翻牌前
+    let plain = OpenHandHistory {
+        flags: Vec::new(),
+        ..parsed
+    };
+    let start = plain.setup().unwrap().start().unwrap();
+    assert_eq!(start.state.stage, GameStage::PreFlop);
+}
+
+#[test]
+fn test_export_rejects_bets_beyond_stack() {
+    let game = showdown_hand();
+    let events = game.current_hand();
+    let export =
+        |events: &[HandEvent]| OpenHandHistory::from_history(events, &OhhOptions::default());
+
+    // 前注、盲注和行动投入的筹码超过记录的筹码量时报错，而不是溢出
+    let mut short = events.to_vec();
+    if let HandEvent::HandStarted { players, .. } = &mut short[0] {
+        players.iter_mut().for_each(|p| p.chips = 15);
+    }
+    assert_eq!(export(&short), Err(GameError::InvalidAction));
+
+    let mut ante = events.to_vec();
+    ante.insert(
+        1,
+        HandEvent::AntePosted {
+            player_id: "a".to_string(),
+            amount: 5000,
+        },
+    );
+    assert_eq!(export(&ante), Err(GameError::InvalidAction));
+
+    let mut raised = events.to_vec();
+    raised.insert(
+        raised.len() - 2,
+        HandEvent::ActionTaken {
+            player_id: "a".to_string(),
+            action: PlayerAction::Raise(2000),
+            amount: 2000,
+        },
+    );
+    assert_eq!(export(&raised), Err(GameError::InvalidAction));
+}
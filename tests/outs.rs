@@ -0,0 +1,112 @@
+mod common;
+
+use common::*;
+use texas_engine::outs::*;
+use texas_engine::*;
+
+#[test]
+fn test_flush_draw_against_overpair() {
+    let hole = (card("Ah"), card("Kh"));
+    let board = parse_cards("2h 7h 9c").unwrap();
+    let opponent = (card("Qs"), card("Qc"));
+
+    let analysis = analyze_outs(hole, &board, &[opponent]).unwrap();
+    assert_eq!(analysis.current, HandRank::HighCard);
+    assert_eq!(analysis.draws, vec![DrawType::FlushDraw]);
+    // 9 张红心 + 3 张 A + 3 张 K
+    assert_eq!(analysis.count(), 15);
+    assert_eq!(
+        analysis
+            .outs
+            .iter()
+            .filter(|o| o.hand_rank == HandRank::Flush)
+            .count(),
+        9
+    );
+    assert_eq!(analysis.unseen, 45);
+    assert_eq!(analysis.rule_of_two, 30.0);
+    assert_eq!(analysis.rule_of_four, Some(60.0));
+    assert!((analysis.next_card_odds() - 15.0 * 100.0 / 45.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_outs_without_opponents_improve_hand_class() {
+    let hole = (card("9s"), card("8d"));
+    let board = parse_cards("7c 6h 2s Kd").unwrap();
+
+    let analysis = analyze_outs(hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws, vec![DrawType::OpenEnded]);
+    assert_eq!(analysis.rule_of_four, None);
+    // 8 张顺子补牌，另外配对底牌 2 × 3 = 6 张，公共牌成对不算
+    assert_eq!(
+        analysis
+            .outs
+            .iter()
+            .filter(|o| o.hand_rank == HandRank::Straight)
+            .count(),
+        8
+    );
+    assert_eq!(analysis.count(), 8 + 6);
+}
+
+#[test]
+fn test_board_only_upgrades_are_not_outs() {
+    let hole = (card("As"), card("Kd"));
+    let board = parse_cards("2h 5h 9h Jh").unwrap();
+    let opponent = (card("Qc"), card("8c"));
+
+    for opponents in [&[][..], &[opponent][..]] {
+        let analysis = analyze_outs(hole, &board, opponents).unwrap();
+        assert_eq!(analysis.current, HandRank::HighCard);
+        // 只有非红心的 A 或 K 算补牌；公共牌成对或成同花时对手一样拿到
+        assert_eq!(analysis.count(), 4);
+        assert!(analysis.outs.iter().all(|o| {
+            matches!(o.card.rank, Rank::Ace | Rank::King) && o.card.suit != Suit::Hearts
+        }));
+    }
+}
+
+#[test]
+fn test_gutshot() {
+    let hole = (card("9s"), card("8d"));
+    let board = parse_cards("6c 5h Ks").unwrap();
+
+    let analysis = analyze_outs(hole, &board, &[]).unwrap();
+    assert!(analysis.draws.contains(&DrawType::Gutshot));
+    assert!(!analysis.draws.contains(&DrawType::OpenEnded));
+}
+
+#[test]
+fn test_board_straight_draw_is_not_players() {
+    let hole = (card("As"), card("Kd"));
+    let board = parse_cards("5c 6h 7s 8d").unwrap();
+
+    let analysis = analyze_outs(hole, &board, &[]).unwrap();
+    assert!(analysis.draws.is_empty());
+}
+
+#[test]
+fn test_backdoor_draws() {
+    let hole = (card("Ah"), card("Kh"));
+    let board = parse_cards("Qh 7c 2d").unwrap();
+
+    let analysis = analyze_outs(hole, &board, &[]).unwrap();
+    assert_eq!(
+        analysis.draws,
+        vec![DrawType::BackdoorFlush, DrawType::BackdoorStraight]
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let hole = (card("Ah"), card("Kh"));
+    let board = parse_cards("Ah 7c 2d").unwrap();
+    assert_eq!(
+        analyze_outs(hole, &board, &[]),
+        Err(GameError::InvalidCards)
+    );
+    assert_eq!(
+        analyze_outs(hole, &board[1..], &[]),
+        Err(GameError::InvalidCards)
+    );
+}
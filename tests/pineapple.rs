@@ -0,0 +1,119 @@
+mod common;
+
+use common::*;
+use texas_engine::*;
+
+/// 当前玩家手中的三张牌
+fn current_three_cards(game: &TexasHoldem) -> (Card, Card, Card) {
+    let index = game.state.current_player_index;
+    let (card1, card2) = game.state.players[index].cards.unwrap();
+    (card1, card2, game.state.extra_hole_cards[index].unwrap())
+}
+
+#[test]
+fn test_pineapple_deals_three_cards() {
+    let game = TexasHoldem::with_variant(
+        vec![player("a", 1000), player("b", 1000)],
+        10,
+        20,
+        GameVariant::Pineapple,
+    );
+
+    assert_eq!(game.state.variant, GameVariant::Pineapple);
+    assert!(game.state.extra_hole_cards.iter().all(|c| c.is_some()));
+    assert_eq!(game.deck.len(), 52 - 6);
+    assert!(!game.awaiting_discard());
+}
+
+#[test]
+fn test_pineapple_discard_before_flop() {
+    let mut game = TexasHoldem::with_variant(
+        vec![player("a", 1000), player("b", 1000)],
+        10,
+        20,
+        GameVariant::Pineapple,
+    );
+
+    // 弃牌阶段之前不能弃牌
+    let (card1, _, _) = current_three_cards(&game);
+    assert_eq!(
+        game.handle_action(PlayerAction::Discard(card1)),
+        Err(GameError::InvalidAction)
+    );
+
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+
+    // 翻牌前下注结束，等待弃牌
+    assert!(game.awaiting_discard());
+    assert_eq!(game.state.stage, GameStage::PreFlop);
+    assert!(game.state.community_cards.is_empty());
+    assert_eq!(
+        game.handle_action(PlayerAction::Check),
+        Err(GameError::InvalidAction)
+    );
+
+    // 不能弃掉不在手中的牌
+    let (card1, card2, extra) = current_three_cards(&game);
+    let foreign = game.deck[0];
+    assert_eq!(
+        game.handle_action(PlayerAction::Discard(foreign)),
+        Err(GameError::InvalidAction)
+    );
+
+    let first = game.state.current_player_index;
+    game.handle_action(PlayerAction::Discard(card1)).unwrap();
+    assert_eq!(game.state.players[first].cards, Some((extra, card2)));
+    assert_eq!(game.state.extra_hole_cards[first], None);
+    assert_eq!(game.state.stage, GameStage::PreFlop);
+
+    let (_, _, extra) = current_three_cards(&game);
+    game.handle_action(PlayerAction::Discard(extra)).unwrap();
+
+    assert!(!game.awaiting_discard());
+    assert_eq!(game.state.stage, GameStage::Flop);
+    assert_eq!(game.state.community_cards.len(), 3);
+}
+
+#[test]
+fn test_crazy_pineapple_discard_after_flop() {
+    let mut game = TexasHoldem::with_variant(
+        vec![player("a", 1000), player("b", 1000)],
+        10,
+        20,
+        GameVariant::CrazyPineapple,
+    );
+
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+
+    // 翻牌前不弃牌，直接进入翻牌圈
+    assert!(!game.awaiting_discard());
+    assert_eq!(game.state.stage, GameStage::Flop);
+
+    game.handle_action(PlayerAction::Check).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+
+    assert!(game.awaiting_discard());
+    assert_eq!(game.state.stage, GameStage::Flop);
+
+    for _ in 0..2 {
+        let (card1, _, _) = current_three_cards(&game);
+        game.handle_action(PlayerAction::Discard(card1)).unwrap();
+    }
+
+    assert_eq!(game.state.stage, GameStage::Turn);
+    assert_eq!(game.state.community_cards.len(), 4);
+}
+
+#[test]
+fn test_holdem_rejects_discard() {
+    let mut game = TexasHoldem::new(vec![player("a", 1000), player("b", 1000)], 10, 20);
+    let (card1, _) = game.state.players[0].cards.unwrap();
+
+    assert!(game.state.extra_hole_cards.iter().all(|c| c.is_none()));
+    assert_eq!(
+        game.handle_action(PlayerAction::Discard(card1)),
+        Err(GameError::InvalidAction)
+    );
+}
@@ -0,0 +1,350 @@
+mod common;
+
+use common::*;
+use texas_engine::history::*;
+use texas_engine::pokerstars::*;
+use texas_engine::replay::*;
+use texas_engine::*;
+
+fn seat(id: &str, name: &str) -> SeatInfo {
+    SeatInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        chips: 1000,
+    }
+}
+
+/// 按给定牌序和行动打完一手牌
+fn play(deck: &str, actions: &[(&str, PlayerAction)]) -> TexasHoldem {
+    let setup = HandSetup {
+        players: vec![seat("a", "Alice"), seat("b", "Bob"), seat("c", "Carol")],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards(deck).unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    for (player_id, action) in actions {
+        game.handle_action_for(player_id, action.clone()).unwrap();
+    }
+    game.resolve_showdown();
+    game
+}
+
+#[test]
+fn test_export_showdown_hand() {
+    let game = play(
+        "As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s",
+        &[
+            ("a", PlayerAction::Raise(40)),
+            ("b", PlayerAction::Call),
+            ("c", PlayerAction::Fold),
+            ("b", PlayerAction::Bet(100)),
+            ("a", PlayerAction::Call),
+            ("b", PlayerAction::Check),
+            ("a", PlayerAction::Check),
+            ("b", PlayerAction::Check),
+            ("a", PlayerAction::Check),
+        ],
+    );
+    let options = ExportOptions {
+        hand_id: 42,
+        table_name: "Home".to_string(),
+        max_seats: 6,
+        timestamp: "2024/01/31 20:15:00 ET".to_string(),
+        hero: Some("a".to_string()),
+    };
+
+    let expected = "\
+PokerStars Hand #42: Hold'em No Limit (10/20) - 2024/01/31 20:15:00 ET
+Table 'Home' 6-max Seat #1 is the button
+Seat 1: Alice (1000 in chips)
+Seat 2: Bob (1000 in chips)
+Seat 3: Carol (1000 in chips)
+Bob: posts small blind 10
+Carol: posts big blind 20
+*** HOLE CARDS ***
+Dealt to Alice [As Ad]
+Alice: raises 40 to 60
+Bob: calls 50
+Carol: folds
+*** FLOP *** [Qs 9h 5c]
+Bob: bets 100
+Alice: calls 100
+*** TURN *** [Qs 9h 5c] [Jd]
+Bob: checks
+Alice: checks
+*** RIVER *** [Qs 9h 5c Jd] [8s]
+Bob: checks
+Alice: checks
+*** SHOW DOWN ***
+Alice: shows [As Ad] (a pair of Aces)
+Bob: shows [Kh Kd] (a pair of Kings)
+Alice collected 340 from pot
+*** SUMMARY ***
+Total pot 340 | Rake 0
+Board [Qs 9h 5c Jd 8s]
+Seat 1: Alice (button) showed [As Ad] and won (340) with a pair of Aces
+Seat 2: Bob (small blind) showed [Kh Kd] and lost with a pair of Kings
+Seat 3: Carol (big blind) folded before Flop
+";
+    assert_eq!(
+        export_hand(game.current_hand(), &options).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_export_uncontested_hand() {
+    let game = play(
+        "As Ad Kh Kd 7c 2d",
+        &[
+            ("a", PlayerAction::Fold),
+            ("b", PlayerAction::Raise(980)),
+            ("c", PlayerAction::Fold),
+        ],
+    );
+    let text = export_hand(game.current_hand(), &ExportOptions::default()).unwrap();
+
+    assert!(
+        text.contains("Dealt to Alice [As Ad]\nDealt to Bob [Kh Kd]\nDealt to Carol [7c 2d]\n")
+    );
+    assert!(text.contains("Bob: raises 980 to 1000 and is all-in\n"));
+    assert!(!text.contains("SHOW DOWN"));
+    assert!(text.contains("Seat 2: Bob (small blind) collected (1020)\n"));
+    assert!(!text.contains("Board"));
+}
+
+#[test]
+fn test_export_returned_bet() {
+    // Bob 只有 100，跟注 Carol 的下注后全下，Carol 多下的 420 退还
+    let setup = HandSetup {
+        players: vec![
+            seat("a", "Alice"),
+            SeatInfo {
+                chips: 100,
+                ..seat("b", "Bob")
+            },
+            seat("c", "Carol"),
+        ],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    let actions = [
+        ("a", PlayerAction::Fold),
+        ("b", PlayerAction::Call),
+        ("c", PlayerAction::Check),
+        ("b", PlayerAction::Check),
+        ("c", PlayerAction::Bet(500)),
+        ("b", PlayerAction::Call),
+    ];
+    for (player_id, action) in actions {
+        game.handle_action_for(player_id, action).unwrap();
+    }
+    game.resolve_showdown();
+    let text = export_hand(game.current_hand(), &ExportOptions::default()).unwrap();
+
+    assert!(text.contains(
+        "Bob: calls 80 and is all-in\n\
+         *** TURN *** [Qs 9h 5c] [Jd]\n\
+         *** RIVER *** [Qs 9h 5c Jd] [8s]\n\
+         Uncalled bet (420) returned to Carol\n\
+         *** SHOW DOWN ***\n"
+    ));
+    assert!(text.contains("Bob collected 200 from pot\n"));
+    assert!(!text.contains("Carol collected"));
+    assert!(text.contains("Total pot 200 | Rake 0\n"));
+    assert!(text.contains("Seat 3: Carol (big blind) showed [7c 2d] and lost with "));
+
+    // 导入时忽略退还的提示行
+    let imported = parse_hand(&text).unwrap();
+    assert_eq!(imported.collected, vec![("Bob".to_string(), 200)]);
+}
+
+#[test]
+fn test_export_multiple_hands() {
+    let game = play(
+        "As Ad Kh Kd 7c 2d",
+        &[("a", PlayerAction::Fold), ("b", PlayerAction::Fold)],
+    );
+    let mut events = game.current_hand().to_vec();
+    events.extend_from_slice(game.current_hand());
+
+    let text = export_hands(&events, &ExportOptions::default()).unwrap();
+    assert!(text.starts_with("PokerStars Hand #1: "));
+    assert!(text.contains("\n\n\nPokerStars Hand #2: "));
+    assert_eq!(
+        export_hand(&events[1..], &ExportOptions::default()),
+        Err(GameError::InvalidAction)
+    );
+}
+
+#[test]
+fn test_export_rejects_bets_beyond_stack() {
+    let game = play(
+        "As Ad Kh Kd 7c 2d",
+        &[("a", PlayerAction::Fold), ("b", PlayerAction::Fold)],
+    );
+    let events = game.current_hand();
+    let with_chips = |chips: u32, events: &[HandEvent]| {
+        let mut events = events.to_vec();
+        if let HandEvent::HandStarted { players, .. } = &mut events[0] {
+            players.iter_mut().for_each(|p| p.chips = chips);
+        }
+        events
+    };
+
+    // 盲注和行动投入的筹码超过记录的筹码量时报错，而不是溢出
+    assert_eq!(
+        export_hand(&with_chips(15, events), &ExportOptions::default()),
+        Err(GameError::InvalidAction)
+    );
+    let mut raised = events.to_vec();
+    raised.insert(
+        raised.len() - 2,
+        HandEvent::ActionTaken {
+            player_id: "c".to_string(),
+            action: PlayerAction::Raise(2000),
+            amount: 2000,
+        },
+    );
+    assert_eq!(
+        export_hand(&raised, &ExportOptions::default()),
+        Err(GameError::InvalidAction)
+    );
+}
+
+const CASH_HAND: &str = "\
+PokerStars Hand #230000000001: Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 20:15:00 ET
+Table 'Alcyone II' 6-max Seat #2 is the button
+Seat 1: Dana ($2 in chips)
+Seat 2: Eve ($2.50 in chips)
+Seat 4: Finn ($1.80 in chips)
+Seat 5: Gus ($3 in chips)
+Finn: posts small blind $0.01
+Gus: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to Dana [Th Tc]
+Dana: raises $0.04 to $0.06
+Eve: folds
+Finn: calls $0.05
+Gus: folds
+*** FLOP *** [2s 7d Kc]
+Finn: checks
+Dana: bets $0.10
+Finn: folds
+Uncalled bet ($0.10) returned to Dana
+Dana collected $0.14 from pot
+Dana said, \"gg\"
+*** SUMMARY ***
+Total pot $0.14 | Rake $0
+Board [2s 7d Kc]
+Seat 1: Dana collected ($0.14)
+Seat 2: Eve (button) folded before Flop (didn't bet)
+Seat 4: Finn (small blind) folded on the Flop
+Seat 5: Gus (big blind) folded before Flop
+";
+
+#[test]
+fn test_import_cash_hand() {
+    let hand = parse_hand(CASH_HAND).unwrap();
+    assert_eq!(hand.hand_id, 230000000001);
+    assert_eq!(hand.table_name, "Alcyone II");
+    assert_eq!(hand.max_seats, 6);
+    assert_eq!(hand.button_seat, 2);
+    assert_eq!((hand.small_blind, hand.big_blind), (1, 2));
+    let stacks: Vec<_> = hand.seats.iter().map(|s| (s.seat, s.chips)).collect();
+    assert_eq!(stacks, vec![(1, 200), (2, 250), (4, 180), (5, 300)]);
+    assert_eq!(hand.board, parse_cards("2s 7d Kc").unwrap());
+    assert_eq!(hand.collected, vec![("Dana".to_string(), 14)]);
+    assert_eq!(hand.actions.len(), 7);
+    assert_eq!(
+        hand.actions[0],
+        ImportedAction {
+            player: "Dana".to_string(),
+            stage: GameStage::PreFlop,
+            action: PlayerAction::Raise(4),
+        }
+    );
+    assert_eq!(hand.actions[5].action, PlayerAction::Bet(10));
+
+    // 在引擎中重放：底牌和公共牌与记录一致，Dana 最终多出 8
+    let game = hand.play().unwrap();
+    assert_eq!(game.state.players[0].cards, Some((card("Th"), card("Tc"))));
+    assert_eq!(game.state.community_cards, hand.board);
+    let chips: Vec<_> = game.state.players.iter().map(|p| p.chips).collect();
+    assert_eq!(chips, vec![208, 250, 174, 298]);
+
+    let mut replay = hand.replay().unwrap();
+    replay.seek(4).unwrap();
+    assert_eq!(replay.game().state.stage, GameStage::Flop);
+}
+
+#[test]
+fn test_import_exported_hand() {
+    let game = play(
+        "As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s",
+        &[
+            ("a", PlayerAction::Raise(40)),
+            ("b", PlayerAction::Call),
+            ("c", PlayerAction::Fold),
+            ("b", PlayerAction::Bet(100)),
+            ("a", PlayerAction::Call),
+            ("b", PlayerAction::Check),
+            ("a", PlayerAction::Check),
+            ("b", PlayerAction::Check),
+            ("a", PlayerAction::Check),
+        ],
+    );
+    let text = export_hands(game.current_hand(), &ExportOptions::default()).unwrap();
+    let hands = parse_hands(&text).unwrap();
+    assert_eq!(hands.len(), 1);
+    let hand = &hands[0];
+    assert_eq!(hand.hole_cards.len(), 3);
+    assert_eq!(hand.collected, vec![("Alice".to_string(), 340)]);
+
+    let replayed = hand.play().unwrap();
+    assert_eq!(replayed.state.community_cards, game.state.community_cards);
+    let chips = |g: &TexasHoldem| g.state.players.iter().map(|p| p.chips).collect::<Vec<_>>();
+    assert_eq!(chips(&replayed), chips(&game));
+}
+
+#[test]
+fn test_import_errors_report_line() {
+    let error = |text: &str| parse_hand(text).unwrap_err();
+
+    let omaha = CASH_HAND.replacen("Hold'em No Limit", "Omaha Pot Limit", 1);
+    assert_eq!(error(&omaha).line, 1);
+
+    let run_twice = CASH_HAND.replacen("*** FLOP ***", "*** FIRST FLOP ***", 1);
+    let e = error(&run_twice);
+    assert_eq!(e.line, 15);
+    assert_eq!(e.to_string(), "line 15: unsupported section: FIRST FLOP");
+
+    let both_blinds =
+        CASH_HAND.replacen("posts big blind $0.02", "posts small & big blinds $0.03", 1);
+    assert_eq!(error(&both_blinds).line, 8);
+
+    let bad_card = CASH_HAND.replacen("[Th Tc]", "[Th Xc]", 1);
+    assert_eq!(error(&bad_card).line, 10);
+
+    let sitting_out = CASH_HAND.replacen("($3 in chips)", "($3 in chips) is sitting out", 1);
+    assert_eq!(error(&sitting_out).line, 6);
+
+    // 小盲不在庄家下家（如单挑时由按钮位下小盲）引擎不支持
+    let heads_up = CASH_HAND.replacen("Finn: posts small blind", "Eve: posts small blind", 1);
+    assert_eq!(error(&heads_up).line, 7);
+
+    let truncated: String = CASH_HAND.lines().take(14).collect::<Vec<_>>().join("\n");
+    assert_eq!(error(&truncated).line, 14);
+    assert_eq!(error("hello").line, 1);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::*;
+use std::collections::HashSet;
+
+use texas_engine::equity::EquityOptions;
+use texas_engine::preflop::*;
+use texas_engine::*;
+
+#[test]
+fn test_class_names() {
+    let aks = StartingHand::from_cards(card("Ks"), card("As"));
+    assert_eq!(aks.name(), "AKs");
+    let seven_two = StartingHand::from_cards(card("2h"), card("7c"));
+    assert_eq!(seven_two.to_string(), "72o");
+    let tens = StartingHand::from_cards(card("Th"), card("Tc"));
+    assert_eq!(tens.name(), "TT");
+
+    assert_eq!("AKs".parse::<StartingHand>(), Ok(aks));
+    assert_eq!("27o".parse::<StartingHand>(), Ok(seven_two));
+    assert_eq!("TT".parse::<StartingHand>(), Ok(tens));
+    assert_eq!("AK".parse::<StartingHand>(), Err(GameError::InvalidCards));
+    assert_eq!("TTs".parse::<StartingHand>(), Err(GameError::InvalidCards));
+}
+
+#[test]
+fn test_all_classes_cover_every_combo() {
+    let all = StartingHand::all();
+    assert_eq!(all.len(), STARTING_HAND_COUNT);
+
+    let mut seen = HashSet::new();
+    for hand in &all {
+        let combos = hand.combos();
+        let expected = match (hand.is_pair(), hand.suited) {
+            (true, _) => 6,
+            (false, true) => 4,
+            (false, false) => 12,
+        };
+        assert_eq!(combos.len(), expected);
+        for (c1, c2) in combos {
+            assert_eq!(StartingHand::from_cards(c1, c2), *hand);
+            assert!(seen.insert(CardSet::from_cards(&[c1, c2])));
+        }
+    }
+    assert_eq!(seen.len(), 1326);
+}
+
+#[test]
+fn test_matrix_layout() {
+    assert_eq!(StartingHand::at(0, 0).name(), "AA");
+    assert_eq!(StartingHand::at(0, 1).name(), "AKs");
+    assert_eq!(StartingHand::at(1, 0).name(), "AKo");
+    assert_eq!(StartingHand::at(12, 12).name(), "22");
+    for (i, hand) in StartingHand::all().iter().enumerate() {
+        assert_eq!(hand.matrix_position(), (i / 13, i % 13));
+    }
+}
+
+#[test]
+fn test_equity_vs_random() {
+    let options = EquityOptions {
+        samples: 20_000,
+        ..Default::default()
+    };
+    let aces = equity_vs_random("AA".parse().unwrap(), 1, &options).unwrap();
+    let trash = equity_vs_random("72o".parse().unwrap(), 1, &options).unwrap();
+    // AA 对随机手牌约 85%，72o 约 35%
+    assert!((aces.equity - 85.2).abs() < 1.5);
+    assert!((trash.equity - 34.6).abs() < 1.5);
+
+    // 对手越多胜率越低
+    let aces_multiway = equity_vs_random("AA".parse().unwrap(), 5, &options).unwrap();
+    assert!((aces_multiway.equity - 49.0).abs() < 2.0);
+
+    assert_eq!(
+        equity_vs_random("AA".parse().unwrap(), 0, &options),
+        Err(GameError::InvalidCards)
+    );
+    assert_eq!(
+        equity_vs_random("AA".parse().unwrap(), 23, &options),
+        Err(GameError::InvalidCards)
+    );
+}
+
+#[test]
+fn test_equity_table() {
+    let table = equity_table(1).unwrap();
+    assert_eq!(table.len(), STARTING_HAND_COUNT);
+    for (i, (hand, equity)) in table.iter().enumerate() {
+        assert_eq!(hand.index(), i);
+        assert_eq!(hand.equity(1), Ok(*equity));
+    }
+
+    let best = table.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+    assert_eq!(best.0.name(), "AA");
+    let worst = table.iter().min_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+    assert_eq!(worst.0.name(), "32o");
+
+    // 对手越多胜率越低
+    let aces: StartingHand = "AA".parse().unwrap();
+    for opponents in 1..MAX_TABLE_OPPONENTS {
+        assert!(aces.equity(opponents).unwrap() > aces.equity(opponents + 1).unwrap());
+    }
+    assert_eq!(equity_table(0), Err(GameError::InvalidCards));
+    assert_eq!(
+        aces.equity(MAX_TABLE_OPPONENTS + 1),
+        Err(GameError::InvalidCards)
+    );
+}
+
+#[test]
+fn test_equity_table_matches_monte_carlo() {
+    let options = EquityOptions {
+        samples: 5_000,
+        seed: 7,
+        ..Default::default()
+    };
+    // 每隔 12 种起手牌抽查一次，覆盖对子、同花和非同花
+    for hand in StartingHand::all().into_iter().step_by(12) {
+        for opponents in [1, 3, MAX_TABLE_OPPONENTS] {
+            let simulated = equity_vs_random(hand, opponents, &options).unwrap();
+            let table = hand.equity(opponents).unwrap();
+            assert!(
+                (simulated.equity - table).abs() < 2.5,
+                "{hand} vs {opponents}: table {table}, simulated {}",
+                simulated.equity
+            );
+        }
+    }
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::*;
+use texas_engine::equity::*;
+use texas_engine::range::*;
+use texas_engine::*;
+
+fn classes(range: &Range) -> Vec<String> {
+    let mut names: Vec<String> = range
+        .combos
+        .iter()
+        .map(|c| format!("{}{}", c.cards.0.rank.to_char(), c.cards.1.rank.to_char()))
+        .collect();
+    names.dedup();
+    names
+}
+
+#[test]
+fn test_parse_basic_classes() {
+    assert_eq!(Range::parse("AA").unwrap().len(), 6);
+    assert_eq!(Range::parse("AKs").unwrap().len(), 4);
+    assert_eq!(Range::parse("AKo").unwrap().len(), 12);
+    assert_eq!(Range::parse("AK").unwrap().len(), 16);
+    assert_eq!(Range::parse("KA").unwrap(), Range::parse("AK").unwrap());
+
+    let range = Range::parse("AhKh").unwrap();
+    assert_eq!(range.len(), 1);
+    assert_eq!(range.combos[0].cards, (card("Ah"), card("Kh")));
+}
+
+#[test]
+fn test_parse_plus_and_dash() {
+    let range = Range::parse("TT+").unwrap();
+    assert_eq!(range.len(), 30);
+    assert_eq!(classes(&range), ["TT", "JJ", "QQ", "KK", "AA"]);
+
+    let range = Range::parse("A2s+").unwrap();
+    assert_eq!(range.len(), 48);
+
+    assert_eq!(Range::parse("22-55").unwrap().len(), 24);
+    assert_eq!(classes(&Range::parse("AQo-AJo").unwrap()), ["AJ", "AQ"]);
+    assert_eq!(
+        classes(&Range::parse("76s-54s").unwrap()),
+        ["76", "65", "54"]
+    );
+}
+
+#[test]
+fn test_parse_weights_and_overrides() {
+    let range = Range::parse("AKs, AhKh:0.5").unwrap();
+    assert_eq!(range.len(), 4);
+    assert!((range.weighted_len() - 3.5).abs() < 1e-9);
+
+    let range: Range = "QQ+:0.25 KK".parse().unwrap();
+    assert!((range.weighted_len() - (6.0 * 0.25 + 6.0 + 6.0 * 0.25)).abs() < 1e-9);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        Range::parse("AKx"),
+        Err(RangeParseError::InvalidToken(_))
+    ));
+    assert!(matches!(
+        Range::parse("AAs"),
+        Err(RangeParseError::InvalidToken(_))
+    ));
+    assert!(matches!(
+        Range::parse("AKs-QQ"),
+        Err(RangeParseError::InvalidToken(_))
+    ));
+    assert!(matches!(
+        Range::parse("AK:2"),
+        Err(RangeParseError::InvalidWeight(_))
+    ));
+    assert!(matches!(
+        Range::parse("AK:abc"),
+        Err(RangeParseError::InvalidWeight(_))
+    ));
+}
+
+#[test]
+fn test_remove_blocked() {
+    let mut range = Range::parse("AA").unwrap();
+    range.remove_blocked(CardSet::from(card("As")));
+    assert_eq!(range.len(), 3);
+}
+
+#[test]
+fn test_single_combo_ranges_match_hand_equity() {
+    let aces = (card("Ah"), card("As"));
+    let kings = (card("Kh"), card("Ks"));
+    let board = parse_cards("2c 7d Kc").unwrap();
+    let options = EquityOptions::default();
+
+    let expected = calculate_equity(&[aces, kings], &board, &[], &options).unwrap();
+    let actual =
+        hand_vs_range_equity(aces, &Range::from_combo(kings), &board, &[], &options).unwrap();
+    assert!(actual.exhaustive);
+    assert_eq!(actual.boards, expected.boards);
+    for (a, e) in actual.players.iter().zip(&expected.players) {
+        assert!((a.equity - e.equity).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_hand_vs_range_on_river() {
+    // 牌面 K7293，AA 对 KK/QQ：赢 QQ，输给成三条的 KK（剩余 3 组）
+    let aces = (card("Ah"), card("As"));
+    let board = parse_cards("Kc 7d 2h 9c 3s").unwrap();
+    let range = Range::parse("KK, QQ").unwrap();
+
+    let equity =
+        hand_vs_range_equity(aces, &range, &board, &[], &EquityOptions::default()).unwrap();
+    assert!(equity.exhaustive);
+    assert_eq!(equity.boards, 3 + 6);
+    assert!((equity.players[0].win - 600.0 / 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_range_vs_range_monte_carlo() {
+    let options = EquityOptions {
+        max_exhaustive: 0,
+        samples: 20_000,
+        seed: 7,
+    };
+    let ranges = [Range::parse("AA").unwrap(), Range::parse("KK").unwrap()];
+
+    let equity = range_equity(&ranges, &[], &[], &options).unwrap();
+    assert!(!equity.exhaustive);
+    assert_eq!(equity.boards, 20_000);
+    // AA 对 KK 约 82%
+    assert!((equity.players[0].equity - 82.0).abs() < 2.0);
+    let total: f64 = equity.players.iter().map(|p| p.equity).sum();
+    assert!((total - 100.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_range_equity_rejects_empty_range() {
+    let board = parse_cards("Ah As Ac").unwrap();
+    let ranges = [Range::parse("AA").unwrap(), Range::parse("KK").unwrap()];
+
+    assert_eq!(
+        range_equity(&ranges, &board, &[], &EquityOptions::default()),
+        Err(GameError::InvalidCards)
+    );
+}
+
+#[test]
+fn test_range_equity_rejects_too_many_players() {
+    // 30 名玩家的底牌就超过一副牌
+    let ranges = vec![Range::parse("22+").unwrap(); 30];
+    assert_eq!(
+        range_equity(&ranges, &[], &[], &EquityOptions::default()),
+        Err(GameError::InvalidCards)
+    );
+
+    let ranges = [Range::parse("AA").unwrap(), Range::parse("KK").unwrap()];
+    let dead: Vec<Card> = parse_cards("2c 2d 2h 2s 3c 3d 3h 3s 4c 4d 4h 4s 5c 5d 5h 5s")
+        .unwrap()
+        .into_iter()
+        .chain(
+            CardSet::full()
+                .iter()
+                .filter(|c| (6..=12).contains(&c.rank.value())),
+        )
+        .collect();
+    assert_eq!(
+        range_equity(&ranges, &[], &dead, &EquityOptions::default()),
+        Err(GameError::InvalidCards)
+    );
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::*;
+use texas_engine::history::*;
+use texas_engine::replay::*;
+use texas_engine::*;
+
+fn seat(id: &str) -> SeatInfo {
+    SeatInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        chips: 1000,
+    }
+}
+
+fn setup(deck: DeckSetup) -> HandSetup {
+    HandSetup {
+        players: vec![seat("a"), seat("b"), seat("c")],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck,
+    }
+}
+
+fn action(player_id: &str, action: PlayerAction) -> RecordedAction {
+    RecordedAction {
+        player_id: player_id.to_string(),
+        action,
+    }
+}
+
+fn hand_actions() -> Vec<RecordedAction> {
+    vec![
+        action("a", PlayerAction::Raise(40)),
+        action("b", PlayerAction::Call),
+        action("c", PlayerAction::Fold),
+        action("b", PlayerAction::Bet(100)),
+        action("a", PlayerAction::Call),
+        action("b", PlayerAction::Check),
+        action("a", PlayerAction::Check),
+    ]
+}
+
+fn chips(game: &TexasHoldem) -> Vec<u32> {
+    game.state.players.iter().map(|p| p.chips).collect()
+}
+
+#[test]
+fn test_seeded_setup_is_deterministic() {
+    let first = setup(DeckSetup::Seed(7)).start().unwrap();
+    let second = setup(DeckSetup::Seed(7)).start().unwrap();
+    assert_eq!(first.deck, second.deck);
+    assert_eq!(first.history, second.history);
+
+    let other = setup(DeckSetup::Seed(8)).start().unwrap();
+    assert_ne!(first.deck, other.deck);
+}
+
+#[test]
+fn test_deck_order_deals_in_sequence() {
+    let order = parse_cards("As Ks Qs Js Ts 9s 2h 3h 4h 5h 6h").unwrap();
+    let game = setup(DeckSetup::Order(order)).start().unwrap();
+    let hole: Vec<_> = game.state.players.iter().map(|p| p.cards).collect();
+    assert_eq!(
+        hole,
+        vec![
+            Some((card("As"), card("Ks"))),
+            Some((card("Qs"), card("Js"))),
+            Some((card("Ts"), card("9s"))),
+        ]
+    );
+    assert_eq!(game.deck.len(), 52 - 6);
+
+    let duplicate = parse_cards("As Ks").unwrap().repeat(2);
+    assert!(matches!(
+        setup(DeckSetup::Order(duplicate)).start(),
+        Err(GameError::InvalidCards)
+    ));
+}
+
+#[test]
+fn test_step_forward_and_back() {
+    let mut replay = Replay::new(setup(DeckSetup::Seed(1)), hand_actions()).unwrap();
+    let initial_pot = replay.game().state.pot;
+    assert_eq!(initial_pot, 30);
+    assert_eq!(replay.step_back(), Ok(false));
+
+    while replay.step_forward().unwrap() {}
+    assert!(replay.is_finished());
+    assert_eq!(replay.position(), 7);
+    assert_eq!(replay.game().state.stage, GameStage::River);
+    assert_eq!(replay.game().state.pot, 60 + 60 + 20 + 200);
+
+    // 退回到翻牌圈的下注之前
+    assert_eq!(replay.step_back(), Ok(true));
+    replay.seek(3).unwrap();
+    assert_eq!(replay.position(), 3);
+    assert_eq!(replay.game().state.stage, GameStage::Flop);
+    assert_eq!(replay.game().state.pot, 140);
+    assert_eq!(replay.game().state.community_cards.len(), 3);
+
+    replay.seek(0).unwrap();
+    assert_eq!(replay.game().state.pot, initial_pot);
+    assert_eq!(replay.seek(8), Err(GameError::InvalidAction));
+}
+
+#[test]
+fn test_invalid_action_is_reported() {
+    let actions = vec![action("b", PlayerAction::Call)];
+    let mut replay = Replay::new(setup(DeckSetup::Seed(1)), actions).unwrap();
+    assert_eq!(replay.step_forward(), Err(GameError::NotYourTurn));
+    assert_eq!(replay.position(), 0);
+}
+
+#[test]
+fn test_replay_recorded_hand() {
+    let players = ["a", "b"]
+        .iter()
+        .map(|id| Player {
+            id: id.to_string(),
+            name: id.to_string(),
+            chips: 1000,
+            cards: None,
+            is_active: true,
+            current_bet: 0,
+            has_acted: false,
+            total_bet_in_hand: 0,
+        })
+        .collect();
+    let mut game = TexasHoldem::with_variant(players, 10, 20, GameVariant::Pineapple);
+
+    // 一路过牌到河牌，翻牌前每人弃掉第三张底牌
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+    for _ in 0..2 {
+        let index = game.state.current_player_index;
+        let extra = game.state.extra_hole_cards[index].unwrap();
+        game.handle_action(PlayerAction::Discard(extra)).unwrap();
+    }
+    while game.state.stage != GameStage::Showdown {
+        game.handle_action(PlayerAction::Check).unwrap();
+    }
+    game.resolve_showdown();
+
+    let mut replay = Replay::from_history(game.current_hand()).unwrap();
+    assert_eq!(replay.actions.len(), 10);
+    replay.seek(replay.actions.len()).unwrap();
+    assert_eq!(
+        replay.game().state.community_cards,
+        game.state.community_cards
+    );
+
+    // 重新执行整手牌并结算，事件记录与原来一致
+    let mut result = replay.setup.start().unwrap();
+    for recorded in &replay.actions {
+        result
+            .handle_action_for(&recorded.player_id, recorded.action.clone())
+            .unwrap();
+    }
+    result.resolve_showdown();
+    assert_eq!(chips(&result), chips(&game));
+    assert_eq!(result.history, game.current_hand());
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use common::*;
+use texas_engine::session::{Rotation, Session};
+use texas_engine::view::LegalAction;
+use texas_engine::*;
+
+fn games() -> Vec<GameConfig> {
+    vec![
+        GameConfig {
+            variant: GameVariant::Holdem,
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        GameConfig {
+            variant: GameVariant::Pineapple,
+            small_blind: 25,
+            big_blind: 50,
+            ante: 5,
+            ..Default::default()
+        },
+    ]
+}
+
+/// 当前行动玩家弃牌直到只剩一人，然后结束这一手
+fn fold_out(session: &mut Session) {
+    let hand = session.hand.as_mut().unwrap();
+    while hand.state.stage != GameStage::Showdown {
+        hand.handle_action(PlayerAction::Fold).unwrap();
+    }
+    session.finish_hand().unwrap();
+}
+
+#[test]
+fn test_rotation_every_n_hands() {
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    let mut session = Session::new(players, games(), Rotation::Hands(2)).unwrap();
+
+    let hand = session.start_hand().unwrap();
+    assert_eq!(hand.state.variant, GameVariant::Holdem);
+    assert_eq!(hand.state.pot, 30);
+    assert_eq!(session.start_hand().err(), Some(GameError::StageError));
+    fold_out(&mut session);
+    assert_eq!(session.dealer_position, 1);
+
+    session.start_hand().unwrap();
+    fold_out(&mut session);
+    assert_eq!(session.current_game().variant, GameVariant::Pineapple);
+
+    // 新玩法的前注和盲注
+    let hand = session.start_hand().unwrap();
+    assert_eq!(hand.state.variant, GameVariant::Pineapple);
+    assert_eq!(hand.state.dealer_position, 2);
+    assert_eq!(hand.state.pot, 3 * 5 + 25 + 50);
+    fold_out(&mut session);
+
+    // 筹码跨手保留
+    let total: u32 = session.players.iter().map(|p| p.chips).sum();
+    assert_eq!(total, 3000);
+    assert_eq!(session.hands_played, 3);
+}
+
+#[test]
+fn test_rotation_every_orbit() {
+    let players = vec![player("a", 1000), player("b", 1000)];
+    let mut session = Session::new(players, games(), Rotation::Orbits(1)).unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(session.current_game().variant, GameVariant::Holdem);
+        session.start_hand().unwrap();
+        fold_out(&mut session);
+    }
+    assert_eq!(session.current_game().variant, GameVariant::Pineapple);
+}
+
+#[test]
+fn test_busted_player_is_skipped() {
+    let players = vec![player("a", 1000), player("b", 0), player("c", 1000)];
+    let mut session = Session::new(players, games(), Rotation::Hands(10)).unwrap();
+
+    let hand = session.start_hand().unwrap();
+    assert_eq!(hand.state.players.len(), 2);
+    fold_out(&mut session);
+    assert_eq!(session.dealer_position, 2);
+}
+
+#[test]
+fn test_dealers_choice() {
+    let players = vec![player("a", 1000), player("b", 1000)];
+    let mut session = Session::new(players, games(), Rotation::DealersChoice).unwrap();
+
+    // 只有庄家可以选择玩法
+    assert_eq!(session.choose_game("b", 1), Err(GameError::InvalidAction));
+    assert_eq!(session.choose_game("x", 1), Err(GameError::PlayerNotFound));
+    assert_eq!(session.choose_game("a", 2), Err(GameError::InvalidAction));
+    session.choose_game("a", 1).unwrap();
+
+    let hand = session.start_hand().unwrap();
+    assert_eq!(hand.state.variant, GameVariant::Pineapple);
+    assert_eq!(session.choose_game("a", 0), Err(GameError::StageError));
+    fold_out(&mut session);
+
+    // 玩法保持到下一位庄家重新选择
+    assert_eq!(session.current_game().variant, GameVariant::Pineapple);
+    session.choose_game("b", 0).unwrap();
+    assert_eq!(session.current_game().variant, GameVariant::Holdem);
+}
+
+fn structured_hand(betting: BettingStructure) -> TexasHoldem {
+    let config = GameConfig {
+        betting,
+        small_blind: 10,
+        big_blind: 20,
+        ..Default::default()
+    };
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 1000)];
+    TexasHoldem::with_config(players, &config, 0)
+}
+
+#[test]
+fn test_pot_limit_caps_raises() {
+    let mut hand = structured_hand(BettingStructure::PotLimit);
+    // 底池 30，跟注 20 后为 50，最多加注 50
+    assert_eq!(
+        hand.handle_action(PlayerAction::Raise(51)),
+        Err(GameError::InvalidAction)
+    );
+    hand.handle_action(PlayerAction::Raise(50)).unwrap();
+
+    // 小盲面对 70：底池 100 加跟注 60
+    assert!(
+        hand.state
+            .legal_actions(1)
+            .contains(&LegalAction::Raise { min: 20, max: 160 })
+    );
+    assert_eq!(
+        hand.handle_action(PlayerAction::Raise(161)),
+        Err(GameError::InvalidAction)
+    );
+}
+
+#[test]
+fn test_fixed_limit_sizes_and_cap() {
+    let mut hand = structured_hand(BettingStructure::FixedLimit);
+    assert_eq!(
+        hand.handle_action(PlayerAction::Raise(40)),
+        Err(GameError::InvalidAction)
+    );
+    hand.handle_action(PlayerAction::Raise(20)).unwrap();
+    hand.handle_action(PlayerAction::Raise(20)).unwrap();
+    hand.handle_action(PlayerAction::Raise(20)).unwrap();
+
+    // 大盲加三次加注后封顶，只能跟注或弃牌
+    assert!(
+        !hand
+            .state
+            .legal_actions(0)
+            .iter()
+            .any(|a| matches!(a, LegalAction::Raise { .. }))
+    );
+    assert_eq!(
+        hand.handle_action(PlayerAction::Raise(20)),
+        Err(GameError::InvalidAction)
+    );
+    hand.handle_action(PlayerAction::Call).unwrap();
+    hand.handle_action(PlayerAction::Call).unwrap();
+
+    // 翻牌圈一个大盲，转牌两个大盲
+    assert_eq!(hand.state.stage, GameStage::Flop);
+    let first = hand.state.current_player_index;
+    assert!(
+        hand.state
+            .legal_actions(first)
+            .contains(&LegalAction::Bet { min: 20, max: 20 })
+    );
+    hand.handle_action(PlayerAction::Check).unwrap();
+    hand.handle_action(PlayerAction::Check).unwrap();
+    hand.handle_action(PlayerAction::Check).unwrap();
+    assert_eq!(hand.state.stage, GameStage::Turn);
+    assert_eq!(
+        hand.handle_action(PlayerAction::Bet(20)),
+        Err(GameError::InvalidAction)
+    );
+    hand.handle_action(PlayerAction::Bet(40)).unwrap();
+}
+
+#[test]
+fn test_fixed_limit_short_stack_goes_all_in() {
+    let config = GameConfig {
+        betting: BettingStructure::FixedLimit,
+        small_blind: 10,
+        big_blind: 20,
+        ..Default::default()
+    };
+    let players = vec![player("a", 1000), player("b", 1000), player("c", 30)];
+    let mut hand = TexasHoldem::with_config(players, &config, 0);
+    hand.handle_action(PlayerAction::Call).unwrap();
+    hand.handle_action(PlayerAction::Call).unwrap();
+
+    // 大盲只剩 10，不够加注 20，可以全下加注 10
+    assert_eq!(
+        hand.state.legal_actions(2),
+        vec![
+            LegalAction::Fold,
+            LegalAction::Check,
+            LegalAction::Raise { min: 10, max: 10 },
+        ]
+    );
+    assert_eq!(
+        hand.handle_action(PlayerAction::Raise(5)),
+        Err(GameError::InvalidAction)
+    );
+    hand.handle_action(PlayerAction::Raise(10)).unwrap();
+    assert_eq!(hand.state.players[2].chips, 0);
+    assert_eq!(hand.state.players[2].current_bet, 30);
+
+    // 其他玩家重新行动，可以按固定额加注
+    assert!(
+        hand.state
+            .legal_actions(0)
+            .contains(&LegalAction::Raise { min: 20, max: 20 })
+    );
+}
+
+#[test]
+fn test_session_switches_betting_structure() {
+    let mut limit_games = games();
+    limit_games[1].betting = BettingStructure::FixedLimit;
+    let players = vec![player("a", 1000), player("b", 1000)];
+    let mut session = Session::new(players, limit_games, Rotation::Hands(1)).unwrap();
+
+    assert_eq!(
+        session.start_hand().unwrap().state.betting,
+        BettingStructure::NoLimit
+    );
+    fold_out(&mut session);
+    assert_eq!(
+        session.start_hand().unwrap().state.betting,
+        BettingStructure::FixedLimit
+    );
+}
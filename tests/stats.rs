@@ -0,0 +1,166 @@
+use texas_engine::history::*;
+use texas_engine::replay::*;
+use texas_engine::stats::*;
+use texas_engine::*;
+
+fn seat(id: &str) -> SeatInfo {
+    SeatInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        chips: 1000,
+    }
+}
+
+/// 按给定牌序和行动打完一手牌，庄家为 a，b 小盲，c 大盲
+fn play(game: Option<TexasHoldem>, actions: &[(&str, PlayerAction)]) -> TexasHoldem {
+    let setup = HandSetup {
+        players: vec![seat("a"), seat("b"), seat("c")],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut next = setup.start().unwrap();
+    if let Some(previous) = game {
+        let mut history = previous.history;
+        history.extend(next.history);
+        next.history = history;
+    }
+    for (player_id, action) in actions {
+        next.handle_action_for(player_id, action.clone()).unwrap();
+    }
+    next.resolve_showdown();
+    next
+}
+
+#[test]
+fn test_showdown_hand_stats() {
+    let game = play(
+        None,
+        &[
+            ("a", PlayerAction::Raise(40)),
+            ("b", PlayerAction::Call),
+            ("c", PlayerAction::Fold),
+            ("b", PlayerAction::Bet(100)),
+            ("a", PlayerAction::Call),
+            ("b", PlayerAction::Check),
+            ("a", PlayerAction::Check),
+            ("b", PlayerAction::Bet(50)),
+            ("a", PlayerAction::Call),
+        ],
+    );
+    let mut store = StatsStore::new();
+    store.record_hand(game.current_hand());
+
+    let a = store.get("a").unwrap();
+    assert_eq!((a.hands, a.vpip, a.pfr), (1, 1, 1));
+    assert_eq!((a.three_bet_opportunities, a.three_bets), (0, 0));
+    assert_eq!((a.postflop_aggressions, a.postflop_calls), (0, 2));
+    assert_eq!(
+        (a.saw_flop, a.went_to_showdown, a.won_at_showdown),
+        (1, 1, 1)
+    );
+
+    let b = store.get("b").unwrap();
+    assert_eq!((b.vpip, b.pfr, b.three_bet_opportunities), (1, 0, 1));
+    assert_eq!(b.af(), None);
+    assert_eq!(
+        (b.saw_flop, b.went_to_showdown, b.won_at_showdown),
+        (1, 1, 0)
+    );
+
+    let c = store.hud("c").unwrap();
+    assert_eq!(c.hands, 1);
+    assert_eq!(
+        (c.vpip, c.pfr, c.three_bet),
+        (Some(0.0), Some(0.0), Some(0.0))
+    );
+    assert_eq!((c.wtsd, c.wsd), (None, None));
+}
+
+#[test]
+fn test_history_accumulates_and_serializes() {
+    let first = play(
+        None,
+        &[
+            ("a", PlayerAction::Raise(40)),
+            ("b", PlayerAction::Raise(100)),
+            ("c", PlayerAction::Fold),
+            ("a", PlayerAction::Fold),
+        ],
+    );
+    let game = play(
+        Some(first),
+        &[("a", PlayerAction::Fold), ("b", PlayerAction::Fold)],
+    );
+    let mut store = StatsStore::new();
+    store.record_history(&game.history);
+
+    let b = store.hud("b").unwrap();
+    assert_eq!(b.hands, 2);
+    assert_eq!(
+        (b.vpip, b.pfr, b.three_bet),
+        (Some(50.0), Some(50.0), Some(100.0))
+    );
+    let a = store.get("a").unwrap();
+    assert_eq!((a.pfr, a.three_bet_opportunities), (1, 0));
+    // 不战而胜不算摊牌
+    assert_eq!(store.get("c").unwrap().went_to_showdown, 0);
+
+    let json = serde_json::to_string(&store).unwrap();
+    let mut restored: StatsStore = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, store);
+    restored.merge(&store);
+    assert_eq!(restored.get("b").unwrap().hands, 4);
+    assert_eq!(restored.hud("b").unwrap().pfr, Some(50.0));
+}
+
+#[test]
+fn test_returned_bet_is_not_a_showdown_win() {
+    // b 只有 100，跟注 c 的下注后全下；c 输掉摊牌，多下的 420 退还给 c
+    let setup = HandSetup {
+        players: vec![
+            seat("a"),
+            SeatInfo {
+                chips: 100,
+                ..seat("b")
+            },
+            seat("c"),
+        ],
+        config: GameConfig {
+            small_blind: 10,
+            big_blind: 20,
+            ..Default::default()
+        },
+        dealer_position: 0,
+        deck: DeckSetup::Order(parse_cards("As Ad Kh Kd 7c 2d Qs 9h 5c Jd 8s").unwrap()),
+    };
+    let mut game = setup.start().unwrap();
+    let actions = [
+        ("a", PlayerAction::Fold),
+        ("b", PlayerAction::Call),
+        ("c", PlayerAction::Check),
+        ("b", PlayerAction::Check),
+        ("c", PlayerAction::Bet(500)),
+        ("b", PlayerAction::Call),
+    ];
+    for (player_id, action) in actions {
+        game.handle_action_for(player_id, action).unwrap();
+    }
+    game.resolve_showdown();
+    assert!(game.current_hand().contains(&HandEvent::BetReturned {
+        player_id: "c".to_string(),
+        amount: 420,
+    }));
+    assert_eq!(game.state.players[2].chips, 900);
+
+    let mut store = StatsStore::new();
+    store.record_hand(game.current_hand());
+    let c = store.get("c").unwrap();
+    assert_eq!((c.went_to_showdown, c.won_at_showdown), (1, 0));
+    let b = store.get("b").unwrap();
+    assert_eq!((b.went_to_showdown, b.won_at_showdown), (1, 1));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::*;
+use texas_engine::texture::*;
+use texas_engine::*;
+
+#[test]
+fn test_dry_rainbow_flop() {
+    let board = parse_cards("Kh 7d 2c").unwrap();
+
+    let texture = analyze_board(&board).unwrap();
+    assert_eq!(texture.pairing, BoardPairing::Unpaired);
+    assert_eq!(texture.suits, SuitTexture::Rainbow);
+    assert_eq!(texture.connectedness, 1);
+    assert!(!texture.is_connected());
+    assert!(!texture.straight_possible());
+    assert!(!texture.flush_possible());
+    // 没有顺子和同花，最大是三条
+    assert_eq!(
+        texture.possible_hands,
+        vec![
+            HandRank::HighCard,
+            HandRank::OnePair,
+            HandRank::TwoPair,
+            HandRank::ThreeOfAKind
+        ]
+    );
+    assert_eq!(texture.nut_hand, HandRank::ThreeOfAKind);
+    assert_eq!(texture.to_string(), "Unpaired, rainbow, disconnected");
+}
+
+#[test]
+fn test_wet_monotone_flop() {
+    let board = parse_cards("Js Ts 9s").unwrap();
+
+    let texture = analyze_board(&board).unwrap();
+    assert_eq!(texture.suits, SuitTexture::Monotone);
+    assert_eq!(texture.flush_suit, Some(Suit::Spades));
+    assert_eq!(texture.nut_flush_card, Some(Rank::Ace));
+    assert_eq!(texture.highest_straight, Some(Rank::King));
+    assert!(texture.possible_hands.contains(&HandRank::Straight));
+    assert!(texture.possible_hands.contains(&HandRank::Flush));
+    assert_eq!(texture.nut_hand, HandRank::StraightFlush);
+}
+
+#[test]
+fn test_paired_and_wheel_boards() {
+    let board = parse_cards("Ah Ad 4c 4h").unwrap();
+    let texture = analyze_board(&board).unwrap();
+    assert_eq!(texture.pairing, BoardPairing::TwoPaired);
+    assert_eq!(texture.suits, SuitTexture::TwoTone);
+    assert_eq!(texture.highest_straight, None);
+    assert_eq!(texture.connectedness, 2);
+    assert_eq!(texture.nut_hand, HandRank::FourOfAKind);
+
+    // A 可以作为最小的牌组成 5 高顺子
+    let board = parse_cards("Ah Ad 4c 3h").unwrap();
+    let texture = analyze_board(&board).unwrap();
+    assert_eq!(texture.pairing, BoardPairing::Paired);
+    assert_eq!(texture.highest_straight, Some(Rank::Five));
+    assert_eq!(texture.connectedness, 3);
+
+    let board = parse_cards("9h 9d 9c").unwrap();
+    let texture = analyze_board(&board).unwrap();
+    assert_eq!(texture.pairing, BoardPairing::Trips);
+    assert!(!texture.possible_hands.contains(&HandRank::HighCard));
+}
+
+#[test]
+fn test_invalid_board() {
+    let ace = card("Ah");
+    assert_eq!(analyze_board(&[ace]), Err(GameError::InvalidCards));
+    assert_eq!(
+        analyze_board(&[ace, ace, card("2c")]),
+        Err(GameError::InvalidCards)
+    );
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::*;
+use texas_engine::view::*;
+use texas_engine::*;
+
+#[test]
+fn test_view_hides_other_players_cards() {
+    let game = new_game();
+    let view = game.state.view_for("b").unwrap();
+
+    assert_eq!(view.player_id, "b");
+    assert_eq!(view.seat, 1);
+    assert_eq!(view.hole_cards, game.state.players[1].cards);
+    assert_eq!(view.players[1].cards, game.state.players[1].cards);
+    for i in [0, 2] {
+        assert!(view.players[i].has_cards);
+        assert_eq!(view.players[i].cards, None);
+    }
+    assert_eq!(view.pot, game.state.pot);
+    assert_eq!(view.players[2].current_bet, 20);
+    assert!(view.board.is_empty());
+
+    assert_eq!(game.state.view_for("x"), Err(GameError::PlayerNotFound));
+}
+
+#[test]
+fn test_legal_actions_follow_betting_rules() {
+    let mut game = new_game();
+    let first = game.state.current_player_index;
+    let id = game.state.players[first].id.clone();
+
+    // 翻牌前面对大盲：弃牌、跟注 20 或加注
+    let view = game.state.view_for(&id).unwrap();
+    assert!(view.is_my_turn());
+    assert_eq!(
+        view.legal_actions,
+        vec![
+            LegalAction::Fold,
+            LegalAction::Call { amount: 20 },
+            LegalAction::Raise { min: 20, max: 980 },
+        ]
+    );
+
+    // 其他玩家不能行动
+    let other = &game.state.players[(first + 1) % 3].id;
+    assert!(game.state.view_for(other).unwrap().legal_actions.is_empty());
+
+    // 所有人跟到大盲，大盲可以过牌
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Call).unwrap();
+    let big_blind = game.state.current_player_index;
+    let view = game
+        .state
+        .view_for(&game.state.players[big_blind].id.clone())
+        .unwrap();
+    assert!(view.legal_actions.contains(&LegalAction::Check));
+    assert!(
+        !view
+            .legal_actions
+            .iter()
+            .any(|a| matches!(a, LegalAction::Call { .. }))
+    );
+}
+
+#[test]
+fn test_bet_available_on_new_street() {
+    let mut game = new_game();
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+    assert_eq!(game.state.stage, GameStage::Flop);
+
+    let index = game.state.current_player_index;
+    let view = game
+        .state
+        .view_for(&game.state.players[index].id.clone())
+        .unwrap();
+    assert_eq!(view.board.len(), 3);
+    assert_eq!(
+        view.legal_actions,
+        vec![
+            LegalAction::Fold,
+            LegalAction::Check,
+            LegalAction::Bet { min: 20, max: 980 },
+        ]
+    );
+}
+
+#[test]
+fn test_showdown_reveals_active_hands() {
+    let mut game = new_game();
+    game.handle_action(PlayerAction::Fold).unwrap();
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+    for _ in 0..3 {
+        game.handle_action(PlayerAction::Check).unwrap();
+        game.handle_action(PlayerAction::Check).unwrap();
+    }
+    assert_eq!(game.state.stage, GameStage::Showdown);
+
+    let folded = game
+        .state
+        .players
+        .iter()
+        .position(|p| !p.is_active)
+        .unwrap();
+    let viewer = &game.state.players[(folded + 1) % 3].id;
+    let view = game.state.view_for(viewer).unwrap();
+    for (i, seat) in view.players.iter().enumerate() {
+        assert_eq!(seat.cards.is_some(), i != folded);
+    }
+    assert!(view.legal_actions.is_empty());
+}
+
+#[test]
+fn test_pineapple_discard_action() {
+    let mut game = TexasHoldem::with_variant(
+        vec![player("a", 1000), player("b", 1000)],
+        10,
+        20,
+        GameVariant::Pineapple,
+    );
+    game.handle_action(PlayerAction::Call).unwrap();
+    game.handle_action(PlayerAction::Check).unwrap();
+    assert!(game.awaiting_discard());
+
+    let index = game.state.current_player_index;
+    let view = game
+        .state
+        .view_for(&game.state.players[index].id.clone())
+        .unwrap();
+    let (c1, c2) = view.hole_cards.unwrap();
+    assert_eq!(
+        view.legal_actions,
+        vec![LegalAction::Discard {
+            cards: vec![c1, c2, view.extra_hole_card.unwrap()]
+        }]
+    );
+}
+
+#[test]
+fn test_handle_action_for_checks_turn() {
+    let mut game = new_game();
+    let current = game.state.current_player_index;
+    let current_id = game.state.players[current].id.clone();
+    let other_id = game.state.players[(current + 1) % 3].id.clone();
+
+    assert_eq!(
+        game.handle_action_for(&other_id, PlayerAction::Fold),
+        Err(GameError::NotYourTurn)
+    );
+    assert!(game.state.players.iter().all(|p| p.is_active));
+    assert_eq!(
+        game.handle_action_for("nobody", PlayerAction::Fold),
+        Err(GameError::PlayerNotFound)
+    );
+
+    game.handle_action_for(&current_id, PlayerAction::Call)
+        .unwrap();
+    assert_eq!(game.state.current_player_index, (current + 1) % 3);
+    assert_eq!(
+        game.handle_action_for(&current_id, PlayerAction::Call),
+        Err(GameError::NotYourTurn)
+    );
+}